@@ -0,0 +1,397 @@
+// Compares the executors on spawn latency, spawn + join throughput, the cost of cancel on drop and of detach,
+// and the memory per task, both through Spawn and SpawnHandle. With the tracing feature, the same is measured
+// for executors wrapped in Instrumented.
+//
+// Run with: `ci/bench.bash`, or pass the features of the executors you want to compare:
+// `cargo bench --features "tokio_ct tokio_tp async_std async_global threadpool localpool native tracing" --bench executors`
+//
+// Criterion writes a report to `target/criterion/report/index.html`. Use `-- --save-baseline <name>` and
+// `-- --baseline <name>` to compare runs. The memory per task is printed as a table before the timings.
+//
+#![ allow( dead_code, unused_imports ) ] // depends on the enabled executors.
+
+use
+{
+	async_executors :: { *                                                                 } ,
+	criterion       :: { criterion_group, criterion_main, Criterion, black_box            } ,
+	futures         :: { channel::{ mpsc, oneshot }, future::{ pending, join_all }, StreamExt } ,
+	futures         :: { task::{ Spawn, SpawnExt }, executor::LocalPool                    } ,
+	std             :: { alloc::{ GlobalAlloc, Layout, System }, future::Future            } ,
+	std             :: { sync::atomic::{ AtomicIsize, Ordering::Relaxed }                 } ,
+	std             :: { time::{ Duration, Instant }                                      } ,
+};
+
+
+// The number of tasks spawned per iteration for the throughput, cancel, detach and memory benchmarks.
+//
+const TASKS: usize = 1000;
+
+
+
+// Keeps track of the bytes in use on the heap, to measure memory per task.
+//
+struct Counting;
+
+static IN_USE: AtomicIsize = AtomicIsize::new(0);
+
+unsafe impl GlobalAlloc for Counting
+{
+	unsafe fn alloc( &self, layout: Layout ) -> *mut u8
+	{
+		IN_USE.fetch_add( layout.size() as isize, Relaxed );
+		System.alloc( layout )
+	}
+
+	unsafe fn dealloc( &self, ptr: *mut u8, layout: Layout )
+	{
+		IN_USE.fetch_sub( layout.size() as isize, Relaxed );
+		System.dealloc( ptr, layout )
+	}
+}
+
+#[ global_allocator ]
+//
+static ALLOC: Counting = Counting;
+
+
+
+// What the benchmarks need from an executor.
+//
+trait Backend
+{
+	type Exec: Spawn + SpawnHandle<()> + SpawnHandle<Instant> + 'static;
+
+	fn exec( &self ) -> Self::Exec;
+
+	fn block_on<F: Future>( &mut self, future: F ) -> F::Output;
+}
+
+
+#[ cfg( feature = "tokio_ct" ) ]
+//
+impl Backend for TokioCt
+{
+	type Exec = TokioCt;
+
+	fn exec    ( &self                            ) -> TokioCt   { self.clone()                  }
+	fn block_on<F: Future>( &mut self, future: F ) -> F::Output { TokioCt::block_on( self, future ) }
+}
+
+
+#[ cfg( feature = "tokio_tp" ) ]
+//
+impl Backend for TokioTp
+{
+	type Exec = TokioTp;
+
+	fn exec    ( &self                            ) -> TokioTp   { self.clone()                  }
+	fn block_on<F: Future>( &mut self, future: F ) -> F::Output { TokioTp::block_on( self, future ) }
+}
+
+
+#[ cfg( feature = "async_std" ) ]
+//
+impl Backend for AsyncStd
+{
+	type Exec = AsyncStd;
+
+	fn exec    ( &self                            ) -> AsyncStd  { AsyncStd                      }
+	fn block_on<F: Future>( &mut self, future: F ) -> F::Output { AsyncStd::block_on( future )  }
+}
+
+
+#[ cfg( feature = "async_global" ) ]
+//
+impl Backend for AsyncGlobal
+{
+	type Exec = AsyncGlobal;
+
+	fn exec    ( &self                            ) -> AsyncGlobal { AsyncGlobal                    }
+	fn block_on<F: Future>( &mut self, future: F ) -> F::Output   { AsyncGlobal::block_on( future ) }
+}
+
+
+#[ cfg( feature = "threadpool" ) ]
+//
+impl Backend for ThreadPool
+{
+	type Exec = ThreadPool;
+
+	fn exec    ( &self                            ) -> ThreadPool { self.clone()                           }
+	fn block_on<F: Future>( &mut self, future: F ) -> F::Output  { futures::executor::block_on( future )  }
+}
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+impl Backend for LocalPool
+{
+	type Exec = LocalSpawner;
+
+	fn exec    ( &self                            ) -> LocalSpawner { self.spawner()          }
+	fn block_on<F: Future>( &mut self, future: F ) -> F::Output    { self.run_until( future ) }
+}
+
+
+#[ cfg( feature = "native" ) ]
+//
+impl Backend for Native
+{
+	type Exec = Native;
+
+	fn exec    ( &self                            ) -> Native    { self.clone()                    }
+	fn block_on<F: Future>( &mut self, future: F ) -> F::Output { Native::block_on( self, future ) }
+}
+
+
+#[ cfg( feature = "glommio" ) ]
+//
+impl Backend for GlommioCt
+{
+	type Exec = GlommioCt;
+
+	fn exec    ( &self                            ) -> GlommioCt { self.clone()                      }
+	fn block_on<F: Future>( &mut self, future: F ) -> F::Output { GlommioCt::block_on( self, future ) }
+}
+
+
+// Measures an executor wrapped in Instrumented.
+//
+#[ cfg( feature = "tracing" ) ]
+//
+struct Traced<B>( B );
+
+#[ cfg( feature = "tracing" ) ]
+//
+impl<B: Backend> Backend for Traced<B>
+{
+	type Exec = tracing_futures::Instrumented<B::Exec>;
+
+	fn exec( &self ) -> Self::Exec
+	{
+		use tracing_futures::Instrument;
+
+		self.0.exec().instrument( tracing_crate::info_span!( "bench" ) )
+	}
+
+	fn block_on<F: Future>( &mut self, future: F ) -> F::Output { self.0.block_on( future ) }
+}
+
+
+
+// The time from spawning a task until it runs.
+//
+async fn spawn_latency( exec: &impl Spawn, iters: u64 ) -> Duration
+{
+	let mut total = Duration::default();
+
+	for _ in 0..iters
+	{
+		let (tx, rx) = oneshot::channel();
+		let start    = Instant::now();
+
+		exec.spawn( async move { let _ = tx.send( Instant::now() ); } ).expect( "spawn" );
+
+		total += rx.await.expect( "receive" ) - start;
+	}
+
+	total
+}
+
+
+async fn handle_latency( exec: &impl SpawnHandle<Instant>, iters: u64 ) -> Duration
+{
+	let mut total = Duration::default();
+
+	for _ in 0..iters
+	{
+		let start = Instant::now();
+
+		total += exec.spawn_handle( async { Instant::now() } ).expect( "spawn" ).await - start;
+	}
+
+	total
+}
+
+
+// Spawn TASKS tasks and wait for all of them to finish.
+//
+async fn spawn_throughput( exec: &impl Spawn )
+{
+	let (tx, rx) = mpsc::unbounded();
+
+	for _ in 0..TASKS
+	{
+		let tx = tx.clone();
+
+		exec.spawn( async move { let _ = tx.unbounded_send(()); } ).expect( "spawn" );
+	}
+
+	rx.take( TASKS ).for_each( |_| async {} ).await;
+}
+
+
+async fn handle_throughput( exec: &impl SpawnHandle<()> )
+{
+	let handles: Vec<_> = (0..TASKS).map( |_| exec.spawn_handle( async {} ).expect( "spawn" ) ).collect();
+
+	join_all( handles ).await;
+}
+
+
+// Spawn TASKS tasks that never finish and drop their handles.
+//
+fn cancel( exec: &impl SpawnHandle<()> )
+{
+	for _ in 0..TASKS
+	{
+		drop( exec.spawn_handle( pending() ).expect( "spawn" ) );
+	}
+}
+
+
+fn detach( exec: &impl SpawnHandle<()> )
+{
+	for _ in 0..TASKS
+	{
+		exec.spawn_handle( async {} ).expect( "spawn" ).detach();
+	}
+}
+
+
+// The bytes in use per task while TASKS tasks are waiting, through Spawn and through SpawnHandle.
+// The channels that keep the tasks waiting are created before measuring.
+//
+async fn memory<E: Spawn + SpawnHandle<()>>( exec: &E ) -> ( isize, isize )
+{
+	let (done_tx, done_rx)                     = mpsc::unbounded();
+	let (senders, receivers): (Vec<_>, Vec<_>) = (0..TASKS).map( |_| oneshot::channel::<()>() ).unzip();
+
+	let before = IN_USE.load( Relaxed );
+
+	for rx in receivers
+	{
+		let done = done_tx.clone();
+
+		exec.spawn( async move { let _ = rx.await; let _ = done.unbounded_send(()); } ).expect( "spawn" );
+	}
+
+	let spawn = ( IN_USE.load( Relaxed ) - before ) / TASKS as isize;
+
+	// Let the tasks finish before measuring the next batch.
+	//
+	drop( senders );
+	done_rx.take( TASKS ).for_each( |_| async {} ).await;
+
+	let (senders, receivers): (Vec<_>, Vec<_>) = (0..TASKS).map( |_| oneshot::channel::<()>() ).unzip();
+	let mut handles                            = Vec::with_capacity( TASKS );
+
+	let before = IN_USE.load( Relaxed );
+
+	for rx in receivers
+	{
+		handles.push( exec.spawn_handle( async move { let _ = rx.await; } ).expect( "spawn" ) );
+	}
+
+	let handle = ( IN_USE.load( Relaxed ) - before ) / TASKS as isize;
+
+	drop( senders );
+	join_all( handles ).await;
+
+	( spawn, handle )
+}
+
+
+
+fn run<B: Backend>( c: &mut Criterion, name: &str, mut backend: B )
+{
+	let exec      = backend.exec();
+	let mut group = c.benchmark_group( name );
+
+	let (spawn, handle) = backend.block_on( memory( &exec ) );
+
+	println!( "{:<30} memory per task: Spawn {:>6} bytes, SpawnHandle {:>6} bytes", name, spawn, handle );
+
+	group.bench_function( "Spawn latency"         , |b| b.iter_custom( |iters| backend.block_on( spawn_latency ( &exec, iters ) ) ) );
+	group.bench_function( "SpawnHandle latency"   , |b| b.iter_custom( |iters| backend.block_on( handle_latency( &exec, iters ) ) ) );
+	group.bench_function( "Spawn throughput"      , |b| b.iter       ( ||      backend.block_on( spawn_throughput ( &exec ) ) ) );
+	group.bench_function( "SpawnHandle throughput", |b| b.iter       ( ||      backend.block_on( handle_throughput( &exec ) ) ) );
+	group.bench_function( "cancel on drop"        , |b| b.iter       ( ||      backend.block_on( async { cancel( black_box( &exec ) ) } ) ) );
+	group.bench_function( "detach"                , |b| b.iter       ( ||      backend.block_on( async { detach( black_box( &exec ) ) } ) ) );
+
+	group.finish();
+}
+
+
+
+fn executors( c: &mut Criterion )
+{
+	#[ cfg( feature = "tokio_ct" ) ]
+	{
+		run( c, "TokioCt", TokioCtBuilder::new().build().expect( "create tokio current thread" ) );
+
+		#[ cfg( feature = "tracing" ) ]
+		run( c, "TokioCt Instrumented", Traced( TokioCtBuilder::new().build().expect( "create tokio current thread" ) ) );
+	}
+
+	#[ cfg( feature = "tokio_tp" ) ]
+	{
+		run( c, "TokioTp", TokioTpBuilder::new().build().expect( "create tokio threadpool" ) );
+
+		#[ cfg( feature = "tracing" ) ]
+		run( c, "TokioTp Instrumented", Traced( TokioTpBuilder::new().build().expect( "create tokio threadpool" ) ) );
+	}
+
+	#[ cfg( feature = "async_std" ) ]
+	{
+		run( c, "AsyncStd", AsyncStd );
+
+		#[ cfg( feature = "tracing" ) ]
+		run( c, "AsyncStd Instrumented", Traced( AsyncStd ) );
+	}
+
+	#[ cfg( feature = "async_global" ) ]
+	{
+		run( c, "AsyncGlobal", AsyncGlobal );
+
+		#[ cfg( feature = "tracing" ) ]
+		run( c, "AsyncGlobal Instrumented", Traced( AsyncGlobal ) );
+	}
+
+	#[ cfg( feature = "threadpool" ) ]
+	{
+		run( c, "ThreadPool", ThreadPool::new().expect( "create threadpool" ) );
+
+		#[ cfg( feature = "tracing" ) ]
+		run( c, "ThreadPool Instrumented", Traced( ThreadPool::new().expect( "create threadpool" ) ) );
+	}
+
+	#[ cfg( feature = "localpool" ) ]
+	{
+		run( c, "LocalPool", LocalPool::new() );
+
+		#[ cfg( feature = "tracing" ) ]
+		run( c, "LocalPool Instrumented", Traced( LocalPool::new() ) );
+	}
+
+	#[ cfg( feature = "native" ) ]
+	{
+		run( c, "Native", NativeBuilder::new().build().expect( "create native executor" ) );
+
+		#[ cfg( feature = "tracing" ) ]
+		run( c, "Native Instrumented", Traced( NativeBuilder::new().build().expect( "create native executor" ) ) );
+	}
+
+	#[ cfg( feature = "glommio" ) ]
+	{
+		run( c, "GlommioCt", GlommioCt::new( glommio_crate::LocalExecutorBuilder::new() ).expect( "create glommio" ) );
+
+		#[ cfg( feature = "tracing" ) ]
+		run( c, "GlommioCt Instrumented", Traced( GlommioCt::new( glommio_crate::LocalExecutorBuilder::new() ).expect( "create glommio" ) ) );
+	}
+}
+
+
+
+criterion_group!( benches, executors );
+criterion_main! ( benches );
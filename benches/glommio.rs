@@ -0,0 +1,108 @@
+// Compares the JoinHandle of GlommioCt, which goes through a RemoteHandle, to GlommioJoinHandle and
+// glommio's native Task.
+//
+// Run with: `cargo bench --features glommio --bench glommio`
+//
+use
+{
+	async_executors :: { GlommioCt, LocalSpawnHandleExt             } ,
+	criterion       :: { criterion_group, criterion_main, Criterion } ,
+	futures         :: { future::pending, task::LocalSpawnExt       } ,
+	glommio_crate   :: { LocalExecutorBuilder, Task                 } ,
+};
+
+
+// The number of tasks spawned per iteration.
+//
+const TASKS: usize = 100;
+
+
+
+fn spawn_join( c: &mut Criterion )
+{
+	let exec      = GlommioCt::new( LocalExecutorBuilder::new() ).expect( "create glommio" );
+	let mut group = c.benchmark_group( "glommio spawn and join" );
+
+	group.bench_function( "JoinHandle", |b| b.iter( || exec.block_on( async
+	{
+		let handles: Vec<_> = (0..TASKS).map( |i| exec.spawn_handle_local( async move { i } ).expect( "spawn" ) ).collect();
+
+		for handle in handles { handle.await; }
+	})));
+
+	group.bench_function( "GlommioJoinHandle", |b| b.iter( || exec.block_on( async
+	{
+		let handles: Vec<_> = (0..TASKS).map( |i| exec.spawn_handle_native( async move { i } ).expect( "spawn" ) ).collect();
+
+		for handle in handles { handle.await; }
+	})));
+
+	group.bench_function( "native Task", |b| b.iter( || exec.block_on( async
+	{
+		let tasks: Vec<_> = (0..TASKS).map( |i| Task::local( async move { i } ) ).collect();
+
+		for task in tasks { task.await; }
+	})));
+
+	group.bench_function( "spawn without handle", |b| b.iter( || exec.block_on( async
+	{
+		for _ in 0..TASKS { exec.spawn_local( async {} ).expect( "spawn" ); }
+	})));
+
+	group.finish();
+}
+
+
+
+fn cancel( c: &mut Criterion )
+{
+	let exec      = GlommioCt::new( LocalExecutorBuilder::new() ).expect( "create glommio" );
+	let mut group = c.benchmark_group( "glommio cancel on drop" );
+
+	group.bench_function( "JoinHandle", |b| b.iter( || exec.block_on( async
+	{
+		for _ in 0..TASKS { drop( exec.spawn_handle_local( pending::<()>() ).expect( "spawn" ) ); }
+	})));
+
+	group.bench_function( "GlommioJoinHandle", |b| b.iter( || exec.block_on( async
+	{
+		for _ in 0..TASKS { drop( exec.spawn_handle_native( pending::<()>() ).expect( "spawn" ) ); }
+	})));
+
+	group.bench_function( "native Task", |b| b.iter( || exec.block_on( async
+	{
+		for _ in 0..TASKS { drop( Task::local( pending::<()>() ) ); }
+	})));
+
+	group.finish();
+}
+
+
+
+fn detach( c: &mut Criterion )
+{
+	let exec      = GlommioCt::new( LocalExecutorBuilder::new() ).expect( "create glommio" );
+	let mut group = c.benchmark_group( "glommio detach" );
+
+	group.bench_function( "JoinHandle", |b| b.iter( || exec.block_on( async
+	{
+		for _ in 0..TASKS { exec.spawn_handle_local( async {} ).expect( "spawn" ).detach(); }
+	})));
+
+	group.bench_function( "GlommioJoinHandle", |b| b.iter( || exec.block_on( async
+	{
+		for _ in 0..TASKS { exec.spawn_handle_native( async {} ).expect( "spawn" ).detach(); }
+	})));
+
+	group.bench_function( "native Task", |b| b.iter( || exec.block_on( async
+	{
+		for _ in 0..TASKS { Task::local( async {} ).detach(); }
+	})));
+
+	group.finish();
+}
+
+
+
+criterion_group!( benches, spawn_join, cancel, detach );
+criterion_main! ( benches );
@@ -0,0 +1,75 @@
+// Compares SpawnHandleExt::spawn_handle, which boxes the future, to SpawnHandleGeneric::spawn_handle_generic.
+//
+// Run with: `cargo bench --features "tokio_ct tokio_tp async_std async_global threadpool" --bench spawn_handle_generic`
+//
+use
+{
+	async_executors :: { *                                               } ,
+	criterion       :: { criterion_group, criterion_main, Criterion      } ,
+	futures         :: { executor::block_on                              } ,
+};
+
+
+// The number of tasks spawned per iteration.
+//
+const TASKS: usize = 100;
+
+
+// A future that is big enough for the allocation to matter.
+//
+async fn work( input: [u8; 256] ) -> u8
+{
+	input[ 255 ]
+}
+
+
+
+async fn boxed( exec: &impl SpawnHandle<u8> )
+{
+	let handles: Vec<_> = (0..TASKS).map( |_| exec.spawn_handle( work( [1; 256] ) ).expect( "spawn" ) ).collect();
+
+	for handle in handles { handle.await; }
+}
+
+
+async fn generic( exec: &impl SpawnHandleGeneric<u8> )
+{
+	let handles: Vec<_> = (0..TASKS).map( |_| exec.spawn_handle_generic( work( [1; 256] ) ).expect( "spawn" ) ).collect();
+
+	for handle in handles { handle.await; }
+}
+
+
+
+fn spawn_handle( c: &mut Criterion )
+{
+	let mut group = c.benchmark_group( "spawn_handle" );
+
+	let tokio_tp = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
+
+	group.bench_function( "TokioTp boxed"  , |b| b.iter( || tokio_tp.block_on( boxed  ( &tokio_tp ) ) ) );
+	group.bench_function( "TokioTp generic", |b| b.iter( || tokio_tp.block_on( generic( &tokio_tp ) ) ) );
+
+	let tokio_ct = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	group.bench_function( "TokioCt boxed"  , |b| b.iter( || tokio_ct.block_on( boxed  ( &tokio_ct ) ) ) );
+	group.bench_function( "TokioCt generic", |b| b.iter( || tokio_ct.block_on( generic( &tokio_ct ) ) ) );
+
+	group.bench_function( "AsyncStd boxed"  , |b| b.iter( || AsyncStd::block_on( boxed  ( &AsyncStd ) ) ) );
+	group.bench_function( "AsyncStd generic", |b| b.iter( || AsyncStd::block_on( generic( &AsyncStd ) ) ) );
+
+	group.bench_function( "AsyncGlobal boxed"  , |b| b.iter( || AsyncGlobal::block_on( boxed  ( &AsyncGlobal ) ) ) );
+	group.bench_function( "AsyncGlobal generic", |b| b.iter( || AsyncGlobal::block_on( generic( &AsyncGlobal ) ) ) );
+
+	let threadpool = ThreadPool::new().expect( "create threadpool" );
+
+	group.bench_function( "ThreadPool boxed"  , |b| b.iter( || block_on( boxed  ( &threadpool ) ) ) );
+	group.bench_function( "ThreadPool generic", |b| b.iter( || block_on( generic( &threadpool ) ) ) );
+
+	group.finish();
+}
+
+
+
+criterion_group!( benches, spawn_handle );
+criterion_main! ( benches );
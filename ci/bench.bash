@@ -0,0 +1,13 @@
+#!/usr/bin/bash
+
+# fail fast
+#
+set -e
+
+# print each command before it's executed
+#
+set -x
+
+# Glommio only works on Linux 5.8+, add it to the features if you want to compare it.
+#
+cargo bench --features "tokio_ct tokio_tp async_std async_global threadpool localpool native tracing" --bench executors -- "$@"
@@ -15,6 +15,7 @@ cargo check --features async_std
 cargo check --features tokio_tp
 cargo check --features tokio_ct
 cargo check --features async_global
+cargo check --features native
 
 # Currently doc tests in readme will fail without all features, because we have no way of turning on
 # the features for the doctest.
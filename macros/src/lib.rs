@@ -0,0 +1,302 @@
+//! Attribute macros for [async_executors](https://docs.rs/async_executors). Don't depend on this crate directly,
+//! turn on the `macros` feature of _async_executors_ and use them as `#[async_executors::macros::test]` and
+//! `#[async_executors::macros::main]`.
+//
+#![ deny  ( missing_docs ) ]
+#![ forbid( unsafe_code  ) ]
+#![ allow ( clippy::suspicious_else_formatting ) ]
+
+#![ warn
+(
+	anonymous_parameters          ,
+	missing_copy_implementations  ,
+	missing_debug_implementations ,
+	nonstandard_style             ,
+	rust_2018_idioms              ,
+	single_use_lifetimes          ,
+	trivial_casts                 ,
+	trivial_numeric_casts         ,
+	unreachable_pub               ,
+	unused_extern_crates          ,
+	unused_qualifications         ,
+	variant_size_differences      ,
+)]
+
+
+use
+{
+	proc_macro  :: { TokenStream                                                 } ,
+	proc_macro2 :: { Span, TokenStream as TokenStream2                           } ,
+	quote       :: { quote, format_ident                                         } ,
+	syn         :: { parse_macro_input, AttributeArgs, Error, ItemFn, Lit, Meta, NestedMeta } ,
+};
+
+
+/// The executors we know how to set up, named after the feature of async_executors that enables them.
+//
+const EXECUTORS: &[&str] = &[ "tokio_ct", "tokio_tp", "async_std", "async_global", "glommio", "localpool", "threadpool" ];
+
+
+/// Run an async test on several executors. This generates one `#[test]` per executor, in a module named
+/// after the test function, eg. `my_test::tokio_ct`. Executors whose feature is not enabled on async_executors
+/// are skipped.
+///
+/// Pass the executors by feature name or use `all`. The function can take the executor as it's only parameter,
+/// in which case it has to accept every executor you list. So with `all` you are limited to traits that every
+/// executor implements, like `Spawn`, `SpawnHandle` and `Timer`.
+///
+/// Other attributes on the function, like `#[should_panic]` or `#[ignore]` are copied to every generated test.
+///
+/// ```ignore
+/// use async_executors::{ SpawnHandle, SpawnHandleExt };
+///
+/// #[ async_executors::macros::test( all ) ]
+/// //
+/// async fn spawn_handle( exec: impl SpawnHandle<u8> )
+/// {
+///    assert_eq!( 5, exec.spawn_handle( async { 5 } ).unwrap().await );
+/// }
+/// ```
+//
+#[ proc_macro_attribute ]
+//
+pub fn test( args: TokenStream, item: TokenStream ) -> TokenStream
+{
+	let args = parse_macro_input!( args as AttributeArgs );
+	let func = parse_macro_input!( item as ItemFn        );
+
+	expand_test( args, func ).unwrap_or_else( |e| e.to_compile_error() ).into()
+}
+
+
+/// Run an async main function on the given executor. The function can take the executor as it's only parameter.
+///
+/// ```ignore
+/// use async_executors::{ TokioTp, SpawnHandleExt };
+///
+/// #[ async_executors::macros::main( exec = "tokio_tp" ) ]
+/// //
+/// async fn main( exec: TokioTp )
+/// {
+///    let out = exec.spawn_handle( async { 5 } ).expect( "spawn" ).await;
+/// }
+/// ```
+//
+#[ proc_macro_attribute ]
+//
+pub fn main( args: TokenStream, item: TokenStream ) -> TokenStream
+{
+	let args = parse_macro_input!( args as AttributeArgs );
+	let func = parse_macro_input!( item as ItemFn        );
+
+	expand_main( args, func ).unwrap_or_else( |e| e.to_compile_error() ).into()
+}
+
+
+
+fn expand_test( args: AttributeArgs, mut func: ItemFn ) -> Result<TokenStream2, Error>
+{
+	check_fn( &func )?;
+
+	let mut execs = Vec::new();
+
+	for arg in &args
+	{
+		let name = match arg
+		{
+			NestedMeta::Meta( Meta::Path(p) ) if p.get_ident().is_some() => p.get_ident().unwrap().to_string(),
+			_ => return Err( Error::new_spanned( arg, "expected the name of an executor or `all`" ) ),
+		};
+
+		if name == "all"
+		{
+			execs.extend( EXECUTORS.iter().map( |e| e.to_string() ) );
+		}
+
+		else if EXECUTORS.contains( &name.as_str() )
+		{
+			execs.push( name );
+		}
+
+		else
+		{
+			return Err( Error::new_spanned( arg, format!( "unknown executor, expected `all` or one of: {}", EXECUTORS.join( ", " ) ) ) );
+		}
+	}
+
+	if execs.is_empty()
+	{
+		return Err( Error::new( Span::call_site(), "specify the executors to test on, eg. `#[async_executors::macros::test(all)]`" ) );
+	}
+
+	execs.sort_by_key( |e| EXECUTORS.iter().position( |x| x == e ) );
+	execs.dedup();
+
+	let attrs    = std::mem::take( &mut func.attrs );
+	let name     = &func.sig.ident;
+	let with_arg = !func.sig.inputs.is_empty();
+
+	let tests = execs.iter().map( |exec|
+	{
+		let gate = format_ident!( "__if_{}", exec );
+		let test = format_ident!( "{}", exec );
+		let body = run( exec, quote!( super::#name ), with_arg );
+
+		quote!
+		{
+			::async_executors::#gate!
+			{
+				#( #attrs )*
+				#[ test ]
+				fn #test() { #body }
+			}
+		}
+	});
+
+	Ok( quote!
+	{
+		#[ allow( dead_code ) ]
+		#func
+
+		mod #name
+		{
+			#( #tests )*
+		}
+	})
+}
+
+
+
+fn expand_main( args: AttributeArgs, func: ItemFn ) -> Result<TokenStream2, Error>
+{
+	check_fn( &func )?;
+
+	let mut exec = None;
+
+	for arg in &args
+	{
+		match arg
+		{
+			NestedMeta::Meta( Meta::NameValue(nv) ) if nv.path.is_ident( "exec" ) => match &nv.lit
+			{
+				Lit::Str(s) if EXECUTORS.contains( &s.value().as_str() ) => exec = Some( s.value() ),
+
+				lit => return Err( Error::new_spanned( lit, format!( "unknown executor, expected one of: {}", EXECUTORS.join( ", " ) ) ) ),
+			}
+
+			_ => return Err( Error::new_spanned( arg, "expected `exec = \"...\"`" ) ),
+		}
+	}
+
+	let exec = exec.ok_or_else( || Error::new( Span::call_site(), "specify the executor, eg. `#[async_executors::macros::main( exec = \"tokio_tp\" )]`" ) )?;
+
+	let ItemFn{ attrs, vis, sig, .. } = &func;
+
+	let name     = &sig.ident;
+	let output   = &sig.output;
+	let body     = run( &exec, quote!( #name ), !sig.inputs.is_empty() );
+
+	Ok( quote!
+	{
+		#( #attrs )*
+		#vis fn #name() #output
+		{
+			#func
+
+			#body
+		}
+	})
+}
+
+
+
+// Both macros only take async functions with at most one parameter, the executor.
+//
+fn check_fn( func: &ItemFn ) -> Result<(), Error>
+{
+	if func.sig.asyncness.is_none()
+	{
+		return Err( Error::new_spanned( func.sig.fn_token, "the function must be async" ) );
+	}
+
+	if func.sig.inputs.len() > 1
+	{
+		return Err( Error::new_spanned( &func.sig.inputs, "the function can only take one parameter, the executor" ) );
+	}
+
+	Ok(())
+}
+
+
+
+// Create the executor and run the async function `f` on it until it completes.
+//
+fn run( exec: &str, f: TokenStream2, with_arg: bool ) -> TokenStream2
+{
+	let call = |arg: TokenStream2| if with_arg { quote!( #f( #arg ) ) } else { quote!( #f() ) };
+
+	match exec
+	{
+		"tokio_ct" | "tokio_tp" =>
+		{
+			let builder = if exec == "tokio_ct" { quote!( TokioCtBuilder ) } else { quote!( TokioTpBuilder ) };
+			let call    = call( quote!( exec.clone() ) );
+
+			quote!
+			{
+				let exec = ::async_executors::#builder::new().build().expect( "create tokio runtime" );
+				exec.block_on( #call )
+			}
+		}
+
+		"async_std" | "async_global" =>
+		{
+			let ty   = if exec == "async_std" { quote!( AsyncStd ) } else { quote!( AsyncGlobal ) };
+			let call = call( quote!( ::async_executors::#ty::new() ) );
+
+			quote!
+			{
+				::async_executors::#ty::block_on( #call )
+			}
+		}
+
+		"glommio" =>
+		{
+			let call = call( quote!( exec.clone() ) );
+
+			quote!
+			{
+				let exec = ::async_executors::GlommioCt::new( ::async_executors::macros::LocalExecutorBuilder::new() )
+
+					.expect( "create glommio executor" );
+
+				exec.block_on( #call )
+			}
+		}
+
+		"localpool" =>
+		{
+			let call = call( quote!( exec ) );
+
+			quote!
+			{
+				let mut pool = ::async_executors::LocalPool::new();
+				#[ allow( unused_variables ) ] let exec = pool.spawner();
+				pool.run_until( #call )
+			}
+		}
+
+		"threadpool" =>
+		{
+			let call = call( quote!( exec ) );
+
+			quote!
+			{
+				#[ allow( unused_variables ) ] let exec = ::async_executors::ThreadPool::new().expect( "create threadpool" );
+				::async_executors::macros::block_on( #call )
+			}
+		}
+
+		_ => unreachable!( "executor names are validated when parsing" ),
+	}
+}
@@ -0,0 +1,251 @@
+//! A test suite that checks an executor against the semantics documented by this crate.
+//!
+//! The executors in this crate all pass these tests. If you implement [`SpawnHandle`] or [`LocalSpawnHandle`]
+//! for your own executor, you can run the suite to prove that it behaves like the others, so that code which
+//! is generic over the executor doesn't get surprised:
+//!
+//! - spawned tasks run, both through the traits from _futures_ and through the `SpawnHandle` family,
+//!   as well as through a trait object.
+//! - the [`JoinHandle`](crate::JoinHandle) resolves to the output of the task.
+//! - dropping the [`JoinHandle`](crate::JoinHandle) cancels the task, eg. the future get's dropped.
+//! - [`JoinHandle::detach`](crate::JoinHandle::detach) lets the task run to completion.
+//! - when the task panics, awaiting the [`JoinHandle`](crate::JoinHandle) panics.
+//!
+//! Each behavior is an async function that panics when the executor does not conform, so you can call them from
+//! your own tests and run them on the executor under test, or on any other. [`run_all`] and [`run_all_local`]
+//! run the whole suite.
+//!
+//! Note that an executor that never drops the future of a canceled task will make [`join_handle_drop`] hang
+//! rather than fail, as there is no executor agnostic way to time out.
+//!
+//! The panic test will print a panic message, even when it passes.
+//!
+//! ```
+//! use async_executors::{ conformance, ThreadPool };
+//!
+//! let exec = ThreadPool::new().expect( "create threadpool" );
+//!
+//! futures::executor::block_on( conformance::run_all( &exec ) );
+//! ```
+//
+use
+{
+	crate           :: { SpawnHandle, SpawnHandleExt, LocalSpawnHandle, LocalSpawnHandleExt } ,
+	futures_task    :: { Spawn, LocalSpawn                                                 } ,
+	futures_util    :: { FutureExt, future::pending, task::{ SpawnExt, LocalSpawnExt }     } ,
+	futures_channel :: { oneshot                                                           } ,
+	std             :: { panic::AssertUnwindSafe, rc::Rc                                   } ,
+};
+
+
+
+/// Run all tests that take an executor which can spawn `Send` futures.
+//
+pub async fn run_all<E>( exec: &E )
+
+	where E: Spawn + SpawnHandle<u8> + SpawnHandle<()>
+{
+	spawn              ( exec ).await;
+	spawn_handle       ( exec ).await;
+	spawn_handle_os    ( exec ).await;
+	join_handle_drop   ( exec ).await;
+	join_handle_detach ( exec ).await;
+	join_handle_panic  ( exec ).await;
+}
+
+
+/// Run all tests that take an executor which can spawn `!Send` futures.
+//
+pub async fn run_all_local<E>( exec: &E )
+
+	where E: LocalSpawn + LocalSpawnHandle< Rc<u8> > + LocalSpawnHandle<()>
+{
+	spawn_local              ( exec ).await;
+	spawn_handle_local       ( exec ).await;
+	spawn_handle_local_os    ( exec ).await;
+	join_handle_drop_local   ( exec ).await;
+	join_handle_detach_local ( exec ).await;
+	join_handle_panic_local  ( exec ).await;
+}
+
+
+
+/// A task spawned with [`Spawn`] runs.
+//
+pub async fn spawn( exec: &impl Spawn )
+{
+	let (tx, rx) = oneshot::channel();
+
+	exec.spawn( async move { let _ = tx.send( 5u8 ); } ).expect( "spawn" );
+
+	assert_eq!( rx.await, Ok(5), "a task spawned with Spawn must run" );
+}
+
+
+/// A `!Send` task spawned with [`LocalSpawn`] runs.
+//
+pub async fn spawn_local( exec: &impl LocalSpawn )
+{
+	let (tx, rx) = oneshot::channel();
+
+	exec.spawn_local( async move
+	{
+		let not_send = Rc::new( 5u8 );
+		let _ = tx.send( *not_send );
+
+	}).expect( "spawn_local" );
+
+	assert_eq!( rx.await, Ok(5), "a task spawned with LocalSpawn must run" );
+}
+
+
+/// The [`JoinHandle`](crate::JoinHandle) resolves to the output of the task.
+//
+pub async fn spawn_handle( exec: &impl SpawnHandle<u8> )
+{
+	let out = exec.spawn_handle( async { 4 + 1 } ).expect( "spawn_handle" ).await;
+
+	assert_eq!( out, 5, "the JoinHandle must resolve to the output of the task" );
+}
+
+
+/// The [`JoinHandle`](crate::JoinHandle) resolves to the output of the task when spawning on a trait object.
+//
+pub async fn spawn_handle_os( exec: &dyn SpawnHandle<u8> )
+{
+	let out = exec.spawn_handle( async { 4 + 1 } ).expect( "spawn_handle" ).await;
+
+	assert_eq!( out, 5, "the JoinHandle must resolve to the output of the task" );
+}
+
+
+/// The [`JoinHandle`](crate::JoinHandle) resolves to the `!Send` output of a `!Send` task.
+//
+pub async fn spawn_handle_local( exec: &impl LocalSpawnHandle< Rc<u8> > )
+{
+	let out = exec.spawn_handle_local( async { Rc::new( 4 + 1 ) } ).expect( "spawn_handle_local" ).await;
+
+	assert_eq!( *out, 5, "the JoinHandle must resolve to the output of the task" );
+}
+
+
+/// The [`JoinHandle`](crate::JoinHandle) resolves to the `!Send` output of a `!Send` task when spawning on a trait object.
+//
+pub async fn spawn_handle_local_os( exec: &dyn LocalSpawnHandle< Rc<u8> > )
+{
+	let out = exec.spawn_handle_local( async { Rc::new( 4 + 1 ) } ).expect( "spawn_handle_local" ).await;
+
+	assert_eq!( *out, 5, "the JoinHandle must resolve to the output of the task" );
+}
+
+
+/// Dropping the [`JoinHandle`](crate::JoinHandle) cancels the task.
+//
+pub async fn join_handle_drop( exec: &impl SpawnHandle<()> )
+{
+	let (started_tx, started_rx) = oneshot::channel::<()>();
+	let (guard_tx  , guard_rx  ) = oneshot::channel::<()>();
+
+	let handle = exec.spawn_handle( async move
+	{
+		// Will be dropped together with the future.
+		//
+		let _guard = guard_tx;
+
+		let _ = started_tx.send(());
+		pending::<()>().await;
+
+	}).expect( "spawn_handle" );
+
+	started_rx.await.expect( "task to start" );
+	drop( handle );
+
+	assert!( guard_rx.await.is_err(), "dropping the JoinHandle must cancel the task" );
+}
+
+
+/// Dropping the [`JoinHandle`](crate::JoinHandle) cancels the `!Send` task.
+//
+pub async fn join_handle_drop_local( exec: &impl LocalSpawnHandle<()> )
+{
+	let (started_tx, started_rx) = oneshot::channel::<()>();
+	let (guard_tx  , guard_rx  ) = oneshot::channel::<()>();
+
+	let handle = exec.spawn_handle_local( async move
+	{
+		let _guard = Rc::new( guard_tx );
+
+		let _ = started_tx.send(());
+		pending::<()>().await;
+
+	}).expect( "spawn_handle_local" );
+
+	started_rx.await.expect( "task to start" );
+	drop( handle );
+
+	assert!( guard_rx.await.is_err(), "dropping the JoinHandle must cancel the task" );
+}
+
+
+/// [`JoinHandle::detach`](crate::JoinHandle::detach) lets the task run to completion.
+//
+pub async fn join_handle_detach( exec: &impl SpawnHandle<()> )
+{
+	let (in_tx , in_rx ) = oneshot::channel();
+	let (out_tx, out_rx) = oneshot::channel();
+
+	exec.spawn_handle( async move
+	{
+		let content = in_rx.await.expect( "receive on in" );
+		let _       = out_tx.send( content );
+
+	}).expect( "spawn_handle" ).detach();
+
+	in_tx.send( 5u8 ).expect( "send on in" );
+
+	assert_eq!( out_rx.await, Ok(5), "a detached task must keep running" );
+}
+
+
+/// [`JoinHandle::detach`](crate::JoinHandle::detach) lets the `!Send` task run to completion.
+//
+pub async fn join_handle_detach_local( exec: &impl LocalSpawnHandle<()> )
+{
+	let (in_tx , in_rx ) = oneshot::channel();
+	let (out_tx, out_rx) = oneshot::channel();
+
+	exec.spawn_handle_local( async move
+	{
+		let content = Rc::new( in_rx.await.expect( "receive on in" ) );
+		let _       = out_tx.send( *content );
+
+	}).expect( "spawn_handle_local" ).detach();
+
+	in_tx.send( 5u8 ).expect( "send on in" );
+
+	assert_eq!( out_rx.await, Ok(5), "a detached task must keep running" );
+}
+
+
+/// Awaiting the [`JoinHandle`](crate::JoinHandle) of a task that panics, panics.
+//
+pub async fn join_handle_panic( exec: &impl SpawnHandle<()> )
+{
+	let handle = exec.spawn_handle( async { panic!( "conformance: this panic is expected" ) } ).expect( "spawn_handle" );
+
+	let result = AssertUnwindSafe( handle ).catch_unwind().await;
+
+	assert!( result.is_err(), "awaiting the JoinHandle of a panicking task must panic" );
+}
+
+
+/// Awaiting the [`JoinHandle`](crate::JoinHandle) of a `!Send` task that panics, panics.
+//
+pub async fn join_handle_panic_local( exec: &impl LocalSpawnHandle<()> )
+{
+	let handle = exec.spawn_handle_local( async { panic!( "conformance: this panic is expected" ) } ).expect( "spawn_handle_local" );
+
+	let result = AssertUnwindSafe( handle ).catch_unwind().await;
+
+	assert!( result.is_err(), "awaiting the JoinHandle of a panicking task must panic" );
+}
@@ -0,0 +1,305 @@
+//! Provides AnyExec, an executor that is chosen at runtime.
+//
+use
+{
+	crate          :: { SpawnHandle, LocalSpawnHandle, JoinHandle, Timer                          } ,
+	crate          :: { SpawnMany, LocalSpawnMany, SpawnHandleGeneric, LocalSpawnHandleGeneric    } ,
+	std            :: { future::Future, time::Duration                                            } ,
+	futures_task   :: { FutureObj, LocalFutureObj, Spawn, LocalSpawn, SpawnError                  } ,
+	futures_util   :: { future::BoxFuture                                                         } ,
+};
+
+#[ cfg( feature = "localpool" ) ] use { std::{ rc::Rc, cell::RefCell }, crate::{ LocalPool, LocalSpawner } };
+
+
+
+/// An executor that is chosen at runtime, eg. from a configuration file. It has a variant for every
+/// executor that is compiled in and implements the traits of this crate by forwarding to it.
+///
+/// [`ExecConfig`](crate::ExecConfig) describes which executor to build, and can be deserialized or read
+/// from an environment variable. You can also convert an executor you have created yourself with `From`.
+///
+/// `TokioTp` and `ThreadPool` cannot spawn `!Send` futures. For those variants, [`LocalSpawn`] and
+/// [`LocalSpawnHandle`] return [`SpawnError::shutdown`].
+///
+/// Note that `AnyExec` is `!Send` and `!Sync` as soon as one of the single threaded executors is compiled in
+/// (`tokio_ct`, `glommio` or `localpool`).
+///
+/// ```
+/// // Make sure to set the `tokio_tp` feature on async_executors.
+/// //
+/// use async_executors::{ AnyExec, ExecConfig, SpawnHandleExt };
+///
+/// let config: ExecConfig = "tokio_tp:threads=2".parse().expect( "valid config" );
+/// let exec  : AnyExec    = config.build().expect( "create executor" );
+///
+/// let handle = exec.spawn_handle( async { 5u8 } ).expect( "spawn" );
+///
+/// assert_eq!( 5, exec.block_on( handle ) );
+/// ```
+//
+#[ derive( Debug, Clone ) ]
+#[ non_exhaustive ]
+//
+pub enum AnyExec
+{
+	/// See [`TokioTp`](crate::TokioTp).
+	//
+	#[ cfg( feature = "tokio_tp" ) ]
+	#[ cfg_attr( nightly, doc(cfg( feature = "tokio_tp" )) ) ]
+	//
+	TokioTp( crate::TokioTp ),
+
+	/// See [`TokioCt`](crate::TokioCt).
+	//
+	#[ cfg( feature = "tokio_ct" ) ]
+	#[ cfg_attr( nightly, doc(cfg( feature = "tokio_ct" )) ) ]
+	//
+	TokioCt( crate::TokioCt ),
+
+	/// See [`AsyncStd`](crate::AsyncStd).
+	//
+	#[ cfg( feature = "async_std" ) ]
+	#[ cfg_attr( nightly, doc(cfg( feature = "async_std" )) ) ]
+	//
+	AsyncStd( crate::AsyncStd ),
+
+	/// See [`AsyncGlobal`](crate::AsyncGlobal).
+	//
+	#[ cfg( feature = "async_global" ) ]
+	#[ cfg_attr( nightly, doc(cfg( feature = "async_global" )) ) ]
+	//
+	AsyncGlobal( crate::AsyncGlobal ),
+
+	/// See [`GlommioCt`](crate::GlommioCt).
+	//
+	#[ cfg( feature = "glommio" ) ]
+	#[ cfg_attr( nightly, doc(cfg( feature = "glommio" )) ) ]
+	//
+	GlommioCt( crate::GlommioCt ),
+
+	/// See [`ThreadPool`](crate::ThreadPool).
+	//
+	#[ cfg( feature = "threadpool" ) ]
+	#[ cfg_attr( nightly, doc(cfg( feature = "threadpool" )) ) ]
+	//
+	ThreadPool( crate::ThreadPool ),
+
+	/// A [`LocalPool`](crate::LocalPool) together with it's spawner. The pool only makes progress
+	/// inside [`AnyExec::block_on`].
+	//
+	#[ cfg( feature = "localpool" ) ]
+	#[ cfg_attr( nightly, doc(cfg( feature = "localpool" )) ) ]
+	//
+	LocalPool
+	{
+		/// The pool, needed to run it.
+		//
+		pool: Rc<RefCell<LocalPool>>,
+
+		/// The spawner of the pool.
+		//
+		spawner: LocalSpawner,
+	},
+}
+
+
+// Run the same code on whatever executor we hold.
+//
+macro_rules! dispatch
+{
+	( $self:expr, $e:ident => $body:expr ) =>
+	{
+		match *$self
+		{
+			#[ cfg( feature = "tokio_tp"     ) ] AnyExec::TokioTp    ( ref $e        ) => $body,
+			#[ cfg( feature = "tokio_ct"     ) ] AnyExec::TokioCt    ( ref $e        ) => $body,
+			#[ cfg( feature = "async_std"    ) ] AnyExec::AsyncStd   ( ref $e        ) => $body,
+			#[ cfg( feature = "async_global" ) ] AnyExec::AsyncGlobal( ref $e        ) => $body,
+			#[ cfg( feature = "glommio"      ) ] AnyExec::GlommioCt  ( ref $e        ) => $body,
+			#[ cfg( feature = "threadpool"   ) ] AnyExec::ThreadPool ( ref $e        ) => $body,
+			#[ cfg( feature = "localpool"    ) ] AnyExec::LocalPool{ spawner: ref $e, .. } => $body,
+		}
+	}
+}
+
+
+// Like dispatch, but only for executors that can spawn !Send futures. The others evaluate to `$unsupported`.
+//
+macro_rules! dispatch_local
+{
+	( $self:expr, $e:ident => $body:expr, $unsupported:expr ) =>
+	{
+		match *$self
+		{
+			#[ cfg( feature = "tokio_tp"     ) ] AnyExec::TokioTp    (_) => $unsupported,
+			#[ cfg( feature = "threadpool"   ) ] AnyExec::ThreadPool (_) => $unsupported,
+
+			#[ cfg( feature = "tokio_ct"     ) ] AnyExec::TokioCt    ( ref $e        ) => $body,
+			#[ cfg( feature = "async_std"    ) ] AnyExec::AsyncStd   ( ref $e        ) => $body,
+			#[ cfg( feature = "async_global" ) ] AnyExec::AsyncGlobal( ref $e        ) => $body,
+			#[ cfg( feature = "glommio"      ) ] AnyExec::GlommioCt  ( ref $e        ) => $body,
+			#[ cfg( feature = "localpool"    ) ] AnyExec::LocalPool{ spawner: ref $e, .. } => $body,
+		}
+	}
+}
+
+
+
+impl AnyExec
+{
+	/// Run the future to completion on the current thread with the `block_on` of the underlying executor.
+	/// This is not available on Wasm.
+	///
+	/// For `ThreadPool` this uses `futures::executor::block_on`. For `LocalPool` it runs the pool until the
+	/// future completes, so don't call it from within a task of that pool.
+	//
+	#[ cfg(not( target_os = "unknown" )) ]
+	#[ cfg_attr( nightly, doc(cfg(not( target_os = "unknown" ))) ) ]
+	//
+	pub fn block_on<F: Future>( &self, future: F ) -> F::Output
+	{
+		match *self
+		{
+			#[ cfg( feature = "tokio_tp"     ) ] AnyExec::TokioTp    ( ref e ) => e.block_on( future ),
+			#[ cfg( feature = "tokio_ct"     ) ] AnyExec::TokioCt    ( ref e ) => e.block_on( future ),
+			#[ cfg( feature = "async_std"    ) ] AnyExec::AsyncStd   ( _     ) => crate::AsyncStd::block_on( future ),
+			#[ cfg( feature = "async_global" ) ] AnyExec::AsyncGlobal( _     ) => crate::AsyncGlobal::block_on( future ),
+			#[ cfg( feature = "glommio"      ) ] AnyExec::GlommioCt  ( ref e ) => e.block_on( future ),
+			#[ cfg( feature = "threadpool"   ) ] AnyExec::ThreadPool ( _     ) => futures_executor::block_on( future ),
+			#[ cfg( feature = "localpool"    ) ] AnyExec::LocalPool{ ref pool, .. } => pool.borrow_mut().run_until( future ),
+		}
+	}
+}
+
+
+
+impl Spawn for AnyExec
+{
+	fn spawn_obj( &self, future: FutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		dispatch!( self, e => e.spawn_obj( future ) )
+	}
+}
+
+
+
+impl LocalSpawn for AnyExec
+{
+	fn spawn_local_obj( &self, future: LocalFutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		dispatch_local!( self, e => e.spawn_local_obj( future ), { drop( future ); Err( SpawnError::shutdown() ) } )
+	}
+}
+
+
+
+impl SpawnMany for AnyExec
+{
+	fn spawn_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		dispatch!( self, e => e.spawn_obj_many( futures ) )
+	}
+}
+
+
+
+impl LocalSpawnMany for AnyExec
+{
+	fn spawn_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		dispatch_local!( self, e => e.spawn_local_obj_many( futures ), { let _ = futures; Err( SpawnError::shutdown() ) } )
+	}
+}
+
+
+
+impl<Out: 'static + Send> SpawnHandle<Out> for AnyExec
+{
+	fn spawn_handle_obj( &self, future: FutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>
+	{
+		dispatch!( self, e => e.spawn_handle_obj( future ) )
+	}
+
+
+	fn spawn_handle_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		dispatch!( self, e => e.spawn_handle_obj_many( futures ) )
+	}
+}
+
+
+
+impl<Out: 'static> LocalSpawnHandle<Out> for AnyExec
+{
+	fn spawn_handle_local_obj( &self, future: LocalFutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>
+	{
+		dispatch_local!( self, e => e.spawn_handle_local_obj( future ), { drop( future ); Err( SpawnError::shutdown() ) } )
+	}
+
+
+	fn spawn_handle_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		dispatch_local!( self, e => e.spawn_handle_local_obj_many( futures ), { let _ = futures; Err( SpawnError::shutdown() ) } )
+	}
+}
+
+
+
+impl<Out: 'static + Send> SpawnHandleGeneric<Out> for AnyExec
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		dispatch!( self, e => e.spawn_handle_generic( future ) )
+	}
+}
+
+
+
+impl<Out: 'static> LocalSpawnHandleGeneric<Out> for AnyExec
+{
+	fn spawn_handle_local_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + 'static
+	{
+		dispatch_local!( self, e => e.spawn_handle_local_generic( future ), { drop( future ); Err( SpawnError::shutdown() ) } )
+	}
+}
+
+
+
+impl Timer for AnyExec
+{
+	fn sleep( &self, dur: Duration ) -> BoxFuture<'static, ()>
+	{
+		dispatch!( self, e => e.sleep( dur ) )
+	}
+}
+
+
+
+#[ cfg( feature = "tokio_tp"     ) ] impl From<crate::TokioTp    > for AnyExec { fn from( e: crate::TokioTp     ) -> Self { AnyExec::TokioTp    (e) } }
+#[ cfg( feature = "tokio_ct"     ) ] impl From<crate::TokioCt    > for AnyExec { fn from( e: crate::TokioCt     ) -> Self { AnyExec::TokioCt    (e) } }
+#[ cfg( feature = "async_std"    ) ] impl From<crate::AsyncStd   > for AnyExec { fn from( e: crate::AsyncStd    ) -> Self { AnyExec::AsyncStd   (e) } }
+#[ cfg( feature = "async_global" ) ] impl From<crate::AsyncGlobal> for AnyExec { fn from( e: crate::AsyncGlobal ) -> Self { AnyExec::AsyncGlobal(e) } }
+#[ cfg( feature = "glommio"      ) ] impl From<crate::GlommioCt  > for AnyExec { fn from( e: crate::GlommioCt   ) -> Self { AnyExec::GlommioCt  (e) } }
+#[ cfg( feature = "threadpool"   ) ] impl From<crate::ThreadPool > for AnyExec { fn from( e: crate::ThreadPool  ) -> Self { AnyExec::ThreadPool (e) } }
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+impl From<LocalPool> for AnyExec
+{
+	fn from( pool: LocalPool ) -> Self
+	{
+		let spawner = pool.spawner();
+
+		AnyExec::LocalPool{ pool: Rc::new( RefCell::new( pool ) ), spawner }
+	}
+}
@@ -1,7 +1,11 @@
 use
 {
-	crate        :: { SpawnHandle, LocalSpawnHandle, JoinHandle                } ,
+	crate        :: { SpawnHandle, LocalSpawnHandle, JoinHandle, Timer         } ,
+	crate        :: { SpawnHandleGeneric, LocalSpawnHandleGeneric              } ,
+	crate        :: { SpawnMany, LocalSpawnMany                                } ,
 	futures_task :: { FutureObj, LocalFutureObj, Spawn, LocalSpawn, SpawnError } ,
+	futures_util :: { future::BoxFuture                                        } ,
+	std          :: { future::Future, time::Duration                           } ,
 };
 
 
@@ -9,6 +13,9 @@ use
 /// is not self contained, because async-global-executor does not provide an API that allows that,
 /// so the threadpool is global.
 ///
+/// The threadpool starts with default settings when the first task is spawned. To configure it, use
+/// [`AsyncGlobalBuilder`](crate::AsyncGlobalBuilder) before that.
+///
 /// It works on Wasm.
 //
 #[ derive( Copy, Clone, Default ) ]
@@ -35,7 +42,7 @@ impl AsyncGlobal
 	#[cfg(not(target_os = "unknown"))]
 	#[ cfg_attr( nightly, doc(cfg(not( target_os = "unknown" ))) ) ]
 	//
-	pub fn block_on<F: std::future::Future>(future: F) -> F::Output
+	pub fn block_on<F: Future>(future: F) -> F::Output
 	{
 		async_global_executor::block_on( future )
 	}
@@ -71,6 +78,10 @@ impl Spawn for AsyncGlobal
 
 
 
+impl SpawnMany for AsyncGlobal {}
+
+
+
 #[ cfg( not(target_arch = "wasm32") ) ]
 //
 impl<Out: 'static + Send> SpawnHandle<Out> for AsyncGlobal
@@ -114,6 +125,52 @@ impl<Out: 'static> LocalSpawnHandle<Out> for AsyncGlobal
 
 
 
+#[ cfg( not(target_arch = "wasm32") ) ]
+//
+impl<Out: 'static + Send> SpawnHandleGeneric<Out> for AsyncGlobal
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		Ok( JoinHandle{ inner: crate::join_handle::InnerJh::AsyncGlobal
+		{
+			task: Some( async_global_executor::spawn(future) ),
+		}})
+	}
+}
+
+
+
+#[ cfg( target_arch = "wasm32" ) ]
+//
+impl<Out: 'static + Send> SpawnHandleGeneric<Out> for AsyncGlobal
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		self.spawn_handle_local_generic( future )
+	}
+}
+
+
+
+impl<Out: 'static> LocalSpawnHandleGeneric<Out> for AsyncGlobal
+{
+	fn spawn_handle_local_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + 'static
+	{
+		Ok( JoinHandle{ inner: crate::join_handle::InnerJh::AsyncGlobal
+		{
+			task: Some( async_global_executor::spawn_local(future) ),
+		}})
+	}
+}
+
+
+
 impl LocalSpawn for AsyncGlobal
 {
 	fn spawn_local_obj( &self, future: LocalFutureObj<'static, ()> ) -> Result<(), SpawnError>
@@ -125,6 +182,22 @@ impl LocalSpawn for AsyncGlobal
 }
 
 
+
+impl LocalSpawnMany for AsyncGlobal {}
+
+
+/// async-global-executor has no timer of it's own, so this uses futures-timer.
+//
+impl Timer for AsyncGlobal
+{
+	fn sleep( &self, dur: Duration ) -> BoxFuture<'static, ()>
+	{
+		Box::pin( futures_timer::Delay::new( dur ) )
+	}
+}
+
+
+
 impl std::fmt::Debug for AsyncGlobal
 {
 	fn fmt( &self, f: &mut std::fmt::Formatter<'_> ) -> std::fmt::Result
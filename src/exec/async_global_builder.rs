@@ -0,0 +1,205 @@
+//! Provides AsyncGlobalBuilder to configure the threadpool of async-global-executor.
+//
+use
+{
+	crate                 :: { AsyncGlobal                                                  } ,
+	async_global_executor :: { GlobalExecutorConfig                                         } ,
+	std                   :: { error::Error, fmt, sync::Arc                                 } ,
+	std                   :: { sync::atomic::{ AtomicBool, AtomicUsize, Ordering::SeqCst }  } ,
+};
+
+
+/// Builder to configure the global threadpool of async-global-executor before it starts.
+///
+/// [`AsyncGlobal`] uses a global executor that starts with default settings the first time a task is spawned on it.
+/// To configure it, call [`init`](AsyncGlobalBuilder::init) before anything gets spawned. The settings map to
+/// [`GlobalExecutorConfig`]:
+///
+/// - [`min_threads`](AsyncGlobalBuilder::min_threads): the number of threads started by `init`. Defaults to the
+///   available parallelism.
+/// - [`max_threads`](AsyncGlobalBuilder::max_threads): the number of threads the pool can grow to. Defaults to four
+///   times `min_threads`.
+/// - [`thread_name_fn`](AsyncGlobalBuilder::thread_name_fn): the names of the threads. Defaults to
+///   `async-global-executor-{n}`.
+/// - [`env_var`](AsyncGlobalBuilder::env_var): an environment variable which overrides `min_threads`. Defaults to
+///   `ASYNC_GLOBAL_EXECUTOR_THREADS`.
+///
+/// The executor is global, so it can only be configured once. When it has already started, `init` returns
+/// [`AlreadyStarted`] instead of silently ignoring the configuration. Note that async-std runs it's tasks on
+/// async-global-executor as well, so spawning on [`AsyncStd`](crate::AsyncStd) also starts it.
+///
+/// This is not available on Wasm, where there are no threads.
+///
+/// ```
+/// use async_executors::{ AsyncGlobal, AsyncGlobalBuilder, SpawnHandleExt };
+///
+/// let exec = AsyncGlobalBuilder::new()
+///
+///    .min_threads   ( 2 )
+///    .max_threads   ( 4 )
+///    .thread_name_fn( || "worker".to_string() )
+///    .init()
+///    .expect( "configure async-global-executor" )
+/// ;
+///
+/// let name = exec.spawn_handle( async { std::thread::current().name().map( str::to_string ) } ).expect( "spawn" );
+///
+/// assert_eq!( AsyncGlobal::block_on( name ).as_deref(), Some( "worker" ) );
+///
+/// // The executor has started, so it can't be configured anymore.
+/// //
+/// assert!( AsyncGlobalBuilder::new().init().is_err() );
+/// ```
+//
+#[ derive( Clone, Default ) ]
+//
+#[ cfg_attr( nightly, doc(cfg(all( feature = "async_global", not(target_arch = "wasm32") ))) ) ]
+//
+pub struct AsyncGlobalBuilder
+{
+	env_var    : Option< &'static str                            > ,
+	min_threads: Option< usize                                   > ,
+	max_threads: Option< usize                                   > ,
+	thread_name: Option< Arc<dyn Fn() -> String + Send + Sync> > ,
+}
+
+
+
+impl AsyncGlobalBuilder
+{
+	/// Constructor.
+	//
+	pub fn new() -> Self
+	{
+		Self::default()
+	}
+
+
+	/// Read the minimum number of threads from this environment variable instead of `ASYNC_GLOBAL_EXECUTOR_THREADS`.
+	/// When it is set to a number, it takes precedence over [`min_threads`](AsyncGlobalBuilder::min_threads).
+	//
+	pub fn env_var( &mut self, env_var: &'static str ) -> &mut Self
+	{
+		self.env_var = Some( env_var );
+		self
+	}
+
+
+	/// The number of threads started by [`init`](AsyncGlobalBuilder::init). The pool never shrinks below this.
+	//
+	pub fn min_threads( &mut self, min_threads: usize ) -> &mut Self
+	{
+		self.min_threads = Some( min_threads );
+		self
+	}
+
+
+	/// The maximum number of threads for async tasks. It is raised to the minimum if it is lower.
+	//
+	pub fn max_threads( &mut self, max_threads: usize ) -> &mut Self
+	{
+		self.max_threads = Some( max_threads );
+		self
+	}
+
+
+	/// A closure that returns the name for each new thread.
+	//
+	pub fn thread_name_fn( &mut self, thread_name_fn: impl Fn() -> String + Send + Sync + 'static ) -> &mut Self
+	{
+		self.thread_name = Some( Arc::new( thread_name_fn ) );
+		self
+	}
+
+
+	/// Start the global executor with this configuration.
+	///
+	/// ## Errors
+	///
+	/// Returns [`AlreadyStarted`] if the global executor was started before, in which case the configuration is not
+	/// applied.
+	//
+	pub fn init( &mut self ) -> Result<AsyncGlobal, AlreadyStarted>
+	{
+		// async-global-executor ignores the config if it has one already. It names the `min_threads` (at least 1)
+		// threads it starts during init, so if our closure didn't get called, our config wasn't used.
+		//
+		let applied     = Arc::new( AtomicBool::new( false ) );
+		let applied2    = applied.clone();
+		let thread_name = self.thread_name.clone();
+
+		let mut config = GlobalExecutorConfig::default().with_thread_name_fn( move ||
+		{
+			applied2.store( true, SeqCst );
+
+			match &thread_name
+			{
+				Some(name) => name(),
+
+				// The same as the default of async-global-executor.
+				//
+				None =>
+				{
+					static NEXT_THREAD: AtomicUsize = AtomicUsize::new( 1 );
+
+					format!( "async-global-executor-{}", NEXT_THREAD.fetch_add( 1, SeqCst ) )
+				}
+			}
+		});
+
+		if let Some(env_var) = self.env_var     { config = config.with_env_var    ( env_var ); }
+		if let Some(min    ) = self.min_threads { config = config.with_min_threads( min     ); }
+		if let Some(max    ) = self.max_threads { config = config.with_max_threads( max     ); }
+
+		async_global_executor::init_with_config( config );
+
+		if applied.load( SeqCst )
+		{
+			Ok( AsyncGlobal )
+		}
+
+		else
+		{
+			Err( AlreadyStarted )
+		}
+	}
+}
+
+
+
+impl fmt::Debug for AsyncGlobalBuilder
+{
+	fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result
+	{
+		f.debug_struct( "AsyncGlobalBuilder" )
+
+			.field( "env_var"    , &self.env_var               )
+			.field( "min_threads", &self.min_threads           )
+			.field( "max_threads", &self.max_threads           )
+			.field( "thread_name", &self.thread_name.is_some() )
+			.finish()
+	}
+}
+
+
+
+/// Returned by [`AsyncGlobalBuilder::init`] when the global executor of async-global-executor has already started,
+/// so it can not be configured anymore.
+//
+#[ derive( Debug, Clone, Copy, PartialEq, Eq, Hash ) ]
+//
+#[ cfg_attr( nightly, doc(cfg(all( feature = "async_global", not(target_arch = "wasm32") ))) ) ]
+//
+pub struct AlreadyStarted;
+
+
+impl fmt::Display for AlreadyStarted
+{
+	fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result
+	{
+		f.write_str( "the async-global-executor threadpool has already started, it can not be configured anymore" )
+	}
+}
+
+
+impl Error for AlreadyStarted {}
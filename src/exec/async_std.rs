@@ -1,11 +1,17 @@
 use
 {
-	crate        :: { SpawnHandle, LocalSpawnHandle, JoinHandle, join_handle::InnerJh } ,
-	futures_task :: { FutureObj, LocalFutureObj, Spawn, LocalSpawn, SpawnError        } ,
-	futures_util :: { future::abortable                                               } ,
-	std          :: { sync::atomic::AtomicBool                                        } ,
+	crate        :: { SpawnHandle, LocalSpawnHandle, JoinHandle, Timer, join_handle::InnerJh } ,
+	crate        :: { SpawnHandleGeneric, LocalSpawnHandleGeneric                            } ,
+	crate        :: { SpawnMany, LocalSpawnMany                                              } ,
+	futures_task :: { FutureObj, LocalFutureObj, Spawn, LocalSpawn, SpawnError               } ,
+	futures_util :: { future::BoxFuture                                                      } ,
+	std          :: { future::Future, time::Duration                                         } ,
 };
 
+#[ cfg( target_arch = "wasm32" ) ]
+//
+use futures_util::future::FutureExt;
+
 
 /// An executor that spawns tasks on async-std. In contrast to the other executors, this one
 /// is not self contained, because async-std does not provide an API that allows that,
@@ -35,7 +41,7 @@ impl AsyncStd
 	#[cfg(not(target_os = "unknown"))]
 	#[ cfg_attr( nightly, doc(cfg(not( target_os = "unknown" ))) ) ]
 	//
-	pub fn block_on<F: std::future::Future>(future: F) -> F::Output
+	pub fn block_on<F: Future>(future: F) -> F::Output
 	{
 		async_std_crate::task::block_on( future )
 	}
@@ -71,19 +77,19 @@ impl Spawn for AsyncStd
 
 
 
+impl SpawnMany for AsyncStd {}
+
+
+
 #[ cfg( not(target_arch = "wasm32") ) ]
 //
 impl<Out: 'static + Send> SpawnHandle<Out> for AsyncStd
 {
 	fn spawn_handle_obj( &self, future: FutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>
 	{
-		let (fut, a_handle) = abortable( future );
-
-		Ok( JoinHandle{ inner: crate::join_handle::InnerJh::AsyncStd
+		Ok( JoinHandle{ inner: InnerJh::AsyncStd
 		{
-			handle  : async_std_crate::task::spawn( fut ) ,
-			detached: AtomicBool::new( false )            ,
-			a_handle                                      ,
+			handle: Some( async_std_crate::task::spawn( future ) ),
 		}})
 	}
 }
@@ -96,36 +102,119 @@ impl<Out: 'static + Send> SpawnHandle<Out> for AsyncStd
 {
 	fn spawn_handle_obj( &self, future: FutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>
 	{
-		let (fut, a_handle) = abortable( future );
+		// async-std can't cancel tasks on Wasm.
+		//
+		let (remote, handle) = future.remote_handle();
 
+		async_std_crate::task::spawn_local( remote );
+
+		Ok( JoinHandle{ inner: InnerJh::RemoteHandle( Some(handle) ) } )
+	}
+}
+
+
+
+#[ cfg( not(target_arch = "wasm32") ) ]
+//
+impl<Out: 'static> LocalSpawnHandle<Out> for AsyncStd
+{
+	fn spawn_handle_local_obj( &self, future: LocalFutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>
+	{
 		Ok( JoinHandle{ inner: InnerJh::AsyncStd
 		{
-			handle  : async_std_crate::task::spawn_local( fut ) ,
-			detached: AtomicBool::new( false )                  ,
-			a_handle                                            ,
+			handle: Some( async_std_crate::task::spawn_local( future ) ),
 		}})
 	}
 }
 
 
 
+#[ cfg( target_arch = "wasm32" ) ]
+//
 impl<Out: 'static> LocalSpawnHandle<Out> for AsyncStd
 {
 	fn spawn_handle_local_obj( &self, future: LocalFutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>
 	{
-		let (fut, a_handle) = abortable( future );
+		// async-std can't cancel tasks on Wasm.
+		//
+		let (remote, handle) = future.remote_handle();
+
+		async_std_crate::task::spawn_local( remote );
+
+		Ok( JoinHandle{ inner: InnerJh::RemoteHandle( Some(handle) ) } )
+	}
+}
+
+
+
+#[ cfg( not(target_arch = "wasm32") ) ]
+//
+impl<Out: 'static + Send> SpawnHandleGeneric<Out> for AsyncStd
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
 
+		where Fut: Future<Output = Out> + Send + 'static
+	{
 		Ok( JoinHandle{ inner: InnerJh::AsyncStd
 		{
-			handle  : async_std_crate::task::spawn_local( fut ) ,
-			detached: AtomicBool::new( false )            ,
-			a_handle                                      ,
+			handle: Some( async_std_crate::task::spawn( future ) ),
 		}})
 	}
 }
 
 
 
+#[ cfg( target_arch = "wasm32" ) ]
+//
+impl<Out: 'static + Send> SpawnHandleGeneric<Out> for AsyncStd
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		self.spawn_handle_local_generic( future )
+	}
+}
+
+
+
+#[ cfg( not(target_arch = "wasm32") ) ]
+//
+impl<Out: 'static> LocalSpawnHandleGeneric<Out> for AsyncStd
+{
+	fn spawn_handle_local_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + 'static
+	{
+		Ok( JoinHandle{ inner: InnerJh::AsyncStd
+		{
+			handle: Some( async_std_crate::task::spawn_local( future ) ),
+		}})
+	}
+}
+
+
+
+#[ cfg( target_arch = "wasm32" ) ]
+//
+impl<Out: 'static> LocalSpawnHandleGeneric<Out> for AsyncStd
+{
+	fn spawn_handle_local_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + 'static
+	{
+		// async-std can't cancel tasks on Wasm.
+		//
+		let (remote, handle) = future.remote_handle();
+
+		async_std_crate::task::spawn_local( remote );
+
+		Ok( JoinHandle{ inner: InnerJh::RemoteHandle( Some(handle) ) } )
+	}
+}
+
+
+
 impl LocalSpawn for AsyncStd
 {
 	fn spawn_local_obj( &self, future: LocalFutureObj<'static, ()> ) -> Result<(), SpawnError>
@@ -139,6 +228,20 @@ impl LocalSpawn for AsyncStd
 }
 
 
+
+impl LocalSpawnMany for AsyncStd {}
+
+
+impl Timer for AsyncStd
+{
+	fn sleep( &self, dur: Duration ) -> BoxFuture<'static, ()>
+	{
+		Box::pin( async_std_crate::task::sleep( dur ) )
+	}
+}
+
+
+
 impl std::fmt::Debug for AsyncStd
 {
 	fn fmt( &self, f: &mut std::fmt::Formatter<'_> ) -> std::fmt::Result
@@ -1,9 +1,12 @@
 use
 {
-	crate                :: { SpawnHandle, LocalSpawnHandle, JoinHandle, join_handle::InnerJh } ,
-	wasm_bindgen_futures :: { spawn_local                                                     } ,
-	futures_task         :: { FutureObj, LocalFutureObj, Spawn, LocalSpawn, SpawnError        } ,
-	futures_util         :: { FutureExt                                                       } ,
+	crate                :: { SpawnHandle, LocalSpawnHandle, JoinHandle, Timer, join_handle::InnerJh } ,
+	crate                :: { SpawnHandleGeneric, LocalSpawnHandleGeneric                            } ,
+	crate                :: { SpawnMany, LocalSpawnMany                                              } ,
+	wasm_bindgen_futures :: { spawn_local                                                            } ,
+	futures_task         :: { FutureObj, LocalFutureObj, Spawn, LocalSpawn, SpawnError               } ,
+	futures_util         :: { FutureExt, future::BoxFuture                                           } ,
+	std                  :: { future::Future, time::Duration                                         } ,
 };
 
 
@@ -42,6 +45,10 @@ impl Spawn for Bindgen
 
 
 
+impl SpawnMany for Bindgen {}
+
+
+
 impl LocalSpawn for Bindgen
 {
 	fn spawn_local_obj( &self, future: LocalFutureObj<'static, ()> ) -> Result<(), SpawnError>
@@ -54,6 +61,10 @@ impl LocalSpawn for Bindgen
 
 
 
+impl LocalSpawnMany for Bindgen {}
+
+
+
 impl<Out: 'static + Send> SpawnHandle<Out> for Bindgen
 {
 	fn spawn_handle_obj( &self, future: FutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>
@@ -80,6 +91,45 @@ impl<Out: 'static> LocalSpawnHandle<Out> for Bindgen
 
 
 
+impl<Out: 'static + Send> SpawnHandleGeneric<Out> for Bindgen
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		self.spawn_handle_local_generic( future )
+	}
+}
+
+
+
+impl<Out: 'static> LocalSpawnHandleGeneric<Out> for Bindgen
+{
+	fn spawn_handle_local_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + 'static
+	{
+		let (fut, handle) = future.remote_handle();
+		spawn_local(fut);
+
+		Ok( JoinHandle{ inner: InnerJh::RemoteHandle( Some(handle) ) } )
+	}
+}
+
+
+
+/// Uses futures-timer, which is backed by `setTimeout` on Wasm.
+//
+impl Timer for Bindgen
+{
+	fn sleep( &self, dur: Duration ) -> BoxFuture<'static, ()>
+	{
+		Box::pin( futures_timer::Delay::new( dur ) )
+	}
+}
+
+
+
 impl std::fmt::Debug for Bindgen
 {
 	fn fmt( &self, f: &mut std::fmt::Formatter<'_> ) -> std::fmt::Result
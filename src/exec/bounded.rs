@@ -0,0 +1,539 @@
+//! Provides Bounded, an executor wrapper that limits the number of tasks running concurrently.
+//
+use
+{
+	crate        :: { SpawnHandle, SpawnHandleExt, LocalSpawnHandle, JoinHandle, Timer          } ,
+	crate        :: { SpawnMany, LocalSpawnMany, SpawnHandleGeneric, LocalSpawnHandleGeneric    } ,
+	futures_task :: { FutureObj, LocalFutureObj, Spawn, LocalSpawn, SpawnError                  } ,
+	futures_util :: { future::{ BoxFuture, FutureExt }, task::SpawnExt                          } ,
+	std          :: { collections::VecDeque, future::Future, pin::Pin, time::{ Duration, Instant } } ,
+	std          :: { sync::{ Arc, Mutex, MutexGuard }, task::{ Context, Poll, Waker }          } ,
+};
+
+
+/// An executor wrapper that limits the number of tasks spawned through it that run concurrently. A task takes up
+/// a slot from the moment it is spawned until it finishes or is dropped, eg. because it's [`JoinHandle`] was dropped.
+///
+/// There are two ways to spawn when all slots are taken:
+///
+/// - the spawn traits fail fast. They return [`SpawnError::shutdown`], as that is the only error _futures_ lets us
+///   create. Use this when you would rather shed load than queue it.
+/// - [`spawn_when_ready`](Bounded::spawn_when_ready) and [`spawn_handle_when_ready`](Bounded::spawn_handle_when_ready)
+///   wait for a slot to free up, which gives you backpressure.
+///
+/// Waiters are served in the order they started waiting. The traits will not take a slot while others are waiting
+/// for one.
+///
+/// Clones share the same slots.
+///
+/// ```
+/// use
+/// {
+///    async_executors :: { Bounded, ThreadPool, SpawnHandleExt } ,
+///    futures         :: { executor::block_on                  } ,
+/// };
+///
+/// let exec = Bounded::new( ThreadPool::new().expect( "create threadpool" ), 2 );
+///
+/// block_on( async
+/// {
+///    let mut handles = Vec::new();
+///
+///    for i in 0..10u8
+///    {
+///       // Never more than 2 of these run at the same time.
+///       //
+///       handles.push( exec.spawn_handle_when_ready( async move { i * 2 } ).await.expect( "spawn" ) );
+///    }
+///
+///    for (i, handle) in handles.into_iter().enumerate()
+///    {
+///       assert_eq!( handle.await, i as u8 * 2 );
+///    }
+/// });
+/// ```
+//
+#[ derive( Clone ) ]
+//
+pub struct Bounded<E>
+{
+	exec : E            ,
+	slots: Arc<Slots>   ,
+}
+
+
+struct Slots
+{
+	limit: usize         ,
+	inner: Mutex<Inner>  ,
+}
+
+
+struct Inner
+{
+	running: usize                    ,
+	next_id: u64                      ,
+	waiters: VecDeque<( u64, Waker )> ,
+}
+
+
+impl Slots
+{
+	fn lock( &self ) -> MutexGuard<'_, Inner>
+	{
+		// A panic while holding the lock cannot leave the slots in an inconsistent state.
+		//
+		self.inner.lock().unwrap_or_else( |e| e.into_inner() )
+	}
+
+
+	// Take a slot unless they are all taken or someone is waiting for one.
+	//
+	fn try_acquire( self: &Arc<Self> ) -> Option<Permit>
+	{
+		let mut inner = self.lock();
+
+		if inner.running < self.limit && inner.waiters.is_empty()
+		{
+			inner.running += 1;
+
+			return Some( Permit{ slots: self.clone() } );
+		}
+
+		None
+	}
+}
+
+
+
+impl<E> Bounded<E>
+{
+	/// Wrap `exec` so that at most `limit` tasks spawned through the wrapper run at the same time.
+	///
+	/// # Panics
+	///
+	/// When `limit` is zero.
+	//
+	pub fn new( exec: E, limit: usize ) -> Self
+	{
+		assert!( limit > 0, "Bounded: the limit must be at least 1" );
+
+		let inner = Inner{ running: 0, next_id: 0, waiters: VecDeque::new() };
+
+		Self { exec, slots: Arc::new( Slots{ limit, inner: Mutex::new( inner ) } ) }
+	}
+
+
+	/// The maximum number of tasks that run concurrently.
+	//
+	pub fn limit( &self ) -> usize
+	{
+		self.slots.limit
+	}
+
+
+	/// The number of tasks that currently take up a slot.
+	//
+	pub fn running( &self ) -> usize
+	{
+		self.slots.lock().running
+	}
+
+
+	/// Access the wrapped executor.
+	//
+	pub fn inner( &self ) -> &E
+	{
+		&self.exec
+	}
+
+
+	/// Wait for a free slot, then spawn the future.
+	//
+	pub async fn spawn_when_ready( &self, future: impl Future<Output = ()> + Send + 'static ) -> Result<(), SpawnError>
+
+		where E: Spawn
+	{
+		let permit = Acquire::new( self.slots.clone() ).await;
+
+		self.exec.spawn( async move
+		{
+			let _permit = permit;
+			future.await;
+		})
+	}
+
+
+	/// Wait for a free slot, then spawn the future and return a [`JoinHandle`] to await it's output.
+	//
+	pub async fn spawn_handle_when_ready<Out>( &self, future: impl Future<Output = Out> + Send + 'static ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where E: SpawnHandle<Out>, Out: 'static + Send
+	{
+		let permit = Acquire::new( self.slots.clone() ).await;
+
+		self.exec.spawn_handle( async move
+		{
+			let _permit = permit;
+			future.await
+		})
+	}
+
+
+	// Takes a slot for each future until none are left, in which case `full` is set and the remaining futures
+	// are dropped.
+	//
+	fn permits<'a, F: 'a>( &'a self, futures: &'a mut dyn Iterator<Item = F>, full: &'a mut bool )
+
+		-> impl Iterator< Item = (Permit, F) > + 'a
+	{
+		futures.map_while( move |future| match self.slots.try_acquire()
+		{
+			Some( permit ) => Some(( permit, future )),
+			None           => { *full = true; None }
+		})
+	}
+}
+
+
+
+impl<E: Spawn> Spawn for Bounded<E>
+{
+	fn spawn_obj( &self, future: FutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		let permit = self.slots.try_acquire().ok_or_else( SpawnError::shutdown )?;
+
+		self.exec.spawn_obj( FutureObj::new( async move
+		{
+			let _permit = permit;
+			future.await;
+
+		}.boxed() ))
+	}
+}
+
+
+
+impl<E: SpawnMany> SpawnMany for Bounded<E>
+{
+	fn spawn_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		let mut full = false;
+
+		self.exec.spawn_obj_many( &mut self.permits( futures, &mut full ).map( |(permit, future)| FutureObj::new( async move
+		{
+			let _permit = permit;
+			future.await;
+
+		}.boxed() )))?;
+
+		if full { Err( SpawnError::shutdown() ) } else { Ok(()) }
+	}
+}
+
+
+
+impl<E: LocalSpawn> LocalSpawn for Bounded<E>
+{
+	fn spawn_local_obj( &self, future: LocalFutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		let permit = self.slots.try_acquire().ok_or_else( SpawnError::shutdown )?;
+
+		self.exec.spawn_local_obj( LocalFutureObj::new( async move
+		{
+			let _permit = permit;
+			future.await;
+
+		}.boxed_local() ))
+	}
+}
+
+
+
+impl<E: LocalSpawnMany> LocalSpawnMany for Bounded<E>
+{
+	fn spawn_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		let mut full = false;
+
+		self.exec.spawn_local_obj_many( &mut self.permits( futures, &mut full ).map( |(permit, future)| LocalFutureObj::new( async move
+		{
+			let _permit = permit;
+			future.await;
+
+		}.boxed_local() )))?;
+
+		if full { Err( SpawnError::shutdown() ) } else { Ok(()) }
+	}
+}
+
+
+
+impl<E, Out> SpawnHandle<Out> for Bounded<E> where E: SpawnHandle<Out>, Out: 'static + Send
+{
+	fn spawn_handle_obj( &self, future: FutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>
+	{
+		let permit = self.slots.try_acquire().ok_or_else( SpawnError::shutdown )?;
+
+		self.exec.spawn_handle_obj( FutureObj::new( async move
+		{
+			let _permit = permit;
+			future.await
+
+		}.boxed() ))
+	}
+
+
+	fn spawn_handle_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		let mut full = false;
+
+		let handles = self.exec.spawn_handle_obj_many( &mut self.permits( futures, &mut full ).map( |(permit, future)| FutureObj::new( async move
+		{
+			let _permit = permit;
+			future.await
+
+		}.boxed() )))?;
+
+		// Like the default implementation, this drops the handles, which cancels the tasks.
+		//
+		if full { Err( SpawnError::shutdown() ) } else { Ok( handles ) }
+	}
+}
+
+
+
+impl<E, Out> LocalSpawnHandle<Out> for Bounded<E> where E: LocalSpawnHandle<Out>, Out: 'static
+{
+	fn spawn_handle_local_obj( &self, future: LocalFutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>
+	{
+		let permit = self.slots.try_acquire().ok_or_else( SpawnError::shutdown )?;
+
+		self.exec.spawn_handle_local_obj( LocalFutureObj::new( async move
+		{
+			let _permit = permit;
+			future.await
+
+		}.boxed_local() ))
+	}
+
+
+	fn spawn_handle_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		let mut full = false;
+
+		let handles = self.exec.spawn_handle_local_obj_many( &mut self.permits( futures, &mut full ).map( |(permit, future)| LocalFutureObj::new( async move
+		{
+			let _permit = permit;
+			future.await
+
+		}.boxed_local() )))?;
+
+		if full { Err( SpawnError::shutdown() ) } else { Ok( handles ) }
+	}
+}
+
+
+
+impl<E, Out> SpawnHandleGeneric<Out> for Bounded<E> where E: SpawnHandleGeneric<Out>, Out: 'static + Send
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		let permit = self.slots.try_acquire().ok_or_else( SpawnError::shutdown )?;
+
+		self.exec.spawn_handle_generic( async move
+		{
+			let _permit = permit;
+			future.await
+		})
+	}
+}
+
+
+
+impl<E, Out> LocalSpawnHandleGeneric<Out> for Bounded<E> where E: LocalSpawnHandleGeneric<Out>, Out: 'static
+{
+	fn spawn_handle_local_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + 'static
+	{
+		let permit = self.slots.try_acquire().ok_or_else( SpawnError::shutdown )?;
+
+		self.exec.spawn_handle_local_generic( async move
+		{
+			let _permit = permit;
+			future.await
+		})
+	}
+}
+
+
+
+impl<E: Timer> Timer for Bounded<E>
+{
+	fn sleep      ( &self, dur     : Duration ) -> BoxFuture<'static, ()> { self.exec.sleep( dur )            }
+	fn now        ( &self                     ) -> Instant                { self.exec.now()                   }
+	fn sleep_until( &self, deadline: Instant  ) -> BoxFuture<'static, ()> { self.exec.sleep_until( deadline ) }
+}
+
+
+
+impl<E: std::fmt::Debug> std::fmt::Debug for Bounded<E>
+{
+	fn fmt( &self, f: &mut std::fmt::Formatter<'_> ) -> std::fmt::Result
+	{
+		let inner = self.slots.lock();
+
+		f.debug_struct( "Bounded" )
+
+			.field( "exec"   , &self.exec           )
+			.field( "limit"  , &self.slots.limit    )
+			.field( "running", &inner.running       )
+			.field( "waiting", &inner.waiters.len() )
+			.finish()
+	}
+}
+
+
+
+// Holds a slot, frees it on drop. Lives inside the spawned task.
+//
+struct Permit
+{
+	slots: Arc<Slots>,
+}
+
+
+impl Drop for Permit
+{
+	fn drop( &mut self )
+	{
+		let mut inner = self.slots.lock();
+
+		inner.running -= 1;
+
+		let next = inner.waiters.front().map( |(_, waker)| waker.clone() );
+
+		drop( inner );
+
+		if let Some( waker ) = next { waker.wake() }
+	}
+}
+
+
+
+// Waits for a free slot.
+//
+struct Acquire
+{
+	slots: Arc<Slots> ,
+	id   : Option<u64>,
+}
+
+
+impl Acquire
+{
+	fn new( slots: Arc<Slots> ) -> Self
+	{
+		Self { slots, id: None }
+	}
+}
+
+
+impl Future for Acquire
+{
+	type Output = Permit;
+
+	fn poll( mut self: Pin<&mut Self>, cx: &mut Context<'_> ) -> Poll<Permit>
+	{
+		let mut inner = self.slots.lock();
+
+		let first = match self.id
+		{
+			Some( id ) => inner.waiters.front().map( |(w, _)| *w ) == Some( id ),
+			None       => inner.waiters.is_empty(),
+		};
+
+		if first && inner.running < self.slots.limit
+		{
+			if self.id.is_some() { inner.waiters.pop_front(); }
+
+			inner.running += 1;
+
+			// If there is room for more, let the next waiter know.
+			//
+			let next = if inner.running < self.slots.limit
+			{
+				inner.waiters.front().map( |(_, waker)| waker.clone() )
+			}
+
+			else { None };
+
+			drop( inner );
+
+			self.id = None;
+
+			if let Some( waker ) = next { waker.wake() }
+
+			return Poll::Ready( Permit{ slots: self.slots.clone() } );
+		}
+
+		match self.id
+		{
+			Some( id ) =>
+			{
+				if let Some( entry ) = inner.waiters.iter_mut().find( |(w, _)| *w == id )
+				{
+					entry.1 = cx.waker().clone();
+				}
+			}
+
+			None =>
+			{
+				let id = inner.next_id;
+				inner.next_id += 1;
+				inner.waiters.push_back( (id, cx.waker().clone()) );
+
+				drop( inner );
+				self.id = Some( id );
+			}
+		}
+
+		Poll::Pending
+	}
+}
+
+
+impl Drop for Acquire
+{
+	fn drop( &mut self )
+	{
+		if let Some( id ) = self.id
+		{
+			let mut inner = self.slots.lock();
+
+			let first = inner.waiters.front().map( |(w, _)| *w ) == Some( id );
+
+			inner.waiters.retain( |(w, _)| *w != id );
+
+			// If we were first in line, we might have been woken up for a free slot. Pass it on.
+			//
+			let next = if first && inner.running < self.slots.limit
+			{
+				inner.waiters.front().map( |(_, waker)| waker.clone() )
+			}
+
+			else { None };
+
+			drop( inner );
+
+			if let Some( waker ) = next { waker.wake() }
+		}
+	}
+}
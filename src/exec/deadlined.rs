@@ -0,0 +1,243 @@
+//! Provides Deadlined, an executor wrapper that gives every task a time budget.
+//
+use
+{
+	crate        :: { SpawnHandle, SpawnHandleExt, LocalSpawnHandle, LocalSpawnHandleExt, JoinHandle, Timer, DeadlineExceeded } ,
+	crate        :: { SpawnMany, LocalSpawnMany, SpawnHandleGeneric, LocalSpawnHandleGeneric                            } ,
+	futures_task :: { FutureObj, LocalFutureObj, Spawn, LocalSpawn, SpawnError                                              } ,
+	futures_util :: { future::{ BoxFuture, FutureExt, select, Either }, pin_mut                                             } ,
+	std          :: { future::Future, time::{ Duration, Instant }                                                           } ,
+};
+
+
+/// An executor wrapper that cancels every task spawned through it which hasn't finished within the time budget
+/// given to [`new`](Deadlined::new), counted from the moment it is spawned.
+///
+/// Tasks spawned with [`Spawn`] and [`LocalSpawn`] are silently dropped when they run out of time. To find out whether
+/// a task finished in time, use [`spawn_handle_deadlined`](Deadlined::spawn_handle_deadlined) or
+/// [`spawn_handle_local_deadlined`](Deadlined::spawn_handle_local_deadlined), which return a [`JoinHandle`] that resolves to [`DeadlineExceeded`] if it didn't.
+///
+/// `Deadlined` doesn't implement [`SpawnHandle`], as the handles resolve to a `Result`. The methods have their own names
+/// so they can't be confused with [`SpawnHandleExt::spawn_handle`]. It does implement [`SpawnHandleGeneric`] and
+/// [`LocalSpawnHandleGeneric`] for futures that return `Result<Out, DeadlineExceeded>` themselves, so code that is
+/// generic over the executor gets the deadline too. Their handles resolve to `Err(DeadlineExceeded)` when the future
+/// runs out of time.
+///
+/// The deadlines are tracked with the [`Timer`] of the wrapped executor. To give a task a different deadline,
+/// spawn it on the wrapped executor with [`spawn_handle_with_deadline`](SpawnHandleExt::spawn_handle_with_deadline).
+///
+/// ```
+/// use
+/// {
+///    async_executors :: { Deadlined, ThreadPool, DeadlineExceeded } ,
+///    futures         :: { executor::block_on, future::pending     } ,
+///    std             :: { time::Duration                          } ,
+/// };
+///
+/// let exec = Deadlined::new( ThreadPool::new().expect( "create threadpool" ), Duration::from_millis( 10 ) );
+///
+/// let quick = exec.spawn_handle_deadlined( async { 5u8 }   ).expect( "spawn" );
+/// let slow  = exec.spawn_handle_deadlined( pending::<u8>() ).expect( "spawn" );
+///
+/// assert_eq!( block_on( quick ), Ok( 5 )                 );
+/// assert_eq!( block_on( slow  ), Err( DeadlineExceeded ) );
+/// ```
+//
+#[ derive( Debug, Clone ) ]
+//
+pub struct Deadlined<E>
+{
+	exec  : E        ,
+	budget: Duration ,
+}
+
+
+impl<E> Deadlined<E>
+{
+	/// Wrap `exec`, so that tasks spawned through the wrapper get cancelled when they run for longer than `budget`.
+	//
+	pub fn new( exec: E, budget: Duration ) -> Self
+	{
+		Self { exec, budget }
+	}
+
+
+	/// The time budget of every task.
+	//
+	pub fn budget( &self ) -> Duration
+	{
+		self.budget
+	}
+
+
+	/// Access the wrapped executor.
+	//
+	pub fn inner( &self ) -> &E
+	{
+		&self.exec
+	}
+}
+
+
+impl<E: Timer> Deadlined<E>
+{
+	/// Spawn a future and return a [`JoinHandle`] that resolves to it's output, or to [`DeadlineExceeded`] when it
+	/// didn't finish within the budget.
+	//
+	pub fn spawn_handle_deadlined<Out>( &self, future: impl Future<Output = Out> + Send + 'static )
+
+		-> Result<JoinHandle< Result<Out, DeadlineExceeded> >, SpawnError>
+
+		where E: SpawnHandle<Out> + SpawnHandle< Result<Out, DeadlineExceeded> >, Out: 'static + Send
+	{
+		self.exec.spawn_handle_with_deadline( future, self.deadline() )
+	}
+
+
+	/// Spawn a `!Send` future and return a [`JoinHandle`] that resolves to it's output, or to [`DeadlineExceeded`]
+	/// when it didn't finish within the budget.
+	//
+	pub fn spawn_handle_local_deadlined<Out>( &self, future: impl Future<Output = Out> + 'static )
+
+		-> Result<JoinHandle< Result<Out, DeadlineExceeded> >, SpawnError>
+
+		where E: LocalSpawnHandle<Out> + LocalSpawnHandle< Result<Out, DeadlineExceeded> >, Out: 'static
+	{
+		self.exec.spawn_handle_local_with_deadline( future, self.deadline() )
+	}
+
+
+	fn deadline( &self ) -> Instant
+	{
+		self.exec.now() + self.budget
+	}
+}
+
+
+
+impl<E: Spawn + Timer> Spawn for Deadlined<E>
+{
+	fn spawn_obj( &self, future: FutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		let sleep = self.exec.sleep( self.budget );
+
+		self.exec.spawn_obj( FutureObj::new( async move
+		{
+			select( future, sleep ).await;
+
+		}.boxed() ))
+	}
+}
+
+
+
+impl<E: SpawnMany + Timer> SpawnMany for Deadlined<E>
+{
+	fn spawn_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		self.exec.spawn_obj_many( &mut futures.map( |future|
+		{
+			let sleep = self.exec.sleep( self.budget );
+
+			FutureObj::new( async move
+			{
+				select( future, sleep ).await;
+
+			}.boxed() )
+		}))
+	}
+}
+
+
+
+impl<E: LocalSpawn + Timer> LocalSpawn for Deadlined<E>
+{
+	fn spawn_local_obj( &self, future: LocalFutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		let sleep = self.exec.sleep( self.budget );
+
+		self.exec.spawn_local_obj( LocalFutureObj::new( async move
+		{
+			select( future, sleep ).await;
+
+		}.boxed_local() ))
+	}
+}
+
+
+
+impl<E: LocalSpawnMany + Timer> LocalSpawnMany for Deadlined<E>
+{
+	fn spawn_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		self.exec.spawn_local_obj_many( &mut futures.map( |future|
+		{
+			let sleep = self.exec.sleep( self.budget );
+
+			LocalFutureObj::new( async move
+			{
+				select( future, sleep ).await;
+
+			}.boxed_local() )
+		}))
+	}
+}
+
+
+
+impl<E, Out> SpawnHandleGeneric< Result<Out, DeadlineExceeded> > for Deadlined<E>
+
+	where E: SpawnHandleGeneric< Result<Out, DeadlineExceeded> > + Timer, Out: 'static + Send
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle< Result<Out, DeadlineExceeded> >, SpawnError>
+
+		where Fut: Future< Output = Result<Out, DeadlineExceeded> > + Send + 'static
+	{
+		let sleep = self.exec.sleep( self.budget );
+
+		self.exec.spawn_handle_generic( async move
+		{
+			pin_mut!( future );
+
+			match select( future, sleep ).await
+			{
+				Either::Left ( (out, _) ) => out                   ,
+				Either::Right( _        ) => Err( DeadlineExceeded ),
+			}
+		})
+	}
+}
+
+
+
+impl<E, Out> LocalSpawnHandleGeneric< Result<Out, DeadlineExceeded> > for Deadlined<E>
+
+	where E: LocalSpawnHandleGeneric< Result<Out, DeadlineExceeded> > + Timer, Out: 'static
+{
+	fn spawn_handle_local_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle< Result<Out, DeadlineExceeded> >, SpawnError>
+
+		where Fut: Future< Output = Result<Out, DeadlineExceeded> > + 'static
+	{
+		let sleep = self.exec.sleep( self.budget );
+
+		self.exec.spawn_handle_local_generic( async move
+		{
+			pin_mut!( future );
+
+			match select( future, sleep ).await
+			{
+				Either::Left ( (out, _) ) => out                   ,
+				Either::Right( _        ) => Err( DeadlineExceeded ),
+			}
+		})
+	}
+}
+
+
+
+impl<E: Timer> Timer for Deadlined<E>
+{
+	fn sleep      ( &self, dur     : Duration ) -> BoxFuture<'static, ()> { self.exec.sleep( dur )            }
+	fn now        ( &self                     ) -> Instant                { self.exec.now()                   }
+	fn sleep_until( &self, deadline: Instant  ) -> BoxFuture<'static, ()> { self.exec.sleep_until( deadline ) }
+}
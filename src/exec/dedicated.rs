@@ -0,0 +1,279 @@
+//! Provides DedicatedExec, a tokio threadpool on it's own threads for CPU heavy work.
+//
+use
+{
+	crate           :: { SpawnHandle, SpawnHandleGeneric, JoinHandle, iface::join_handle::InnerJh    } ,
+	crate           :: { SpawnMany                                                                   } ,
+	futures_task    :: { FutureObj, Spawn, SpawnError                                                } ,
+	futures_util    :: { future::{ BoxFuture, FutureExt, Shared }, stream::StreamExt                 } ,
+	futures_channel :: { mpsc, oneshot                                                               } ,
+	std             :: { fmt, future::Future, io, panic::AssertUnwindSafe, thread, time::Duration    } ,
+	std             :: { sync::{ Arc, Mutex, atomic::AtomicBool }                                    } ,
+	tokio           :: { runtime::{ Handle, Runtime }, task::JoinHandle as TokioJoinHandle           } ,
+};
+
+
+/// A multi threaded tokio runtime for CPU heavy futures, isolated from the executor that does your IO.
+///
+/// Create it with [`TokioTpBuilder::build_dedicated`](crate::TokioTpBuilder::build_dedicated). The runtime is
+/// owned by a thread of it's own, so the executor can be used and dropped from within any other executor, which
+/// isn't true for a tokio `Runtime`. Jobs only ever run on the worker threads of this runtime, so a job that
+/// hogs it's thread doesn't keep the tasks of your IO executor from running.
+///
+/// The [`JoinHandle`]s it returns can be awaited from any executor in this crate, eg. from [`TokioCt`](crate::TokioCt)
+/// or [`AsyncStd`](crate::AsyncStd). As usual, dropping one cancels the job.
+///
+/// ## Panics
+///
+/// A job that panics only unwinds it's own task, the worker threads keep running. Awaiting the `JoinHandle` of such
+/// a job resumes the panic in the task that awaits it, like for [`TokioTp`](crate::TokioTp). Use
+/// [`spawn_catch_unwind`](DedicatedExec::spawn_catch_unwind) to get the panic as an error instead, so it can't
+/// bring down your IO tasks.
+///
+/// ## Shutdown
+///
+/// [`shutdown`](DedicatedExec::shutdown) stops accepting new jobs and lets the running ones finish before the
+/// runtime is dropped. [`shutdown_timeout`](DedicatedExec::shutdown_timeout) cancels the jobs that are still running
+/// after the timeout. Both return a future that resolves once the runtime is gone, which can be awaited on any
+/// executor. Dropping the last clone of the executor shuts it down gracefully in the background.
+///
+/// ```
+/// use
+/// {
+///    async_executors :: { TokioCtBuilder, TokioTpBuilder, SpawnHandleExt } ,
+/// };
+///
+/// let compute = TokioTpBuilder::new().build_dedicated().expect( "create dedicated executor" );
+/// let io      = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+///
+/// let sum = io.block_on( async
+/// {
+///    let sum = compute.spawn_handle( async { (0..1_000u64).sum::<u64>() } ).expect( "spawn" );
+///
+///    sum.await
+/// });
+///
+/// assert_eq!( sum, 499_500 );
+///
+/// io.block_on( compute.shutdown() );
+///
+/// assert!( compute.spawn_handle( async {} ).is_err() );
+/// ```
+//
+#[ derive( Clone ) ]
+//
+#[ cfg_attr( nightly, doc(cfg( feature = "tokio_tp" )) ) ]
+//
+pub struct DedicatedExec
+{
+	inner: Arc<Inner>,
+}
+
+
+struct Inner
+{
+	handle : Handle                          ,
+	state  : Mutex< Option<Running> >        ,
+	stopped: Shared< oneshot::Receiver<()> > ,
+}
+
+
+// Only exists until shutdown is requested.
+//
+struct Running
+{
+	// Every job holds a clone, so the receiver on the runtime thread sees the end of the stream
+	// once this one is dropped and all jobs are done.
+	//
+	tasks: mpsc::Sender<()>,
+
+	// Tells the runtime thread to shut down, with an optional timeout.
+	//
+	shutdown: oneshot::Sender< Option<Duration> >,
+}
+
+
+
+impl DedicatedExec
+{
+	/// Move the runtime to a thread of it's own. See [`TokioTpBuilder::build_dedicated`](crate::TokioTpBuilder::build_dedicated).
+	//
+	pub(crate) fn new( rt: Runtime ) -> Result<Self, io::Error>
+	{
+		let handle                   = rt.handle().clone();
+		let (tasks     , mut tasks_rx) = mpsc::channel( 0 );
+		let (shutdown  , shutdown_rx ) = oneshot::channel();
+		let (stopped_tx, stopped     ) = oneshot::channel();
+
+		thread::Builder::new().name( "async_executors-dedicated".to_string() ).spawn( move ||
+		{
+			rt.block_on( async move
+			{
+				// When the sender is dropped, shut down gracefully.
+				//
+				let timeout = shutdown_rx.await.unwrap_or( None );
+				let done    = tasks_rx.next();
+
+				match timeout
+				{
+					None          => { done.await;                                          }
+					Some(timeout) => { let _ = tokio::time::timeout( timeout, done ).await; }
+				}
+			});
+
+			// Don't wait for jobs that got cancelled but are still stuck in a poll.
+			//
+			rt.shutdown_background();
+
+			let _ = stopped_tx.send(());
+
+		})?;
+
+		Ok( Self { inner: Arc::new( Inner
+		{
+			handle                                                   ,
+			state  : Mutex::new( Some( Running{ tasks, shutdown } ) ) ,
+			stopped: stopped.shared()                                ,
+		})})
+	}
+
+
+	/// Stop accepting new jobs and shut down once all running jobs have finished. The returned future resolves when
+	/// the runtime is gone. Calling it again, or from a clone, only waits for that.
+	///
+	/// Don't await it from within a job on this executor, as it would wait for itself.
+	//
+	pub fn shutdown( &self ) -> BoxFuture<'static, ()>
+	{
+		self.stop( None )
+	}
+
+
+	/// Stop accepting new jobs and shut down once all running jobs have finished, or when the timeout expires, in
+	/// which case the remaining jobs are cancelled. The returned future resolves when the runtime is gone.
+	//
+	pub fn shutdown_timeout( &self, timeout: Duration ) -> BoxFuture<'static, ()>
+	{
+		self.stop( Some(timeout) )
+	}
+
+
+	/// Whether shutdown was requested. No new jobs can be spawned once it is.
+	//
+	pub fn is_shutdown( &self ) -> bool
+	{
+		self.inner.state.lock().expect( "lock dedicated executor state" ).is_none()
+	}
+
+
+	/// Spawn a job and catch it if it panics, so awaiting the [`JoinHandle`] returns the panic as an error instead of
+	/// resuming it in the task that awaits.
+	///
+	/// As for [`std::panic::catch_unwind`], make sure nothing can observe data the job left in an inconsistent state.
+	//
+	pub fn spawn_catch_unwind<Fut>( &self, future: Fut ) -> Result<JoinHandle< thread::Result<Fut::Output> >, SpawnError>
+
+		where Fut: Future + Send + 'static, Fut::Output: Send + 'static
+	{
+		self.spawn_handle_generic( AssertUnwindSafe( future ).catch_unwind() )
+	}
+
+
+	fn stop( &self, timeout: Option<Duration> ) -> BoxFuture<'static, ()>
+	{
+		if let Some(running) = self.inner.state.lock().expect( "lock dedicated executor state" ).take()
+		{
+			let _ = running.shutdown.send( timeout );
+		}
+
+		// Resolves when the runtime thread is done, whether it got to send or not.
+		//
+		self.inner.stopped.clone().map( |_| () ).boxed()
+	}
+
+
+	// Spawn a job that holds on to a task guard, so shutdown waits for it.
+	//
+	fn spawn_tracked<Fut>( &self, future: Fut ) -> Result<TokioJoinHandle<Fut::Output>, SpawnError>
+
+		where Fut: Future + Send + 'static, Fut::Output: Send + 'static
+	{
+		// Hold the lock while spawning, so shutdown can't start in between.
+		//
+		let state = self.inner.state.lock().expect( "lock dedicated executor state" );
+		let guard = state.as_ref().ok_or_else( SpawnError::shutdown )?.tasks.clone();
+
+		Ok( self.inner.handle.spawn( async move
+		{
+			let _guard = guard;
+			future.await
+		}))
+	}
+}
+
+
+
+impl Spawn for DedicatedExec
+{
+	fn spawn_obj( &self, future: FutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		// We drop the JoinHandle, so the task becomes detached.
+		//
+		drop( self.spawn_tracked( future )? );
+
+		Ok(())
+	}
+
+
+	fn status( &self ) -> Result<(), SpawnError>
+	{
+		if self.is_shutdown() { Err( SpawnError::shutdown() ) } else { Ok(()) }
+	}
+}
+
+
+
+impl SpawnMany for DedicatedExec {}
+
+
+
+impl<Out: 'static + Send> SpawnHandle<Out> for DedicatedExec
+{
+	fn spawn_handle_obj( &self, future: FutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>
+	{
+		Ok( JoinHandle{ inner: InnerJh::Tokio
+		{
+			handle  : self.spawn_tracked( future )? ,
+			detached: AtomicBool::new( false )      ,
+		}})
+	}
+}
+
+
+
+impl<Out: 'static + Send> SpawnHandleGeneric<Out> for DedicatedExec
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		Ok( JoinHandle{ inner: InnerJh::Tokio
+		{
+			handle  : self.spawn_tracked( future )? ,
+			detached: AtomicBool::new( false )      ,
+		}})
+	}
+}
+
+
+
+impl fmt::Debug for DedicatedExec
+{
+	fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result
+	{
+		f.debug_struct( "DedicatedExec" )
+
+			.field( "shutdown", &self.is_shutdown() )
+			.finish()
+	}
+}
@@ -0,0 +1,344 @@
+//! Provides ExecConfig, which describes the executor to build as an AnyExec.
+//
+use
+{
+	crate :: { AnyExec                          } ,
+	std   :: { fmt, error::Error, io, str::FromStr } ,
+};
+
+
+/// The kinds of executor [`AnyExec`] can hold. They are named like the features that enable them,
+/// eg. `tokio_tp`, which is also how they are parsed and deserialized.
+///
+/// All kinds exist regardless of the enabled features, so a configuration can be parsed even if it names an executor
+/// that is not compiled in. [`ExecConfig::build`] returns [`ExecConfigError::Disabled`] in that case.
+//
+#[ derive( Debug, Clone, Copy, PartialEq, Eq, Hash ) ]
+//
+pub enum ExecKind
+{
+	/// [`TokioTp`](crate::TokioTp), needs the `tokio_tp` feature.
+	//
+	TokioTp,
+
+	/// [`TokioCt`](crate::TokioCt), needs the `tokio_ct` feature.
+	//
+	TokioCt,
+
+	/// [`AsyncStd`](crate::AsyncStd), needs the `async_std` feature.
+	//
+	AsyncStd,
+
+	/// [`AsyncGlobal`](crate::AsyncGlobal), needs the `async_global` feature.
+	//
+	AsyncGlobal,
+
+	/// [`GlommioCt`](crate::GlommioCt), needs the `glommio` feature.
+	//
+	GlommioCt,
+
+	/// [`ThreadPool`](crate::ThreadPool), needs the `threadpool` feature.
+	//
+	ThreadPool,
+
+	/// [`LocalPool`](crate::LocalPool), needs the `localpool` feature.
+	//
+	LocalPool,
+}
+
+
+impl ExecKind
+{
+	/// The name of this kind, which is also the feature that enables it.
+	//
+	pub fn name( self ) -> &'static str
+	{
+		match self
+		{
+			ExecKind::TokioTp     => "tokio_tp"     ,
+			ExecKind::TokioCt     => "tokio_ct"     ,
+			ExecKind::AsyncStd    => "async_std"    ,
+			ExecKind::AsyncGlobal => "async_global" ,
+			ExecKind::GlommioCt   => "glommio"      ,
+			ExecKind::ThreadPool  => "threadpool"   ,
+			ExecKind::LocalPool   => "localpool"    ,
+		}
+	}
+}
+
+
+impl fmt::Display for ExecKind
+{
+	fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result
+	{
+		f.write_str( self.name() )
+	}
+}
+
+
+impl FromStr for ExecKind
+{
+	type Err = ExecConfigError;
+
+	fn from_str( s: &str ) -> Result<Self, Self::Err>
+	{
+		match s.trim()
+		{
+			"tokio_tp"     => Ok( ExecKind::TokioTp     ),
+			"tokio_ct"     => Ok( ExecKind::TokioCt     ),
+			"async_std"    => Ok( ExecKind::AsyncStd    ),
+			"async_global" => Ok( ExecKind::AsyncGlobal ),
+			"glommio"      => Ok( ExecKind::GlommioCt   ),
+			"threadpool"   => Ok( ExecKind::ThreadPool  ),
+			"localpool"    => Ok( ExecKind::LocalPool   ),
+
+			other => Err( ExecConfigError::Parse( format!( "unknown executor: {:?}", other ) ) ),
+		}
+	}
+}
+
+
+#[ cfg( feature = "serde" ) ]
+//
+impl<'de> serde::Deserialize<'de> for ExecKind
+{
+	fn deserialize<D: serde::Deserializer<'de>>( deserializer: D ) -> Result<Self, D::Error>
+	{
+		let name = <std::borrow::Cow<'de, str>>::deserialize( deserializer )?;
+
+		name.parse().map_err( serde::de::Error::custom )
+	}
+}
+
+
+
+/// Describes an executor so it can be chosen at runtime, eg. from a configuration file. Use [`build`](ExecConfig::build)
+/// to create it.
+///
+/// The textual form, used by [`FromStr`] and [`from_env`](ExecConfig::from_env), is the name of the [`ExecKind`],
+/// optionally followed by a colon and comma separated options, eg. `tokio_tp:threads=4`.
+///
+/// With the `serde` feature, it can be deserialized from a map like `{ exec = "tokio_tp", threads = 4 }`.
+///
+/// Options:
+///
+/// - `threads`: the number of worker threads, for `tokio_tp` and `threadpool`. Defaults to what the executor chooses.
+//
+#[ derive( Debug, Clone, Copy, PartialEq, Eq, Hash ) ]
+#[ cfg_attr( feature = "serde", derive( serde::Deserialize ), serde( deny_unknown_fields ) ) ]
+//
+pub struct ExecConfig
+{
+	/// Which executor to build.
+	//
+	pub exec: ExecKind,
+
+	/// The number of worker threads, only for `tokio_tp` and `threadpool`.
+	//
+	#[ cfg_attr( feature = "serde", serde( default ) ) ]
+	//
+	pub threads: Option<usize>,
+}
+
+
+impl ExecConfig
+{
+	/// The environment variable read by [`from_env`](ExecConfig::from_env).
+	//
+	pub const ENV_VAR: &'static str = "ASYNC_EXECUTOR";
+
+
+	/// Read the configuration from the `ASYNC_EXECUTOR` environment variable, eg. `ASYNC_EXECUTOR=tokio_tp:threads=4`.
+	//
+	pub fn from_env() -> Result<Self, ExecConfigError>
+	{
+		let var = std::env::var( Self::ENV_VAR )
+
+			.map_err( |e| ExecConfigError::Parse( format!( "{}: {}", Self::ENV_VAR, e ) ) )?
+		;
+
+		var.parse()
+	}
+
+
+	/// Create the executor.
+	///
+	/// Fails if the options don't apply to the executor, if it is not compiled in or if creating it fails.
+	//
+	pub fn build( &self ) -> Result<AnyExec, ExecConfigError>
+	{
+		if let Some(threads) = self.threads
+		{
+			if self.exec != ExecKind::TokioTp && self.exec != ExecKind::ThreadPool
+			{
+				return Err( ExecConfigError::Parse( format!( "the threads option is not supported by {}", self.exec ) ) );
+			}
+
+			if threads == 0
+			{
+				return Err( ExecConfigError::Parse( "threads must be at least 1".to_string() ) );
+			}
+		}
+
+		match self.exec
+		{
+			#[ cfg( feature = "tokio_tp" ) ]
+			//
+			ExecKind::TokioTp =>
+			{
+				let mut builder = crate::TokioTpBuilder::new();
+
+				if let Some(threads) = self.threads
+				{
+					builder.tokio_builder().worker_threads( threads );
+				}
+
+				Ok( AnyExec::TokioTp( builder.build().map_err( ExecConfigError::Build )? ) )
+			}
+
+			#[ cfg( feature = "tokio_ct" ) ]
+			//
+			ExecKind::TokioCt => Ok( AnyExec::TokioCt( crate::TokioCtBuilder::new().build().map_err( ExecConfigError::Build )? ) ),
+
+			#[ cfg( feature = "async_std" ) ]
+			//
+			ExecKind::AsyncStd => Ok( AnyExec::AsyncStd( crate::AsyncStd::new() ) ),
+
+			#[ cfg( feature = "async_global" ) ]
+			//
+			ExecKind::AsyncGlobal => Ok( AnyExec::AsyncGlobal( crate::AsyncGlobal::new() ) ),
+
+			#[ cfg( feature = "glommio" ) ]
+			//
+			ExecKind::GlommioCt =>
+			{
+				let exec = crate::GlommioCt::new( glommio_crate::LocalExecutorBuilder::new() )
+
+					.map_err( |e| ExecConfigError::Build( io::Error::new( io::ErrorKind::Other, e.to_string() ) ) )?
+				;
+
+				Ok( AnyExec::GlommioCt( exec ) )
+			}
+
+			#[ cfg( feature = "threadpool" ) ]
+			//
+			ExecKind::ThreadPool =>
+			{
+				let mut builder = crate::ThreadPool::builder();
+
+				if let Some(threads) = self.threads
+				{
+					builder.pool_size( threads );
+				}
+
+				Ok( AnyExec::ThreadPool( builder.create().map_err( ExecConfigError::Build )? ) )
+			}
+
+			#[ cfg( feature = "localpool" ) ]
+			//
+			ExecKind::LocalPool => Ok( crate::LocalPool::new().into() ),
+
+			#[ allow( unreachable_patterns ) ]
+			//
+			kind => Err( ExecConfigError::Disabled( kind ) ),
+		}
+	}
+}
+
+
+impl From<ExecKind> for ExecConfig
+{
+	fn from( exec: ExecKind ) -> Self
+	{
+		Self{ exec, threads: None }
+	}
+}
+
+
+impl FromStr for ExecConfig
+{
+	type Err = ExecConfigError;
+
+	fn from_str( s: &str ) -> Result<Self, Self::Err>
+	{
+		let mut parts  = s.splitn( 2, ':' );
+		let mut config = ExecConfig::from( parts.next().unwrap_or_default().parse::<ExecKind>()? );
+
+		let options = match parts.next()
+		{
+			Some(o) => o,
+			None    => return Ok( config ),
+		};
+
+		for option in options.split( ',' ).map( str::trim ).filter( |o| !o.is_empty() )
+		{
+			let mut kv = option.splitn( 2, '=' ).map( str::trim );
+
+			match ( kv.next(), kv.next() )
+			{
+				( Some( "threads" ), Some(value) ) =>
+				{
+					let threads = value.parse()
+
+						.map_err( |e| ExecConfigError::Parse( format!( "invalid value for threads: {:?}: {}", value, e ) ) )?
+					;
+
+					config.threads = Some( threads );
+				}
+
+				_ => return Err( ExecConfigError::Parse( format!( "invalid option: {:?}", option ) ) ),
+			}
+		}
+
+		Ok( config )
+	}
+}
+
+
+
+/// Errors that can happen when creating an [`AnyExec`] from an [`ExecConfig`].
+//
+#[ derive( Debug ) ]
+#[ non_exhaustive ]
+//
+pub enum ExecConfigError
+{
+	/// The configuration is not valid.
+	//
+	Parse( String ),
+
+	/// The executor is not compiled in. Enable the feature with the same name on async_executors.
+	//
+	Disabled( ExecKind ),
+
+	/// Creating the executor failed.
+	//
+	Build( io::Error ),
+}
+
+
+impl fmt::Display for ExecConfigError
+{
+	fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result
+	{
+		match self
+		{
+			ExecConfigError::Parse   ( msg  ) => write!( f, "invalid executor configuration: {}", msg ),
+			ExecConfigError::Disabled( kind ) => write!( f, "the {} executor is not compiled in, enable the {0} feature on async_executors", kind ),
+			ExecConfigError::Build   ( e    ) => write!( f, "failed to create the executor: {}", e ),
+		}
+	}
+}
+
+
+impl Error for ExecConfigError
+{
+	fn source( &self ) -> Option<&(dyn Error + 'static)>
+	{
+		match self
+		{
+			ExecConfigError::Build( e ) => Some( e ),
+			_                           => None,
+		}
+	}
+}
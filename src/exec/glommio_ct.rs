@@ -1,10 +1,12 @@
 use
 {
-	crate         :: { LocalSpawnHandle, SpawnHandle, InnerJh, JoinHandle      } ,
-	std           :: { future::Future, rc::Rc                                  } ,
-	futures_task  :: { FutureObj, LocalSpawn,  Spawn, SpawnError               } ,
-	futures_util  :: { FutureExt, task::LocalSpawnExt, future::LocalFutureObj  } ,
-	glommio_crate :: { LocalExecutor, LocalExecutorBuilder, GlommioError, Task } ,
+	crate         :: { LocalSpawnHandle, SpawnHandle, InnerJh, JoinHandle, Timer, RemoteSpawner   } ,
+	crate         :: { SpawnHandleGeneric, LocalSpawnHandleGeneric, SpawnMany, LocalSpawnMany      } ,
+	std           :: { future::Future, rc::Rc, time::Duration, pin::Pin, fmt, thread              } ,
+	std           :: { task::{ Context, Poll }, panic::{ AssertUnwindSafe, resume_unwind }        } ,
+	futures_task  :: { FutureObj, LocalSpawn,  Spawn, SpawnError                                  } ,
+	futures_util  :: { FutureExt, task::LocalSpawnExt, future::{ LocalFutureObj, BoxFuture }, ready } ,
+	glommio_crate :: { LocalExecutor, LocalExecutorBuilder, GlommioError, Task, timer::Timer as GTimer } ,
 };
 
 
@@ -52,6 +54,99 @@ impl GlommioCt
 	{
 		self.exec.run( future )
 	}
+
+
+
+	/// Get a handle that lets other threads spawn on this executor, see [`RemoteSpawner`].
+	/// Like spawning, this must be called from within [block_on](GlommioCt::block_on).
+	//
+	pub fn remote_spawner( &self ) -> RemoteSpawner
+	{
+		let (remote, pump) = RemoteSpawner::channel( |fut| { Task::local( fut ).detach(); } );
+
+		Task::local( pump ).detach();
+
+		remote
+	}
+
+
+
+	/// Spawn a future and get a [`GlommioJoinHandle`], which wraps the native glommio `Task` instead of
+	/// going through a channel like the [`JoinHandle`] returned by [`LocalSpawnHandle`]. The handle is
+	/// `!Send`, so it can only be awaited on this executor.
+	///
+	/// Like spawning, this will panic if called from outside [block_on](GlommioCt::block_on).
+	//
+	pub fn spawn_handle_native<Fut>( &self, future: Fut ) -> Result< GlommioJoinHandle<Fut::Output>, SpawnError >
+
+		where Fut: Future + 'static, Fut::Output: 'static
+	{
+		let task = Task::local( AssertUnwindSafe( future ).catch_unwind() );
+
+		Ok( GlommioJoinHandle{ task: Some(task) } )
+	}
+}
+
+
+
+/// A handle to a task spawned with [`GlommioCt::spawn_handle_native`]. It wraps glommio's own `Task`,
+/// so it avoids the channel behind [`JoinHandle`], but it is bound to the executor thread.
+///
+/// It behaves like [`JoinHandle`]: dropping it cancels the task, [`detach`](GlommioJoinHandle::detach)
+/// lets the task run to completion and if the task panics, awaiting the handle resumes the panic.
+//
+#[ must_use = "GlommioJoinHandle will cancel your future when dropped." ]
+//
+#[ cfg_attr( nightly, doc(cfg( feature = "glommio" )) ) ]
+//
+pub struct GlommioJoinHandle<T>
+{
+	// Only None after detach.
+	//
+	task: Option< Task< thread::Result<T> > >,
+}
+
+
+
+impl<T> GlommioJoinHandle<T>
+{
+	/// Drops this handle without canceling the underlying future.
+	//
+	pub fn detach( mut self )
+	{
+		if let Some( task ) = self.task.take()
+		{
+			task.detach();
+		}
+	}
+}
+
+
+
+impl<T: 'static> Future for GlommioJoinHandle<T>
+{
+	type Output = T;
+
+	fn poll( mut self: Pin<&mut Self>, cx: &mut Context<'_> ) -> Poll<Self::Output>
+	{
+		let task = self.task.as_mut().expect( "GlommioJoinHandle polled after detach" );
+
+		match ready!( Pin::new( task ).poll( cx ) )
+		{
+			Ok ( out   ) => Poll::Ready( out ),
+			Err( panic ) => resume_unwind( panic ),
+		}
+	}
+}
+
+
+
+impl<T> fmt::Debug for GlommioJoinHandle<T>
+{
+	fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result
+	{
+		f.debug_struct( "GlommioJoinHandle" ).finish()
+	}
 }
 
 
@@ -67,12 +162,19 @@ impl LocalSpawn for GlommioCt
 
 
 
+impl LocalSpawnMany for GlommioCt {}
+
+
+
 impl<Out: 'static> LocalSpawnHandle<Out> for GlommioCt
 {
 	fn spawn_handle_local_obj( &self, future: LocalFutureObj<'static, Out> )
 
 		-> Result<JoinHandle<Out>, SpawnError>
 	{
+		// Glommio's Task is bound to this thread, so it can't go in a JoinHandle, which needs to be Send
+		// for the other executors. See the glommio benchmark for the overhead of the RemoteHandle.
+		//
 		let (remote, handle) = future.remote_handle();
 
 		Task::local( remote ).detach();
@@ -96,6 +198,10 @@ impl Spawn for GlommioCt
 
 
 
+impl SpawnMany for GlommioCt {}
+
+
+
 impl<Out: Send + 'static> SpawnHandle<Out> for GlommioCt
 {
 	fn spawn_handle_obj( &self, future: FutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>
@@ -113,6 +219,54 @@ impl<Out: Send + 'static> SpawnHandle<Out> for GlommioCt
 
 
 
+impl<Out: 'static + Send> SpawnHandleGeneric<Out> for GlommioCt
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		self.spawn_handle_local_generic( future )
+	}
+}
+
+
+
+impl<Out: 'static> LocalSpawnHandleGeneric<Out> for GlommioCt
+{
+	fn spawn_handle_local_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + 'static
+	{
+		let (remote, handle) = future.remote_handle();
+
+		Task::local( remote ).detach();
+
+		Ok( JoinHandle
+		{
+			inner: InnerJh::RemoteHandle( Some(handle) )
+		})
+	}
+}
+
+
+
+/// Glommio's timer is `!Send`, so it is driven by a local task and we hand out a `RemoteHandle` to it.
+/// Like spawning, this will panic if called from outside [block_on](GlommioCt::block_on).
+//
+impl Timer for GlommioCt
+{
+	fn sleep( &self, dur: Duration ) -> BoxFuture<'static, ()>
+	{
+		let (remote, handle) = GTimer::new( dur ).remote_handle();
+
+		Task::local( remote ).detach();
+
+		Box::pin( handle )
+	}
+}
+
+
+
 #[ cfg(test) ]
 //
 mod tests
@@ -122,4 +276,8 @@ mod tests
 	// It's important that this is not Send, as we allow spawning !Send futures on it.
 	//
 	static_assertions::assert_not_impl_any!( GlommioCt: Send, Sync );
+
+	// The native handle holds a glommio Task, which is bound to the executor thread.
+	//
+	static_assertions::assert_not_impl_any!( GlommioJoinHandle<()>: Send, Sync );
 }
@@ -0,0 +1,185 @@
+//! Provides Inherit, an executor wrapper that passes task-local values on to spawned tasks.
+//
+use
+{
+	crate        :: { SpawnHandle, LocalSpawnHandle, JoinHandle, Timer, iface::task_local::{ Locals, Inherited } } ,
+	crate        :: { SpawnMany, LocalSpawnMany, SpawnHandleGeneric, LocalSpawnHandleGeneric                } ,
+	futures_task :: { FutureObj, LocalFutureObj, Spawn, LocalSpawn, SpawnError                                  } ,
+	futures_util :: { future::{ BoxFuture, FutureExt }                                                          } ,
+	std          :: { future::Future, time::{ Duration, Instant }                                               } ,
+};
+
+
+/// An executor wrapper that captures the [task-local](crate::task_local!) values of the task that spawns, and sets
+/// them around every poll of the spawned task. Without it, spawned tasks start without any task-local values.
+///
+/// The values are captured when calling one of the spawn methods, so if you spawn from outside of a task, there is
+/// nothing to inherit. The child task can override values with [`LocalKey::scope`](crate::LocalKey::scope)
+/// without affecting the parent.
+///
+/// See [`task_local!`](crate::task_local!) for an example.
+//
+#[ derive( Debug, Clone ) ]
+//
+pub struct Inherit<E>
+{
+	exec: E,
+}
+
+
+impl<E> Inherit<E>
+{
+	/// Wrap `exec`, so that tasks spawned through the wrapper inherit the task-local values of the spawning task.
+	//
+	pub fn new( exec: E ) -> Self
+	{
+		Self { exec }
+	}
+
+
+	/// Access the wrapped executor.
+	//
+	pub fn inner( &self ) -> &E
+	{
+		&self.exec
+	}
+}
+
+
+
+impl<E: Spawn> Spawn for Inherit<E>
+{
+	fn spawn_obj( &self, future: FutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		let task = Inherited { locals: Locals::current(), future };
+
+		self.exec.spawn_obj( FutureObj::new( task.boxed() ) )
+	}
+}
+
+
+
+impl<E: SpawnMany> SpawnMany for Inherit<E>
+{
+	fn spawn_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		let locals = Locals::current();
+
+		self.exec.spawn_obj_many( &mut futures.map( |future|
+		{
+			FutureObj::new( Inherited { locals: locals.clone(), future }.boxed() )
+		}))
+	}
+}
+
+
+
+impl<E: LocalSpawn> LocalSpawn for Inherit<E>
+{
+	fn spawn_local_obj( &self, future: LocalFutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		let task = Inherited { locals: Locals::current(), future };
+
+		self.exec.spawn_local_obj( LocalFutureObj::new( task.boxed_local() ) )
+	}
+}
+
+
+
+impl<E: LocalSpawnMany> LocalSpawnMany for Inherit<E>
+{
+	fn spawn_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		let locals = Locals::current();
+
+		self.exec.spawn_local_obj_many( &mut futures.map( |future|
+		{
+			LocalFutureObj::new( Inherited { locals: locals.clone(), future }.boxed_local() )
+		}))
+	}
+}
+
+
+
+impl<E, Out> SpawnHandle<Out> for Inherit<E> where E: SpawnHandle<Out>, Out: 'static + Send
+{
+	fn spawn_handle_obj( &self, future: FutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>
+	{
+		let task = Inherited { locals: Locals::current(), future };
+
+		self.exec.spawn_handle_obj( FutureObj::new( task.boxed() ) )
+	}
+
+
+	fn spawn_handle_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		let locals = Locals::current();
+
+		self.exec.spawn_handle_obj_many( &mut futures.map( |future|
+		{
+			FutureObj::new( Inherited { locals: locals.clone(), future }.boxed() )
+		}))
+	}
+}
+
+
+
+impl<E, Out> LocalSpawnHandle<Out> for Inherit<E> where E: LocalSpawnHandle<Out>, Out: 'static
+{
+	fn spawn_handle_local_obj( &self, future: LocalFutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>
+	{
+		let task = Inherited { locals: Locals::current(), future };
+
+		self.exec.spawn_handle_local_obj( LocalFutureObj::new( task.boxed_local() ) )
+	}
+
+
+	fn spawn_handle_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		let locals = Locals::current();
+
+		self.exec.spawn_handle_local_obj_many( &mut futures.map( |future|
+		{
+			LocalFutureObj::new( Inherited { locals: locals.clone(), future }.boxed_local() )
+		}))
+	}
+}
+
+
+
+// Inherited needs an Unpin future, so these pin it on the heap. That still saves boxing the future with it.
+//
+impl<E, Out> SpawnHandleGeneric<Out> for Inherit<E> where E: SpawnHandleGeneric<Out>, Out: 'static + Send
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		self.exec.spawn_handle_generic( Inherited { locals: Locals::current(), future: Box::pin( future ) } )
+	}
+}
+
+
+
+impl<E, Out> LocalSpawnHandleGeneric<Out> for Inherit<E> where E: LocalSpawnHandleGeneric<Out>, Out: 'static
+{
+	fn spawn_handle_local_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + 'static
+	{
+		self.exec.spawn_handle_local_generic( Inherited { locals: Locals::current(), future: Box::pin( future ) } )
+	}
+}
+
+
+
+impl<E: Timer> Timer for Inherit<E>
+{
+	fn sleep      ( &self, dur     : Duration ) -> BoxFuture<'static, ()> { self.exec.sleep( dur )            }
+	fn now        ( &self                     ) -> Instant                { self.exec.now()                   }
+	fn sleep_until( &self, deadline: Instant  ) -> BoxFuture<'static, ()> { self.exec.sleep_until( deadline ) }
+}
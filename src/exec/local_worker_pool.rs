@@ -0,0 +1,326 @@
+//! Provides LocalWorkerPool, a pool of threads that each run a single threaded executor for !Send tasks.
+//
+use
+{
+	crate           :: { SpawnHandle, SpawnHandleGeneric, JoinHandle, RemoteSpawner, SpawnMany     } ,
+	futures_task    :: { FutureObj, Spawn, SpawnError                                              } ,
+	futures_channel :: { oneshot                                                                   } ,
+	std             :: { future::Future, io, thread, sync::{ Arc, mpsc, atomic::{ AtomicUsize, Ordering } } } ,
+};
+
+
+/// A pool of threads that each own a single threaded executor, to run `!Send` tasks from any thread.
+///
+/// [`spawn_pinned`](LocalWorkerPool::spawn_pinned) takes a `Send` closure that creates the future on the worker,
+/// so the future itself doesn't have to be `Send`. The task stays on that worker for it's whole life. It goes to the
+/// worker with the least tasks, or to a worker of your choice with [`spawn_pinned_on`](LocalWorkerPool::spawn_pinned_on),
+/// eg. to keep all work for a key on the same thread.
+///
+/// The workers run [`TokioCt`](crate::TokioCt), [`GlommioCt`](crate::GlommioCt) or a `LocalPool` depending on the
+/// constructor. It also implements [`Spawn`] and [`SpawnHandle`], which send the futures to the least loaded worker.
+///
+/// A panic in a task is caught on the worker. It is resumed in the task that awaits the [`JoinHandle`], and the
+/// worker keeps running the other tasks.
+///
+/// Clones refer to the same pool. The workers stop when the last clone is dropped, dropping the tasks that are still
+/// running.
+///
+/// ```
+/// use
+/// {
+///    async_executors :: { LocalWorkerPool        } ,
+///    futures         :: { executor::block_on     } ,
+///    std             :: { rc::Rc                 } ,
+/// };
+///
+/// let pool   = LocalWorkerPool::local_pool( 2 ).expect( "start workers" );
+/// let handle = pool.spawn_pinned( || async { *Rc::new( 5u8 ) } ).expect( "spawn" );
+///
+/// assert_eq!( block_on( handle ), 5 );
+/// ```
+//
+#[ derive( Clone ) ]
+//
+pub struct LocalWorkerPool
+{
+	workers: Arc< Vec<Worker> >,
+}
+
+
+struct Worker
+{
+	remote: RemoteSpawner         ,
+	load  : Arc<AtomicUsize>      ,
+
+	// The worker stops when this is dropped.
+	//
+	_stop : oneshot::Sender<()>   ,
+}
+
+
+// What a worker thread sends back once it's executor is running.
+//
+type Ready = mpsc::Sender< io::Result<RemoteSpawner> >;
+
+
+impl LocalWorkerPool
+{
+	/// Start `threads` workers that each run a [`TokioCt`](crate::TokioCt).
+	//
+	#[ cfg( feature = "tokio_ct" ) ]
+	#[ cfg_attr( nightly, doc(cfg( feature = "tokio_ct" )) ) ]
+	//
+	pub fn tokio_ct( threads: usize ) -> io::Result<Self>
+	{
+		Self::start( threads, |ready, stop|
+		{
+			let exec = match crate::TokioCtBuilder::new().build()
+			{
+				Ok ( exec ) => exec,
+				Err( e    ) => { let _ = ready.send( Err(e) ); return; }
+			};
+
+			let _ = ready.send( Ok( exec.remote_spawner() ) );
+
+			let _ = exec.block_on( stop );
+		})
+	}
+
+
+	/// Start `threads` workers that each run a [`GlommioCt`](crate::GlommioCt) with the default
+	/// `LocalExecutorBuilder`.
+	//
+	#[ cfg( feature = "glommio" ) ]
+	#[ cfg_attr( nightly, doc(cfg( feature = "glommio" )) ) ]
+	//
+	pub fn glommio( threads: usize ) -> io::Result<Self>
+	{
+		Self::start( threads, |ready, stop|
+		{
+			let exec = match crate::GlommioCt::new( glommio_crate::LocalExecutorBuilder::new() )
+			{
+				Ok ( exec ) => exec,
+				Err( e    ) => { let _ = ready.send( Err( io::Error::other( e.to_string() ) ) ); return; }
+			};
+
+			exec.block_on( async
+			{
+				let _ = ready.send( Ok( exec.remote_spawner() ) );
+				let _ = stop.await;
+			});
+		})
+	}
+
+
+	/// Start `threads` workers that each run a `LocalPool`.
+	//
+	#[ cfg( feature = "localpool" ) ]
+	#[ cfg_attr( nightly, doc(cfg( feature = "localpool" )) ) ]
+	//
+	pub fn local_pool( threads: usize ) -> io::Result<Self>
+	{
+		Self::start( threads, |ready, stop|
+		{
+			let mut pool = futures_executor::LocalPool::new();
+
+			let remote = RemoteSpawner::new( pool.spawner() )
+
+				.map_err( io::Error::other )
+			;
+
+			let _ = ready.send( remote );
+
+			let _ = pool.run_until( stop );
+		})
+	}
+
+
+	// Start the threads and wait until their executors run.
+	//
+	fn start( threads: usize, run: fn( Ready, oneshot::Receiver<()> ) ) -> io::Result<Self>
+	{
+		assert!( threads > 0, "LocalWorkerPool: the number of threads must be at least 1" );
+
+		let mut workers = Vec::with_capacity( threads );
+
+		for i in 0..threads
+		{
+			let (ready_tx, ready_rx) = mpsc::channel();
+			let (stop_tx , stop_rx ) = oneshot::channel();
+
+			thread::Builder::new()
+
+				.name( format!( "async_executors-worker-{}", i ) )
+				.spawn( move || run( ready_tx, stop_rx ) )?
+			;
+
+			let remote = ready_rx.recv()
+
+				.unwrap_or_else( |_| Err( io::Error::other( "LocalWorkerPool: worker thread panicked" ) ) )?
+			;
+
+			workers.push( Worker { remote, load: Arc::new( AtomicUsize::new(0) ), _stop: stop_tx } );
+		}
+
+		Ok( Self { workers: Arc::new( workers ) } )
+	}
+
+
+	/// Spawn the future created by `f` on the worker with the least tasks.
+	//
+	pub fn spawn_pinned<F, Fut, Out>( &self, f: F ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where F  : FnOnce() -> Fut + Send + 'static ,
+		      Fut: Future<Output = Out> + 'static   ,
+		      Out: Send + 'static                   ,
+	{
+		self.spawn_pinned_on( self.least_loaded(), f )
+	}
+
+
+	/// Spawn the future created by `f` on the worker with the given index.
+	///
+	/// # Panics
+	///
+	/// When `index` is not smaller than [`len`](LocalWorkerPool::len).
+	//
+	pub fn spawn_pinned_on<F, Fut, Out>( &self, index: usize, f: F ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where F  : FnOnce() -> Fut + Send + 'static ,
+		      Fut: Future<Output = Out> + 'static   ,
+		      Out: Send + 'static                   ,
+	{
+		let worker = &self.workers[ index ];
+		let load   = Load::new( &worker.load );
+
+		worker.remote.spawn_handle_with( move || async move
+		{
+			let _load = load;
+			f().await
+		})
+	}
+
+
+	/// The number of workers.
+	//
+	pub fn len( &self ) -> usize
+	{
+		self.workers.len()
+	}
+
+
+	/// Always false, as a pool has at least one worker.
+	//
+	pub fn is_empty( &self ) -> bool
+	{
+		false
+	}
+
+
+	/// The number of tasks spawned on the worker with the given index that haven't finished yet.
+	///
+	/// # Panics
+	///
+	/// When `index` is not smaller than [`len`](LocalWorkerPool::len).
+	//
+	pub fn load( &self, index: usize ) -> usize
+	{
+		self.workers[ index ].load.load( Ordering::Relaxed )
+	}
+
+
+	// Workers whose executor is gone are skipped, unless they all are, in which case spawning fails.
+	//
+	fn least_loaded( &self ) -> usize
+	{
+		self.workers.iter().enumerate()
+
+			.filter    ( |(_, w)| !w.remote.is_closed()                 )
+			.min_by_key( |(_, w)|  w.load.load( Ordering::Relaxed )    )
+			.map( |(i, _)| i )
+			.unwrap_or( 0 )
+	}
+}
+
+
+
+impl Spawn for LocalWorkerPool
+{
+	fn spawn_obj( &self, future: FutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		let worker = &self.workers[ self.least_loaded() ];
+		let load   = Load::new( &worker.load );
+
+		worker.remote.spawn_with( move || async move
+		{
+			let _load = load;
+			future.await
+		})
+	}
+}
+
+
+
+impl SpawnMany for LocalWorkerPool {}
+
+
+
+impl<Out: 'static + Send> SpawnHandle<Out> for LocalWorkerPool
+{
+	fn spawn_handle_obj( &self, future: FutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>
+	{
+		self.spawn_pinned( move || future )
+	}
+}
+
+
+
+impl<Out: 'static + Send> SpawnHandleGeneric<Out> for LocalWorkerPool
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		self.spawn_pinned( move || future )
+	}
+}
+
+
+
+impl std::fmt::Debug for LocalWorkerPool
+{
+	fn fmt( &self, f: &mut std::fmt::Formatter<'_> ) -> std::fmt::Result
+	{
+		let load: Vec<_> = self.workers.iter().map( |w| w.load.load( Ordering::Relaxed ) ).collect();
+
+		f.debug_struct( "LocalWorkerPool" )
+
+			.field( "load", &load )
+			.finish()
+	}
+}
+
+
+
+// Counts a task on a worker for as long as it lives, including before it was received by the worker.
+//
+struct Load( Arc<AtomicUsize> );
+
+impl Load
+{
+	fn new( load: &Arc<AtomicUsize> ) -> Self
+	{
+		load.fetch_add( 1, Ordering::Relaxed );
+
+		Self( load.clone() )
+	}
+}
+
+impl Drop for Load
+{
+	fn drop( &mut self )
+	{
+		self.0.fetch_sub( 1, Ordering::Relaxed );
+	}
+}
+
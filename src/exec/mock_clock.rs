@@ -0,0 +1,624 @@
+//! Provides MockClock, a Timer with virtual time for testing.
+//
+use
+{
+	crate        :: { SpawnHandle, LocalSpawnHandle, JoinHandle, Timer                           } ,
+	crate        :: { SpawnMany, LocalSpawnMany, SpawnHandleGeneric, LocalSpawnHandleGeneric     } ,
+	futures_task :: { FutureObj, LocalFutureObj, Spawn, LocalSpawn, SpawnError, ArcWake, waker   } ,
+	futures_util :: { future::{ BoxFuture, FutureExt }                                           } ,
+	std          :: { collections::{ BTreeMap, HashMap }, future::Future, pin::Pin               } ,
+	std          :: { sync::{ Arc, Mutex, MutexGuard }, task::{ Context, Poll, Waker }           } ,
+	std          :: { time::{ Duration, Instant }                                                } ,
+};
+
+
+/// A [`Timer`] with virtual time. Time only moves forward when you call [`advance`](MockClock::advance),
+/// or, when auto advance is turned on, as soon as all tasks that are being tracked by the clock are idle.
+///
+/// This lets you unit test retry and timeout logic in code that takes a generic executor without actually
+/// sleeping.
+///
+/// To let the clock know when tasks are idle, spawn them through the [`Tracked`] wrapper you get from
+/// [`track`](MockClock::track). It implements the spawn traits by forwarding to the wrapped executor, as well as
+/// [`Timer`] by forwarding to the clock, so it can be passed to code that requires `impl SpawnHandle<T> + Timer`.
+///
+/// With auto advance on, when no tracked task is running or has been woken up, the clock jumps straight to the
+/// earliest pending deadline. This is also the case when a sleep is polled while no tracked tasks are busy, eg.
+/// from the future passed to `block_on`. Tasks that are not tracked, and external events like IO, are not taken
+/// into account, so auto advance will not wait for them.
+///
+/// ```
+/// use
+/// {
+///    async_executors :: { MockClock, Timer                 } ,
+///    futures         :: { executor::block_on               } ,
+///    std             :: { time::Duration                   } ,
+/// };
+///
+/// let clock = MockClock::new();
+/// clock.set_auto_advance( true );
+///
+/// // This returns immediately, but the clock will have moved forward by an hour.
+/// //
+/// block_on( clock.sleep( Duration::from_secs( 3600 ) ) );
+///
+/// assert_eq!( clock.elapsed(), Duration::from_secs( 3600 ) );
+/// ```
+//
+#[ derive( Clone ) ]
+//
+pub struct MockClock
+{
+	inner: Arc< Mutex<Inner> >,
+}
+
+
+
+struct Inner
+{
+	start       : Instant                            ,
+	elapsed     : Duration                           ,
+	auto_advance: bool                               ,
+	next_id     : u64                                ,
+	sleepers    : BTreeMap< (Duration, u64), Waker > ,
+
+	// Tracked tasks, and whether they are currently scheduled (woken up) or running.
+	//
+	tasks       : HashMap< u64, TaskState >          ,
+
+	// The number of tracked tasks that are either scheduled or running.
+	//
+	busy        : usize                              ,
+}
+
+
+struct TaskState
+{
+	scheduled: bool,
+	running  : bool,
+}
+
+
+
+impl Inner
+{
+	fn next_id( &mut self ) -> u64
+	{
+		self.next_id += 1;
+		self.next_id
+	}
+
+
+	// Move the clock to `elapsed` and return the wakers of all sleeps that are due.
+	//
+	fn advance_to( &mut self, elapsed: Duration ) -> Vec<Waker>
+	{
+		if elapsed > self.elapsed
+		{
+			self.elapsed = elapsed;
+		}
+
+		let pending = self.sleepers.split_off( &(self.elapsed, u64::MAX) );
+		let due     = std::mem::replace( &mut self.sleepers, pending );
+
+		due.into_values().collect()
+	}
+
+
+	// If auto advance is on and no tracked tasks are busy, jump to the next deadline.
+	//
+	fn auto_advance( &mut self ) -> Vec<Waker>
+	{
+		if !self.auto_advance || self.busy != 0
+		{
+			return Vec::new();
+		}
+
+		match self.sleepers.keys().next()
+		{
+			Some( &(deadline, _) ) => self.advance_to( deadline ),
+			None                   => Vec::new(),
+		}
+	}
+}
+
+
+
+impl MockClock
+{
+	/// Create a new clock. It starts at [`Instant::now`] and auto advance is off.
+	//
+	pub fn new() -> Self
+	{
+		let inner = Inner
+		{
+			start       : Instant::now()  ,
+			elapsed     : Duration::ZERO  ,
+			auto_advance: false           ,
+			next_id     : 0               ,
+			sleepers    : BTreeMap::new() ,
+			tasks       : HashMap::new()  ,
+			busy        : 0               ,
+		};
+
+		Self { inner: Arc::new( Mutex::new( inner ) ) }
+	}
+
+
+	/// Move the clock forward by `dur`, waking up all sleeps that are due.
+	//
+	pub fn advance( &self, dur: Duration )
+	{
+		let wakers =
+		{
+			let mut inner = self.lock();
+			let elapsed   = inner.elapsed + dur;
+
+			inner.advance_to( elapsed )
+		};
+
+		wakers.into_iter().for_each( Waker::wake );
+	}
+
+
+	/// Turn auto advance on or off. See the type level documentation.
+	//
+	pub fn set_auto_advance( &self, auto_advance: bool )
+	{
+		let wakers =
+		{
+			let mut inner = self.lock();
+			inner.auto_advance = auto_advance;
+
+			inner.auto_advance()
+		};
+
+		wakers.into_iter().for_each( Waker::wake );
+	}
+
+
+	/// How much virtual time has passed since the clock was created.
+	//
+	pub fn elapsed( &self ) -> Duration
+	{
+		self.lock().elapsed
+	}
+
+
+	/// Wrap an executor so the clock can see when the tasks spawned through it are idle.
+	//
+	pub fn track<E>( &self, exec: E ) -> Tracked<E>
+	{
+		Tracked { exec, clock: self.clone() }
+	}
+
+
+	fn lock( &self ) -> MutexGuard<'_, Inner>
+	{
+		// A panic while holding the lock cannot leave the clock in an inconsistent state.
+		//
+		self.inner.lock().unwrap_or_else( |e| e.into_inner() )
+	}
+
+
+	// Register a tracked task. It starts out scheduled.
+	//
+	fn add_task( &self ) -> u64
+	{
+		let mut inner = self.lock();
+		let id        = inner.next_id();
+
+		inner.tasks.insert( id, TaskState { scheduled: true, running: false } );
+		inner.busy += 1;
+
+		id
+	}
+
+
+	fn remove_task( &self, id: u64 )
+	{
+		let wakers =
+		{
+			let mut inner = self.lock();
+
+			match inner.tasks.remove( &id )
+			{
+				Some( state ) if state.scheduled || state.running => inner.busy -= 1,
+				_                                                 => {}
+			}
+
+			inner.auto_advance()
+		};
+
+		wakers.into_iter().for_each( Waker::wake );
+	}
+
+
+	fn wake_task( &self, id: u64 )
+	{
+		let mut inner = self.lock();
+		let mut newly = false;
+
+		if let Some( state ) = inner.tasks.get_mut( &id )
+		{
+			newly = !state.scheduled && !state.running;
+			state.scheduled = true;
+		}
+
+		if newly { inner.busy += 1; }
+	}
+
+
+	fn start_poll( &self, id: u64 )
+	{
+		if let Some( state ) = self.lock().tasks.get_mut( &id )
+		{
+			state.scheduled = false;
+			state.running   = true;
+		}
+	}
+
+
+	fn end_poll( &self, id: u64 )
+	{
+		let wakers =
+		{
+			let mut inner = self.lock();
+			let mut idle  = false;
+
+			if let Some( state ) = inner.tasks.get_mut( &id )
+			{
+				state.running = false;
+				idle          = !state.scheduled;
+			}
+
+			if idle { inner.busy -= 1; }
+
+			inner.auto_advance()
+		};
+
+		wakers.into_iter().for_each( Waker::wake );
+	}
+}
+
+
+
+impl Default for MockClock
+{
+	fn default() -> Self
+	{
+		Self::new()
+	}
+}
+
+
+
+impl Timer for MockClock
+{
+	fn sleep( &self, dur: Duration ) -> BoxFuture<'static, ()>
+	{
+		let deadline = self.elapsed() + dur;
+
+		Box::pin( Sleep { clock: self.clone(), deadline, id: None } )
+	}
+
+
+	fn now( &self ) -> Instant
+	{
+		let inner = self.lock();
+
+		inner.start + inner.elapsed
+	}
+}
+
+
+
+impl std::fmt::Debug for MockClock
+{
+	fn fmt( &self, f: &mut std::fmt::Formatter<'_> ) -> std::fmt::Result
+	{
+		let inner = self.lock();
+
+		f.debug_struct( "MockClock" )
+
+			.field( "elapsed"     , &inner.elapsed        )
+			.field( "auto_advance", &inner.auto_advance   )
+			.field( "sleepers"    , &inner.sleepers.len() )
+			.field( "busy_tasks"  , &inner.busy           )
+			.finish()
+	}
+}
+
+
+
+// The future returned by MockClock::sleep.
+//
+struct Sleep
+{
+	clock   : MockClock     ,
+	deadline: Duration      ,
+	id      : Option<u64>   ,
+}
+
+
+impl Future for Sleep
+{
+	type Output = ();
+
+	fn poll( mut self: Pin<&mut Self>, cx: &mut Context<'_> ) -> Poll<()>
+	{
+		let mut inner = self.clock.lock();
+
+		if inner.elapsed >= self.deadline
+		{
+			drop( inner );
+			self.id = None;
+			return Poll::Ready(());
+		}
+
+		let id = match self.id
+		{
+			Some( id ) => id,
+			None       => inner.next_id(),
+		};
+
+		inner.sleepers.insert( (self.deadline, id), cx.waker().clone() );
+
+		let wakers = inner.auto_advance();
+		let ready  = inner.elapsed >= self.deadline;
+
+		drop( inner );
+
+		self.id = Some( id );
+		wakers.into_iter().for_each( Waker::wake );
+
+		if ready
+		{
+			self.id = None;
+			return Poll::Ready(());
+		}
+
+		Poll::Pending
+	}
+}
+
+
+impl Drop for Sleep
+{
+	fn drop( &mut self )
+	{
+		if let Some( id ) = self.id
+		{
+			self.clock.lock().sleepers.remove( &(self.deadline, id) );
+		}
+	}
+}
+
+
+
+/// An executor wrapper which lets a [`MockClock`] see whether the tasks spawned through it are idle.
+/// Obtain it with [`MockClock::track`].
+///
+/// It implements [`Spawn`], [`LocalSpawn`], [`SpawnHandle`] and [`LocalSpawnHandle`] when the wrapped executor does,
+/// and [`Timer`] by forwarding to the clock.
+//
+#[ derive( Debug, Clone ) ]
+//
+pub struct Tracked<E>
+{
+	exec : E         ,
+	clock: MockClock ,
+}
+
+
+
+impl<E> Tracked<E>
+{
+	/// The clock this wrapper reports to.
+	//
+	pub fn clock( &self ) -> &MockClock
+	{
+		&self.clock
+	}
+
+
+	/// The wrapped executor.
+	//
+	pub fn inner( &self ) -> &E
+	{
+		&self.exec
+	}
+
+
+	fn track<F: Future>( &self, future: F ) -> TrackedTask<F>
+	{
+		let id = self.clock.add_task();
+
+		TrackedTask { future, clock: self.clock.clone(), id }
+	}
+}
+
+
+
+impl<E: Spawn> Spawn for Tracked<E>
+{
+	fn spawn_obj( &self, future: FutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		self.exec.spawn_obj( FutureObj::new( self.track( future ).boxed() ) )
+	}
+}
+
+
+
+impl<E: SpawnMany> SpawnMany for Tracked<E>
+{
+	fn spawn_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		self.exec.spawn_obj_many( &mut futures.map( |future| FutureObj::new( self.track( future ).boxed() ) ) )
+	}
+}
+
+
+
+impl<E: LocalSpawn> LocalSpawn for Tracked<E>
+{
+	fn spawn_local_obj( &self, future: LocalFutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		self.exec.spawn_local_obj( LocalFutureObj::new( self.track( future ).boxed_local() ) )
+	}
+}
+
+
+
+impl<E: LocalSpawnMany> LocalSpawnMany for Tracked<E>
+{
+	fn spawn_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		self.exec.spawn_local_obj_many( &mut futures.map( |future| LocalFutureObj::new( self.track( future ).boxed_local() ) ) )
+	}
+}
+
+
+
+impl<E, Out> SpawnHandle<Out> for Tracked<E> where E: SpawnHandle<Out>, Out: 'static + Send
+{
+	fn spawn_handle_obj( &self, future: FutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>
+	{
+		self.exec.spawn_handle_obj( FutureObj::new( self.track( future ).boxed() ) )
+	}
+
+
+	fn spawn_handle_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		self.exec.spawn_handle_obj_many( &mut futures.map( |future| FutureObj::new( self.track( future ).boxed() ) ) )
+	}
+}
+
+
+
+impl<E, Out> LocalSpawnHandle<Out> for Tracked<E> where E: LocalSpawnHandle<Out>, Out: 'static
+{
+	fn spawn_handle_local_obj( &self, future: LocalFutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>
+	{
+		self.exec.spawn_handle_local_obj( LocalFutureObj::new( self.track( future ).boxed_local() ) )
+	}
+
+
+	fn spawn_handle_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		self.exec.spawn_handle_local_obj_many( &mut futures.map( |future| LocalFutureObj::new( self.track( future ).boxed_local() ) ) )
+	}
+}
+
+
+
+// TrackedTask needs an Unpin future, so these pin it on the heap.
+//
+impl<E, Out> SpawnHandleGeneric<Out> for Tracked<E> where E: SpawnHandleGeneric<Out>, Out: 'static + Send
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		self.exec.spawn_handle_generic( self.track( Box::pin( future ) ) )
+	}
+}
+
+
+
+impl<E, Out> LocalSpawnHandleGeneric<Out> for Tracked<E> where E: LocalSpawnHandleGeneric<Out>, Out: 'static
+{
+	fn spawn_handle_local_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + 'static
+	{
+		self.exec.spawn_handle_local_generic( self.track( Box::pin( future ) ) )
+	}
+}
+
+
+
+impl<E> Timer for Tracked<E>
+{
+	fn sleep( &self, dur: Duration ) -> BoxFuture<'static, ()>
+	{
+		self.clock.sleep( dur )
+	}
+
+
+	fn now( &self ) -> Instant
+	{
+		self.clock.now()
+	}
+}
+
+
+
+// Wraps a task so we know when it is running and when it is woken up. The waker passed to the inner future
+// notifies the clock before waking up the executor.
+//
+struct TrackedTask<F>
+{
+	future: F         ,
+	clock : MockClock ,
+	id    : u64       ,
+}
+
+
+impl<F: Future + Unpin> Future for TrackedTask<F>
+{
+	type Output = F::Output;
+
+	fn poll( mut self: Pin<&mut Self>, cx: &mut Context<'_> ) -> Poll<F::Output>
+	{
+		let this = &mut *self;
+
+		this.clock.start_poll( this.id );
+
+		let waker   = waker( Arc::new( TaskWaker { clock: this.clock.clone(), id: this.id, waker: cx.waker().clone() } ) );
+		let mut tcx = Context::from_waker( &waker );
+		let result  = Pin::new( &mut this.future ).poll( &mut tcx );
+
+		match result
+		{
+			Poll::Pending  => this.clock.end_poll   ( this.id ),
+			Poll::Ready(_) => this.clock.remove_task( this.id ),
+		}
+
+		result
+	}
+}
+
+
+impl<F> Drop for TrackedTask<F>
+{
+	fn drop( &mut self )
+	{
+		// No-op if the task already completed.
+		//
+		self.clock.remove_task( self.id );
+	}
+}
+
+
+
+struct TaskWaker
+{
+	clock: MockClock ,
+	id   : u64       ,
+	waker: Waker     ,
+}
+
+
+impl ArcWake for TaskWaker
+{
+	fn wake_by_ref( arc_self: &Arc<Self> )
+	{
+		arc_self.clock.wake_task( arc_self.id );
+		arc_self.waker.wake_by_ref();
+	}
+}
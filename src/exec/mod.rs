@@ -7,9 +7,13 @@
 #[ cfg( feature = "tokio_tp"     ) ] mod tokio_tp_builder;
 #[ cfg( feature = "tokio_tp"     ) ] pub use tokio_tp::*;
 #[ cfg( feature = "tokio_tp"     ) ] pub use tokio_tp_builder::*;
+#[ cfg( feature = "tokio_tp"     ) ] mod dedicated;
+#[ cfg( feature = "tokio_tp"     ) ] pub use dedicated::*;
 
 #[ cfg( feature = "async_global" ) ] mod async_global;
 #[ cfg( feature = "async_global" ) ] pub use async_global::*;
+#[ cfg(all( feature = "async_global", not(target_arch = "wasm32") )) ] mod async_global_builder;
+#[ cfg(all( feature = "async_global", not(target_arch = "wasm32") )) ] pub use async_global_builder::*;
 
 #[ cfg( feature = "async_std"    ) ] mod async_std;
 #[ cfg( feature = "async_std"    ) ] pub use async_std::*;
@@ -17,7 +21,52 @@
 #[ cfg( feature = "glommio"      ) ] mod glommio_ct;
 #[ cfg( feature = "glommio"      ) ] pub use glommio_ct::*;
 
+#[ cfg( feature = "native"       ) ] mod native;
+#[ cfg( feature = "native"       ) ] mod native_builder;
+#[ cfg( feature = "native"       ) ] pub use native::*;
+#[ cfg( feature = "native"       ) ] pub use native_builder::*;
+
+#[ cfg( feature = "rayon"        ) ] mod rayon;
+#[ cfg( feature = "rayon"        ) ] pub use rayon::*;
+
+#[ cfg(any( feature = "native", feature = "rayon" )) ] mod task_state;
+
 #[ cfg( feature = "bindgen"      ) ] mod bindgen;
 #[ cfg( feature = "bindgen"      ) ] pub use bindgen::*;
 
 #[ cfg( feature = "tracing"      ) ] mod tracing;
+
+#[ cfg(any( feature = "tokio_ct", feature = "tokio_tp", feature = "async_std", feature = "async_global", feature = "glommio", feature = "threadpool", feature = "localpool" )) ] mod any_exec;
+#[ cfg(any( feature = "tokio_ct", feature = "tokio_tp", feature = "async_std", feature = "async_global", feature = "glommio", feature = "threadpool", feature = "localpool" )) ] pub use any_exec::*;
+#[ cfg(any( feature = "tokio_ct", feature = "tokio_tp", feature = "async_std", feature = "async_global", feature = "glommio", feature = "threadpool", feature = "localpool" )) ] mod exec_config;
+#[ cfg(any( feature = "tokio_ct", feature = "tokio_tp", feature = "async_std", feature = "async_global", feature = "glommio", feature = "threadpool", feature = "localpool" )) ] pub use exec_config::*;
+
+mod mock_clock;
+pub use mock_clock::*;
+
+mod bounded;
+pub use bounded::*;
+
+mod prioritized;
+pub use prioritized::*;
+
+mod deadlined;
+pub use deadlined::*;
+
+mod inherit;
+pub use inherit::*;
+
+mod supervisor;
+pub use supervisor::*;
+
+mod with_handle;
+pub use with_handle::*;
+
+mod remote_spawner;
+pub use remote_spawner::*;
+
+mod sharded;
+pub use sharded::*;
+
+#[ cfg(any( feature = "tokio_ct", feature = "glommio", feature = "localpool" )) ] mod local_worker_pool;
+#[ cfg(any( feature = "tokio_ct", feature = "glommio", feature = "localpool" )) ] pub use local_worker_pool::*;
@@ -0,0 +1,732 @@
+//! Provides Native, a small work-stealing threadpool that only depends on std and futures-task.
+//
+use
+{
+	crate        :: { SpawnHandle, SpawnHandleGeneric, SpawnMany, JoinHandle, iface::join_handle::InnerJh } ,
+	crate        :: { exec::task_state::TaskState                                                    } ,
+	futures_task :: { FutureObj, Spawn, SpawnError, ArcWake, waker, waker_ref                       } ,
+	std          :: { cell::Cell, collections::{ HashMap, VecDeque }, fmt, future::Future, pin::Pin } ,
+	std          :: { panic::{ catch_unwind, resume_unwind, AssertUnwindSafe }, thread              } ,
+	std          :: { task::{ Context, Poll, Waker }                                                } ,
+	std          :: { sync::{ Arc, Weak, Mutex, Condvar, atomic::{ AtomicBool, AtomicUsize, Ordering::SeqCst } } } ,
+};
+
+
+// Lets a worker thread recognize it's own pool, to push the tasks it wakes up onto it's local queue.
+//
+static NEXT_POOL: AtomicUsize = AtomicUsize::new( 0 );
+
+thread_local!
+{
+	// The id of the pool and the index of the worker, if this thread is a worker.
+	//
+	static WORKER: Cell< Option<(usize, usize)> > = const { Cell::new( None ) };
+}
+
+// How often a worker looks at the shared queue before it's own, so tasks that keep waking up locally
+// can't starve the ones spawned from outside.
+//
+const GLOBAL_QUEUE_INTERVAL: usize = 61;
+
+
+/// A multi threaded, work-stealing executor that needs no runtime dependency besides futures-task.
+///
+/// It's meant as a lightweight default for libraries that don't want to pull in tokio or async-std. Each worker
+/// thread has a queue for the tasks that get woken up on it, and takes work from a shared queue for tasks spawned
+/// from outside the pool. Idle workers steal from the others. Create it with [`NativeBuilder`](crate::NativeBuilder)
+/// to set the number of threads, their names and stack size, and hooks that run when a thread starts or stops.
+///
+/// [`JoinHandle`]s for this executor are native, without the channel of a `RemoteHandle`. As usual, dropping one
+/// cancels the task, and [`JoinHandle::detach`] lets it run.
+///
+/// The executor is shut down when the last clone is dropped. The worker threads finish the poll they are in and
+/// stop, and all tasks that are not finished are dropped. Awaiting the `JoinHandle` of such a task panics.
+///
+/// It has no reactor, so there is no IO or [`Timer`](crate::Timer). Futures from other crates that bring their
+/// own reactor, like futures-timer or async-io, work fine.
+///
+/// ## Panics
+///
+/// Panics in tasks are caught, so they don't take down the worker thread. A task spawned with [`Spawn`] is dropped
+/// when it panics. For a task with a [`JoinHandle`], the panic is resumed in the task that awaits the handle.
+///
+/// ```
+/// use async_executors::{ NativeBuilder, SpawnHandleExt };
+///
+/// let exec = NativeBuilder::new().threads( 2 ).build().expect( "create native executor" );
+///
+/// let five = exec.block_on( async
+/// {
+///    exec.spawn_handle( async { 5u8 } ).expect( "spawn" ).await
+/// });
+///
+/// assert_eq!( five, 5 );
+/// ```
+//
+#[ derive( Clone ) ]
+//
+#[ cfg_attr( nightly, doc(cfg( feature = "native" )) ) ]
+//
+pub struct Native
+{
+	handle: Arc<Handle>,
+}
+
+
+// Shuts the pool down when the last clone of Native is dropped. The worker threads only hold on to Shared.
+//
+struct Handle
+{
+	shared: Arc<Shared>,
+}
+
+
+pub(crate) struct Shared
+{
+	id       : usize                                       ,
+	injector : Mutex< VecDeque< Arc<Task> > >              ,
+	locals   : Vec< Mutex< VecDeque< Arc<Task> > > >       ,
+	sleep    : Mutex<()>                                   ,
+	wakeup   : Condvar                                     ,
+	shutdown : AtomicBool                                  ,
+
+	// All tasks that aren't finished, so they can be dropped on shutdown, even when they are not in a queue.
+	//
+	tasks    : Mutex< HashMap< usize, Weak<Task> > >       ,
+	next_task: AtomicUsize                                 ,
+}
+
+
+
+impl Native
+{
+	/// Create the shared state, the worker threads are started by the builder.
+	//
+	pub(crate) fn new( threads: usize ) -> Self
+	{
+		let shared = Shared
+		{
+			id       : NEXT_POOL.fetch_add( 1, SeqCst )                       ,
+			injector : Mutex::new( VecDeque::new() )                          ,
+			locals   : (0..threads).map( |_| Mutex::new( VecDeque::new() ) ).collect() ,
+			sleep    : Mutex::new(())                                         ,
+			wakeup   : Condvar::new()                                         ,
+			shutdown : AtomicBool::new( false )                               ,
+			tasks    : Mutex::new( HashMap::new() )                           ,
+			next_task: AtomicUsize::new( 0 )                                  ,
+		};
+
+		Self { handle: Arc::new( Handle{ shared: Arc::new( shared ) } ) }
+	}
+
+
+	pub(crate) fn shared( &self ) -> Arc<Shared>
+	{
+		self.handle.shared.clone()
+	}
+
+
+	/// The number of worker threads.
+	//
+	pub fn threads( &self ) -> usize
+	{
+		self.handle.shared.locals.len()
+	}
+
+
+	/// Run a future to completion on the current thread, while the tasks run on the pool. This blocks the thread, so
+	/// don't call it from async code.
+	///
+	/// ## Panics
+	///
+	/// When called from a worker thread of a `Native` executor, as that could keep the worker from running the
+	/// tasks the future waits for. It also panics if the future panics.
+	//
+	pub fn block_on<F: Future>( &self, future: F ) -> F::Output
+	{
+		assert!
+		(
+			WORKER.with( Cell::get ).is_none(),
+			"Native::block_on can not be called from a worker thread of a Native executor"
+		);
+
+		let mut future = Box::pin( future );
+		let signal     = Arc::new( Signal{ thread: thread::current(), notified: AtomicBool::new( false ) } );
+		let waker      = waker_ref( &signal );
+		let mut cx     = Context::from_waker( &waker );
+
+		loop
+		{
+			if let Poll::Ready( output ) = future.as_mut().poll( &mut cx )
+			{
+				return output;
+			}
+
+			while !signal.notified.swap( false, SeqCst )
+			{
+				thread::park();
+			}
+		}
+	}
+
+
+	fn new_task( &self, future: FutureObj<'static, ()> ) -> Arc<Task>
+	{
+		let shared = &self.handle.shared;
+
+		Arc::new( Task
+		{
+			id       : shared.next_task.fetch_add( 1, SeqCst ) ,
+			future   : Mutex::new( Some(future) )               ,
+			pool     : Arc::downgrade( shared )                 ,
+			state    : TaskState::new()                         ,
+		})
+	}
+
+
+	fn spawn_task( &self, future: FutureObj<'static, ()> ) -> Arc<Task>
+	{
+		let task = self.new_task( future );
+
+		self.handle.shared.tasks.lock().expect( "lock tasks" ).insert( task.id, Arc::downgrade( &task ) );
+
+		ArcWake::wake_by_ref( &task );
+
+		task
+	}
+
+
+	// Registers and queues a batch of tasks with one lock on the registry and the queue, and wakes up the workers
+	// once, instead of doing that for every task.
+	//
+	fn spawn_tasks( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, ()> > ) -> Vec< Arc<Task> >
+	{
+		let shared = &self.handle.shared;
+		let tasks: Vec<_> = futures.map( |future| self.new_task( future ) ).collect();
+
+		shared.tasks.lock().expect( "lock tasks" ).extend( tasks.iter().map( |task| ( task.id, Arc::downgrade( task ) ) ) );
+
+		// Nobody has a waker for them yet, so they are all idle and this marks them as scheduled.
+		//
+		for task in &tasks { task.state.wake(); }
+
+		shared.push_many( &tasks );
+
+		tasks
+	}
+
+
+	fn harness<F>( future: F ) -> ( FutureObj<'static, ()>, Arc< JoinState<F::Output> > )
+
+		where F: Future + Unpin + Send + 'static, F::Output: Send + 'static
+	{
+		let state = Arc::new( JoinState
+		{
+			inner    : Mutex::new( JoinInner{ output: None, finished: false, waker: None } ) ,
+			cancelled: AtomicBool::new( false )                                              ,
+		});
+
+		( FutureObj::new( Box::new( Harness{ future, state: state.clone() } ) ), state )
+	}
+
+
+	fn join_handle<T>( state: Arc< JoinState<T> >, task: Arc<Task> ) -> JoinHandle<T>
+	{
+		JoinHandle{ inner: InnerJh::Native( NativeJoinHandle
+		{
+			state            ,
+			task: waker(task),
+			detached: false  ,
+		})}
+	}
+
+
+	fn spawn_join<F>( &self, future: F ) -> JoinHandle<F::Output>
+
+		where F: Future + Unpin + Send + 'static, F::Output: Send + 'static
+	{
+		let (future, state) = Self::harness( future );
+
+		Self::join_handle( state, self.spawn_task( future ) )
+	}
+}
+
+
+
+impl Spawn for Native
+{
+	fn spawn_obj( &self, future: FutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		self.spawn_task( future );
+
+		Ok(())
+	}
+}
+
+
+
+impl SpawnMany for Native
+{
+	fn spawn_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		self.spawn_tasks( futures );
+
+		Ok(())
+	}
+}
+
+
+
+impl<Out: 'static + Send> SpawnHandle<Out> for Native
+{
+	fn spawn_handle_obj( &self, future: FutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>
+	{
+		Ok( self.spawn_join( future ) )
+	}
+
+
+	fn spawn_handle_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		let (futures, states): (Vec<_>, Vec<_>) = futures.map( Self::harness ).unzip();
+
+		let tasks = self.spawn_tasks( &mut futures.into_iter() );
+
+		Ok( tasks.into_iter().zip( states ).map( |(task, state)| Self::join_handle( state, task ) ).collect() )
+	}
+}
+
+
+
+impl<Out: 'static + Send> SpawnHandleGeneric<Out> for Native
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		Ok( self.spawn_join( Box::pin( future ) ) )
+	}
+}
+
+
+
+impl fmt::Debug for Native
+{
+	fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result
+	{
+		f.debug_struct( "Native" )
+
+			.field( "threads", &self.threads() )
+			.finish()
+	}
+}
+
+
+
+impl Drop for Handle
+{
+	fn drop( &mut self )
+	{
+		self.shared.shutdown.store( true, SeqCst );
+
+		{
+			let _guard = self.shared.sleep.lock().expect( "lock sleep" );
+			self.shared.wakeup.notify_all();
+		}
+
+		// Drop the futures outside of the lock, as their destructors might wake other tasks.
+		//
+		let tasks: Vec<_> = self.shared.tasks.lock().expect( "lock tasks" ).drain().map( |(_, task)| task ).collect();
+
+		for task in tasks.iter().filter_map( Weak::upgrade )
+		{
+			// If it's being polled, the worker drops it after the poll.
+			//
+			let future = match task.future.try_lock()
+			{
+				Ok( mut slot ) => slot.take(),
+				Err( _       ) => None       ,
+			};
+
+			drop( future );
+		}
+	}
+}
+
+
+
+impl Shared
+{
+	/// The main loop of a worker thread.
+	//
+	pub(crate) fn run_worker( &self, index: usize )
+	{
+		WORKER.with( |w| w.set( Some(( self.id, index )) ) );
+
+		let mut tick = 0;
+
+		while !self.shutdown.load( SeqCst )
+		{
+			match self.next_task( index, tick == 0 )
+			{
+				Some(task) => task.run(),
+				None       => self.sleep(),
+			}
+
+			tick = ( tick + 1 ) % GLOBAL_QUEUE_INTERVAL;
+		}
+
+		WORKER.with( |w| w.set( None ) );
+	}
+
+
+	fn push( &self, task: Arc<Task> )
+	{
+		match WORKER.with( Cell::get )
+		{
+			Some(( pool, index )) if pool == self.id => self.locals[index].lock().expect( "lock queue" ).push_back( task ),
+			_                                        => self.injector     .lock().expect( "lock queue" ).push_back( task ),
+		}
+
+		// A worker checks the queues while holding this lock before it goes to sleep, so it either sees the task
+		// or gets notified.
+		//
+		let _guard = self.sleep.lock().expect( "lock sleep" );
+		self.wakeup.notify_one();
+	}
+
+
+	fn push_many( &self, tasks: &[ Arc<Task> ] )
+	{
+		if tasks.is_empty() { return; }
+
+		match WORKER.with( Cell::get )
+		{
+			Some(( pool, index )) if pool == self.id => self.locals[index].lock().expect( "lock queue" ).extend( tasks.iter().cloned() ),
+			_                                        => self.injector     .lock().expect( "lock queue" ).extend( tasks.iter().cloned() ),
+		}
+
+		let _guard = self.sleep.lock().expect( "lock sleep" );
+		self.wakeup.notify_all();
+	}
+
+
+	fn next_task( &self, index: usize, global_first: bool ) -> Option< Arc<Task> >
+	{
+		if global_first
+		{
+			if let Some(task) = self.injector.lock().expect( "lock queue" ).pop_front() { return Some(task) }
+		}
+
+		if let Some(task) = self.locals[index].lock().expect( "lock queue" ).pop_front() { return Some(task) }
+		if let Some(task) = self.injector     .lock().expect( "lock queue" ).pop_front() { return Some(task) }
+
+		// Steal from the back of the other queues.
+		//
+		let len = self.locals.len();
+
+		(1..len).find_map( |i| self.locals[ (index + i) % len ].lock().expect( "lock queue" ).pop_back() )
+	}
+
+
+	fn has_work( &self ) -> bool
+	{
+		!self.injector.lock().expect( "lock queue" ).is_empty()
+
+			|| self.locals.iter().any( |queue| !queue.lock().expect( "lock queue" ).is_empty() )
+	}
+
+
+	fn sleep( &self )
+	{
+		let guard = self.sleep.lock().expect( "lock sleep" );
+
+		if self.shutdown.load( SeqCst ) || self.has_work()
+		{
+			return;
+		}
+
+		drop( self.wakeup.wait( guard ).expect( "wait for tasks" ) );
+	}
+}
+
+
+
+// A spawned future. It's put in a queue every time it's woken up.
+//
+struct Task
+{
+	id       : usize                                       ,
+	future   : Mutex< Option< FutureObj<'static, ()> > >   ,
+	pool     : Weak<Shared>                                ,
+
+	// Makes sure the task is queued once and that a wake up during the poll doesn't make another worker wait for
+	// the lock on the future.
+	//
+	state    : TaskState,
+}
+
+
+impl Task
+{
+	fn queue( self: &Arc<Self> )
+	{
+		if let Some(pool) = self.pool.upgrade()
+		{
+			if !pool.shutdown.load( SeqCst )
+			{
+				pool.push( self.clone() );
+			}
+		}
+	}
+
+
+	fn run( self: Arc<Self> )
+	{
+		if !self.state.start() { return; }
+
+		// Only the worker that polls the task takes this lock, and Handle::drop if it can get it.
+		//
+		let done =
+		{
+			let mut slot = self.future.lock().expect( "lock task" );
+
+			match slot.as_mut()
+			{
+				None => true,
+
+				Some(future) =>
+				{
+					let waker  = waker_ref( &self );
+					let mut cx = Context::from_waker( &waker );
+
+					// A task spawned with Spawn is dropped when it panics.
+					//
+					!matches!( catch_unwind( AssertUnwindSafe( || Pin::new( future ).poll( &mut cx ) ) ), Ok( Poll::Pending ) )
+				}
+			}
+		};
+
+		let pool     = self.pool.upgrade();
+		let shutdown = match &pool
+		{
+			Some(pool) => pool.shutdown.load( SeqCst ),
+			None       => true,
+		};
+
+		if done || shutdown
+		{
+			self.state.complete();
+
+			let future = self.future.lock().expect( "lock task" ).take();
+
+			if let Some(pool) = pool
+			{
+				pool.tasks.lock().expect( "lock tasks" ).remove( &self.id );
+			}
+
+			drop( future );
+		}
+
+		else if self.state.pending()
+		{
+			// Woken up during the poll.
+			//
+			self.queue();
+		}
+	}
+}
+
+
+impl ArcWake for Task
+{
+	fn wake_by_ref( arc_self: &Arc<Self> )
+	{
+		if arc_self.state.wake()
+		{
+			arc_self.queue();
+		}
+	}
+}
+
+
+
+// Wakes up the thread in block_on.
+//
+struct Signal
+{
+	thread  : thread::Thread,
+	notified: AtomicBool    ,
+}
+
+
+impl ArcWake for Signal
+{
+	fn wake_by_ref( arc_self: &Arc<Self> )
+	{
+		arc_self.notified.store( true, SeqCst );
+		arc_self.thread.unpark();
+	}
+}
+
+
+
+// Shared between a task and it's JoinHandle.
+//
+struct JoinState<T>
+{
+	inner    : Mutex< JoinInner<T> > ,
+	cancelled: AtomicBool            ,
+}
+
+
+struct JoinInner<T>
+{
+	output  : Option< thread::Result<T> > ,
+	finished: bool                        ,
+	waker   : Option< Waker >             ,
+}
+
+
+impl<T> JoinState<T>
+{
+	// Also called when the task is dropped before it finishes, without output.
+	//
+	fn finish( &self, output: Option< thread::Result<T> > )
+	{
+		let waker =
+		{
+			let mut inner = self.inner.lock().expect( "lock join state" );
+
+			if inner.finished { return; }
+
+			inner.finished = true;
+			inner.output   = output;
+			inner.waker.take()
+		};
+
+		if let Some(waker) = waker { waker.wake(); }
+	}
+}
+
+
+
+// The future of a task with a JoinHandle. Stores the output or the panic for the handle.
+//
+struct Harness<F: Future>
+{
+	future: F                          ,
+	state : Arc< JoinState<F::Output> > ,
+}
+
+
+impl<F: Future + Unpin> Future for Harness<F>
+{
+	type Output = ();
+
+	fn poll( self: Pin<&mut Self>, cx: &mut Context<'_> ) -> Poll<()>
+	{
+		let this = self.get_mut();
+
+		if this.state.cancelled.load( SeqCst )
+		{
+			return Poll::Ready(());
+		}
+
+		let output = match catch_unwind( AssertUnwindSafe( || Pin::new( &mut this.future ).poll( cx ) ) )
+		{
+			Ok ( Poll::Pending     ) => return Poll::Pending,
+			Ok ( Poll::Ready(out)  ) => Ok ( out   ),
+			Err( panic             ) => Err( panic ),
+		};
+
+		this.state.finish( Some(output) );
+
+		Poll::Ready(())
+	}
+}
+
+
+impl<F: Future> Drop for Harness<F>
+{
+	fn drop( &mut self )
+	{
+		self.state.finish( None );
+	}
+}
+
+
+
+/// The native JoinHandle of a task on [`Native`].
+//
+pub(crate) struct NativeJoinHandle<T>
+{
+	state   : Arc< JoinState<T> > ,
+	task    : Waker               ,
+	detached: bool                ,
+}
+
+
+impl<T> NativeJoinHandle<T>
+{
+	pub(crate) fn detach( &mut self )
+	{
+		self.detached = true;
+	}
+}
+
+
+impl<T> Future for NativeJoinHandle<T>
+{
+	type Output = T;
+
+	fn poll( self: Pin<&mut Self>, cx: &mut Context<'_> ) -> Poll<T>
+	{
+		let mut inner = self.state.inner.lock().expect( "lock join state" );
+
+		match inner.output.take()
+		{
+			Some( Ok (output) ) => Poll::Ready( output ),
+			Some( Err(panic ) ) => { drop( inner ); resume_unwind( panic ) }
+
+			None if inner.finished => { drop( inner ); panic!( "Task has been canceled. Are you dropping the executor to early?" ) }
+
+			None =>
+			{
+				inner.waker = Some( cx.waker().clone() );
+				Poll::Pending
+			}
+		}
+	}
+}
+
+
+impl<T> Drop for NativeJoinHandle<T>
+{
+	fn drop( &mut self )
+	{
+		if self.detached { return; }
+
+		self.state.cancelled.store( true, SeqCst );
+
+		// Wake the task so it drops it's future.
+		//
+		if !self.state.inner.lock().expect( "lock join state" ).finished
+		{
+			self.task.wake_by_ref();
+		}
+	}
+}
+
+
+impl<T> fmt::Debug for NativeJoinHandle<T>
+{
+	fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result
+	{
+		f.debug_struct( "NativeJoinHandle" )
+
+			.field( "detached", &self.detached )
+			.finish()
+	}
+}
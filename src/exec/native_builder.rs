@@ -0,0 +1,169 @@
+//! Provides NativeBuilder to configure and start the Native executor.
+//
+use
+{
+	crate :: { Native                 } ,
+	std   :: { fmt, io, sync::Arc, thread } ,
+};
+
+
+/// Builder to create a [`Native`] executor.
+///
+/// ```
+/// use async_executors::{ NativeBuilder, SpawnHandleExt };
+///
+/// let exec = NativeBuilder::new()
+///
+///    .threads        ( 2                                   )
+///    .thread_name_fn ( |i| format!( "compute-{}", i )      )
+///    .stack_size     ( 4 * 1024 * 1024                     )
+///    .on_thread_start( || println!( "worker started" )     )
+///    .on_thread_stop ( || println!( "worker stopped" )     )
+///    .build()
+///    .expect( "create native executor" )
+/// ;
+///
+/// let name = exec.spawn_handle( async { std::thread::current().name().map( str::to_string ) } ).expect( "spawn" );
+///
+/// assert!( exec.block_on( name ).expect( "thread name" ).starts_with( "compute-" ) );
+/// ```
+//
+#[ derive( Clone, Default ) ]
+//
+#[ cfg_attr( nightly, doc(cfg( feature = "native" )) ) ]
+//
+pub struct NativeBuilder
+{
+	threads        : Option< usize                                    > ,
+	thread_name    : Option< Arc<dyn Fn(usize) -> String + Send + Sync> > ,
+	stack_size     : Option< usize                                    > ,
+	on_thread_start: Option< Arc<dyn Fn() + Send + Sync>              > ,
+	on_thread_stop : Option< Arc<dyn Fn() + Send + Sync>              > ,
+}
+
+
+
+impl NativeBuilder
+{
+	/// Constructor.
+	//
+	pub fn new() -> Self
+	{
+		Self::default()
+	}
+
+
+	/// The number of worker threads. Defaults to the available parallelism.
+	//
+	pub fn threads( &mut self, threads: usize ) -> &mut Self
+	{
+		self.threads = Some( threads );
+		self
+	}
+
+
+	/// A closure that returns the name of a worker thread from it's index. Defaults to `async_executors-native-{i}`.
+	//
+	pub fn thread_name_fn( &mut self, thread_name_fn: impl Fn(usize) -> String + Send + Sync + 'static ) -> &mut Self
+	{
+		self.thread_name = Some( Arc::new( thread_name_fn ) );
+		self
+	}
+
+
+	/// The stack size of the worker threads in bytes. Defaults to the one of [`std::thread`].
+	//
+	pub fn stack_size( &mut self, stack_size: usize ) -> &mut Self
+	{
+		self.stack_size = Some( stack_size );
+		self
+	}
+
+
+	/// Runs on every worker thread when it starts, before it runs any tasks.
+	//
+	pub fn on_thread_start( &mut self, hook: impl Fn() + Send + Sync + 'static ) -> &mut Self
+	{
+		self.on_thread_start = Some( Arc::new( hook ) );
+		self
+	}
+
+
+	/// Runs on every worker thread when it stops, after the executor is dropped.
+	//
+	pub fn on_thread_stop( &mut self, hook: impl Fn() + Send + Sync + 'static ) -> &mut Self
+	{
+		self.on_thread_stop = Some( Arc::new( hook ) );
+		self
+	}
+
+
+	/// Start the worker threads and create the executor.
+	///
+	/// ## Errors
+	///
+	/// When the number of threads is 0, or when spawning a thread fails. The threads started so far are stopped.
+	//
+	pub fn build( &mut self ) -> Result<Native, io::Error>
+	{
+		let threads = match self.threads
+		{
+			Some(0) => return Err( io::Error::new( io::ErrorKind::InvalidInput, "Native needs at least one thread" ) ),
+			Some(n) => n,
+			None    => thread::available_parallelism().map_or( 1, usize::from ),
+		};
+
+		// If we return early, dropping the executor stops the threads that were started.
+		//
+		let exec = Native::new( threads );
+
+		for index in 0..threads
+		{
+			let name = match &self.thread_name
+			{
+				Some(name) => name( index ),
+				None       => format!( "async_executors-native-{}", index ),
+			};
+
+			let mut builder = thread::Builder::new().name( name );
+
+			if let Some(size) = self.stack_size
+			{
+				builder = builder.stack_size( size );
+			}
+
+			let shared = exec.shared();
+			let start  = self.on_thread_start.clone();
+			let stop   = self.on_thread_stop .clone();
+
+			builder.spawn( move ||
+			{
+				if let Some(start) = start { start(); }
+
+				shared.run_worker( index );
+
+				if let Some(stop) = stop { stop(); }
+
+			})?;
+		}
+
+		Ok( exec )
+	}
+}
+
+
+
+impl fmt::Debug for NativeBuilder
+{
+	fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result
+	{
+		f.debug_struct( "NativeBuilder" )
+
+			.field( "threads"        , &self.threads                   )
+			.field( "thread_name"    , &self.thread_name.is_some()     )
+			.field( "stack_size"     , &self.stack_size                )
+			.field( "on_thread_start", &self.on_thread_start.is_some() )
+			.field( "on_thread_stop" , &self.on_thread_stop .is_some() )
+			.finish()
+	}
+}
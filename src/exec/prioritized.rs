@@ -0,0 +1,506 @@
+//! Provides Prioritized, an executor wrapper that shares the executor fairly between classes of tasks.
+//
+use
+{
+	crate        :: { SpawnHandle, SpawnMany, JoinHandle, Timer, iface::join_handle::InnerJh       } ,
+	crate        :: { SpawnHandleGeneric                                                           } ,
+	futures_task :: { FutureObj, Spawn, SpawnError                                                 } ,
+	futures_util :: { future::{ BoxFuture, FutureExt }                                             } ,
+	std          :: { collections::VecDeque, future::Future, time::{ Duration, Instant }           } ,
+	std          :: { sync::{ Arc, Mutex, MutexGuard }                                             } ,
+};
+
+
+// The pass of a class advances by this divided by it's weight every time one of it's tasks is started.
+//
+const STRIDE: u64 = 1 << 20;
+
+
+/// Identifies a class of tasks in a [`Prioritized`] executor, eg. a tenant or a priority level.
+/// Create one with [`Prioritized::add_class`].
+//
+#[ derive( Debug, Clone, Copy, PartialEq, Eq, Hash ) ]
+//
+pub struct Class( usize );
+
+
+impl Class
+{
+	/// The class used by the spawn traits. It has weight 1.
+	//
+	pub const DEFAULT: Class = Class(0);
+}
+
+
+
+/// An executor wrapper that shares the wrapped executor between classes of tasks in proportion to their weight,
+/// so that a burst of tasks in one class cannot starve the others.
+///
+/// At most `concurrency` tasks spawned through the wrapper run on the executor at the same time. The others wait in a
+/// queue per class. Whenever a task finishes, the next one is taken from the class that has received the least
+/// service relative to it's weight (stride scheduling). A class with weight 4 gets to start 4 tasks for every task
+/// of a class with weight 1, as long as both have tasks waiting. Classes that were idle do not bank their share.
+///
+/// The spawn traits use [`Class::DEFAULT`]. To pick the class per spawn, use [`SpawnClassExt::spawn_class`]
+/// or [`SpawnClassExt::spawn_handle_class`]. They are available on every executor that implements [`SpawnClass`],
+/// so code that takes a generic executor can choose the class as well.
+///
+/// Note that the wrapped executor needs to be `Send + Sync`, as tasks are started from the thread on which the previous
+/// one finishes. Spawning fails when the [`status`](Spawn::status) of the wrapped executor is an error, eg. because
+/// it was shut down. If it starts refusing tasks while some are still queued, those are dropped. If you hold the
+/// [`JoinHandle`] of such a task, awaiting it will panic.
+///
+/// Clones share the same queues.
+///
+/// ```
+/// use
+/// {
+///    async_executors :: { Prioritized, SpawnClassExt, ThreadPool } ,
+///    futures         :: { executor::block_on             } ,
+/// };
+///
+/// let exec   = Prioritized::new( ThreadPool::new().expect( "create threadpool" ), 4 );
+/// let bulk   = exec.add_class( 1 );
+/// let urgent = exec.add_class( 8 );
+///
+/// // When both classes have tasks waiting, urgent ones get started 8 times as often.
+/// //
+/// let slow = exec.spawn_handle_class( bulk  , async { 1u8 } ).expect( "spawn" );
+/// let fast = exec.spawn_handle_class( urgent, async { 2u8 } ).expect( "spawn" );
+///
+/// assert_eq!( block_on( slow ), 1 );
+/// assert_eq!( block_on( fast ), 2 );
+/// ```
+//
+pub struct Prioritized<E>
+{
+	shared: Arc< Shared<E> >,
+}
+
+
+struct Shared<E>
+{
+	exec : E            ,
+	limit: usize        ,
+	inner: Mutex<Inner> ,
+}
+
+
+struct Inner
+{
+	running: usize            ,
+	classes: Vec<ClassState>  ,
+
+	// The pass of the class that most recently started a task. Classes that become active again start from here.
+	//
+	vtime  : u64              ,
+
+	// Whether some thread is currently starting tasks.
+	//
+	pumping: bool             ,
+}
+
+
+struct ClassState
+{
+	stride: u64                                ,
+	pass  : u64                                ,
+	queue : VecDeque< FutureObj<'static, ()> > ,
+}
+
+
+impl ClassState
+{
+	fn new( weight: u32 ) -> Self
+	{
+		assert!( weight > 0, "Prioritized: the weight of a class must be at least 1" );
+
+		Self { stride: STRIDE / u64::from( weight ).min( STRIDE ), pass: 0, queue: VecDeque::new() }
+	}
+}
+
+
+
+impl<E> Shared<E>
+{
+	fn lock( &self ) -> MutexGuard<'_, Inner>
+	{
+		// A panic while holding the lock cannot leave the queues in an inconsistent state.
+		//
+		self.inner.lock().unwrap_or_else( |e| e.into_inner() )
+	}
+}
+
+
+impl<E> Shared<E> where E: Spawn + Send + Sync + 'static
+{
+	fn enqueue( self: &Arc<Self>, class: Class, task: FutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		self.enqueue_many( class, &mut std::iter::once( task ) )
+	}
+
+
+	// Queues a batch of tasks with one lock and starts as many as there are free slots.
+	//
+	fn enqueue_many( self: &Arc<Self>, class: Class, tasks: &mut dyn Iterator< Item = FutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		// Don't accept a task that the wrapped executor would refuse once it's turn comes.
+		//
+		self.exec.status()?;
+
+		// Don't run the iterator while holding the lock, it might spawn on this executor.
+		//
+		let tasks: Vec<_> = tasks.collect();
+
+		let mut inner = self.lock();
+		let vtime     = inner.vtime;
+
+		let state = inner.classes.get_mut( class.0 ).expect( "Prioritized: class is not registered with this executor" );
+
+		if state.queue.is_empty()
+		{
+			state.pass = state.pass.max( vtime );
+		}
+
+		state.queue.extend( tasks );
+
+		drop( inner );
+
+		self.pump();
+
+		Ok(())
+	}
+
+
+	// Start tasks as long as there are free slots. Only one thread does this at a time, the others just
+	// update the counters and leave it to that one, which also prevents recursion when a task is dropped
+	// because the executor failed to spawn it.
+	//
+	fn pump( self: &Arc<Self> )
+	{
+		{
+			let mut inner = self.lock();
+
+			if inner.pumping { return; }
+
+			inner.pumping = true;
+		}
+
+		loop
+		{
+			let mut inner = self.lock();
+
+			let task = if inner.running < self.limit { next( &mut inner ) } else { None };
+
+			let task = match task
+			{
+				Some( task ) => task,
+
+				None =>
+				{
+					inner.pumping = false;
+					return;
+				}
+			};
+
+			inner.running += 1;
+
+			drop( inner );
+
+			let permit = Permit{ shared: self.clone() };
+
+			let spawned = self.exec.spawn_obj( FutureObj::new( async move
+			{
+				let _permit = permit;
+				task.await;
+
+			}.boxed() ));
+
+			// The wrapped executor started refusing tasks after enqueue checked it's status. The task and it's permit
+			// were dropped, which frees the slot again. Awaiting the JoinHandle, if any, panics.
+			//
+			if spawned.is_err() { continue; }
+		}
+	}
+}
+
+
+// Take the next task from the class with the lowest pass.
+//
+fn next( inner: &mut Inner ) -> Option< FutureObj<'static, ()> >
+{
+	let state = inner.classes.iter_mut()
+
+		.filter ( |c| !c.queue.is_empty() )
+		.min_by_key( |c| c.pass )?
+	;
+
+	let task    = state.queue.pop_front();
+	let pass    = state.pass;
+	state.pass += state.stride;
+	inner.vtime = pass;
+
+	task
+}
+
+
+
+impl<E> Prioritized<E> where E: Spawn + Send + Sync + 'static
+{
+	/// Wrap `exec`. At most `concurrency` tasks spawned through the wrapper run at the same time. There is one class,
+	/// [`Class::DEFAULT`], with weight 1.
+	///
+	/// # Panics
+	///
+	/// When `concurrency` is zero.
+	//
+	pub fn new( exec: E, concurrency: usize ) -> Self
+	{
+		assert!( concurrency > 0, "Prioritized: the concurrency must be at least 1" );
+
+		let inner = Inner{ running: 0, classes: vec![ ClassState::new(1) ], vtime: 0, pumping: false };
+
+		Self { shared: Arc::new( Shared{ exec, limit: concurrency, inner: Mutex::new( inner ) } ) }
+	}
+
+
+	/// Add a class with the given weight.
+	///
+	/// # Panics
+	///
+	/// When `weight` is zero.
+	//
+	pub fn add_class( &self, weight: u32 ) -> Class
+	{
+		let mut inner = self.shared.lock();
+
+		inner.classes.push( ClassState::new( weight ) );
+
+		Class( inner.classes.len() - 1 )
+	}
+
+
+	/// The number of tasks that are running on the wrapped executor.
+	//
+	pub fn running( &self ) -> usize
+	{
+		self.shared.lock().running
+	}
+
+
+	/// The number of tasks that are waiting to run.
+	//
+	pub fn queued( &self ) -> usize
+	{
+		self.shared.lock().classes.iter().map( |c| c.queue.len() ).sum()
+	}
+
+
+	/// Access the wrapped executor.
+	//
+	pub fn inner( &self ) -> &E
+	{
+		&self.shared.exec
+	}
+}
+
+
+
+impl<E> Spawn for Prioritized<E> where E: Spawn + Send + Sync + 'static
+{
+	fn spawn_obj( &self, future: FutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		self.shared.enqueue( Class::DEFAULT, future )
+	}
+}
+
+
+
+impl<E> SpawnMany for Prioritized<E> where E: Spawn + Send + Sync + 'static
+{
+	fn spawn_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		self.shared.enqueue_many( Class::DEFAULT, futures )
+	}
+}
+
+
+
+impl<E, Out> SpawnHandle<Out> for Prioritized<E> where E: Spawn + Send + Sync + 'static, Out: 'static + Send
+{
+	fn spawn_handle_obj( &self, future: FutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>
+	{
+		self.spawn_handle_class( Class::DEFAULT, future )
+	}
+
+
+	fn spawn_handle_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		let mut handles = Vec::new();
+
+		self.shared.enqueue_many( Class::DEFAULT, &mut futures.map( |future|
+		{
+			let (fut, handle) = future.remote_handle();
+
+			handles.push( JoinHandle{ inner: InnerJh::RemoteHandle( Some(handle) ) } );
+
+			FutureObj::new( fut.boxed() )
+		}))?;
+
+		Ok( handles )
+	}
+}
+
+
+
+impl<E, Out> SpawnHandleGeneric<Out> for Prioritized<E> where E: Spawn + Send + Sync + 'static, Out: 'static + Send
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		// The queues hold FutureObj, so this still allocates once.
+		//
+		let (fut, handle) = future.remote_handle();
+
+		self.shared.enqueue( Class::DEFAULT, FutureObj::new( Box::new( fut ) ) )?;
+
+		Ok( JoinHandle{ inner: InnerJh::RemoteHandle( Some(handle) ) } )
+	}
+}
+
+
+
+impl<E> SpawnClass for Prioritized<E> where E: Spawn + Send + Sync + 'static
+{
+	fn spawn_class_obj( &self, class: Class, future: FutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		self.shared.enqueue( class, future )
+	}
+}
+
+
+
+/// An executor that can spawn a task in a given [`Class`], like [`Prioritized`].
+///
+/// This trait is object safe. Use [`SpawnClassExt`] to spawn a generic future or get a [`JoinHandle`].
+//
+pub trait SpawnClass: Spawn
+{
+	/// Spawn a future in the given class.
+	///
+	/// # Panics
+	///
+	/// Implementations may panic when the class was not created by this executor, like [`Prioritized`] does.
+	//
+	fn spawn_class_obj( &self, class: Class, future: FutureObj<'static, ()> ) -> Result<(), SpawnError>;
+}
+
+
+
+/// Convenience methods for [`SpawnClass`]. This is implemented for every type that implements it, but it must be in
+/// scope.
+///
+/// ```
+/// use
+/// {
+///    async_executors :: { Prioritized, Class, SpawnClass, SpawnClassExt, ThreadPool, JoinHandle } ,
+///    futures         :: { executor::block_on                                                    } ,
+/// };
+///
+/// fn urgent( exec: &impl SpawnClass, class: Class ) -> JoinHandle<u8>
+/// {
+///    exec.spawn_handle_class( class, async { 5 } ).expect( "spawn" )
+/// }
+///
+/// let exec  = Prioritized::new( ThreadPool::new().expect( "create threadpool" ), 4 );
+/// let class = exec.add_class( 8 );
+///
+/// assert_eq!( block_on( urgent( &exec, class ) ), 5 );
+/// ```
+//
+pub trait SpawnClassExt: SpawnClass
+{
+	/// Spawn a future in the given class.
+	//
+	fn spawn_class( &self, class: Class, future: impl Future<Output = ()> + Send + 'static ) -> Result<(), SpawnError>
+	{
+		self.spawn_class_obj( class, FutureObj::new( future.boxed() ) )
+	}
+
+
+	/// Spawn a future in the given class and return a [`JoinHandle`] to await it's output.
+	//
+	fn spawn_handle_class<Out: 'static + Send>( &self, class: Class, future: impl Future<Output = Out> + Send + 'static )
+
+		-> Result<JoinHandle<Out>, SpawnError>
+	{
+		let (fut, handle) = future.remote_handle();
+
+		self.spawn_class_obj( class, FutureObj::new( fut.boxed() ) )?;
+
+		Ok( JoinHandle{ inner: InnerJh::RemoteHandle( Some(handle) ) } )
+	}
+}
+
+
+impl<T: SpawnClass + ?Sized> SpawnClassExt for T {}
+
+
+
+impl<E: Timer> Timer for Prioritized<E>
+{
+	fn sleep      ( &self, dur     : Duration ) -> BoxFuture<'static, ()> { self.shared.exec.sleep( dur )            }
+	fn now        ( &self                     ) -> Instant                { self.shared.exec.now()                   }
+	fn sleep_until( &self, deadline: Instant  ) -> BoxFuture<'static, ()> { self.shared.exec.sleep_until( deadline ) }
+}
+
+
+
+impl<E> Clone for Prioritized<E>
+{
+	fn clone( &self ) -> Self
+	{
+		Self { shared: self.shared.clone() }
+	}
+}
+
+
+
+impl<E: std::fmt::Debug> std::fmt::Debug for Prioritized<E>
+{
+	fn fmt( &self, f: &mut std::fmt::Formatter<'_> ) -> std::fmt::Result
+	{
+		let inner = self.shared.lock();
+
+		f.debug_struct( "Prioritized" )
+
+			.field( "exec"       , &self.shared.exec                                         )
+			.field( "concurrency", &self.shared.limit                                        )
+			.field( "running"    , &inner.running                                            )
+			.field( "queued"     , &inner.classes.iter().map( |c| c.queue.len() ).sum::<usize>() )
+			.field( "classes"    , &inner.classes.len()                                      )
+			.finish()
+	}
+}
+
+
+
+// Holds a slot on the wrapped executor, frees it on drop. Lives inside the spawned task.
+//
+struct Permit<E> where E: Spawn + Send + Sync + 'static
+{
+	shared: Arc< Shared<E> >,
+}
+
+
+impl<E> Drop for Permit<E> where E: Spawn + Send + Sync + 'static
+{
+	fn drop( &mut self )
+	{
+		self.shared.lock().running -= 1;
+		self.shared.pump();
+	}
+}
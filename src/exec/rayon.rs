@@ -0,0 +1,255 @@
+//! Provides RayonExec, which polls futures and runs closures on a rayon threadpool.
+//
+use
+{
+	crate        :: { SpawnHandle, SpawnHandleGeneric, JoinHandle, iface::join_handle::InnerJh } ,
+	crate        :: { SpawnMany, exec::task_state::TaskState                                       } ,
+	futures_task :: { FutureObj, Spawn, SpawnError, ArcWake, waker_ref                         } ,
+	futures_util :: { future::FutureExt                                                        } ,
+	rayon_crate  :: { ThreadPool                                                               } ,
+	std          :: { future::Future, task::{ Context, Poll }                                  } ,
+	std          :: { panic::{ catch_unwind, AssertUnwindSafe }                                } ,
+	std          :: { sync::{ Arc, Mutex }                                                     } ,
+};
+
+
+/// An executor that runs futures and closures on a [rayon](https://docs.rs/rayon) threadpool, to offload CPU heavy
+/// work from async code.
+///
+/// It implements [`Spawn`], [`SpawnHandle`] and [`SpawnHandleGeneric`] like [`TokioTp`](crate::TokioTp), so it can
+/// be passed to the same APIs. Futures are polled on the rayon worker threads, and when they are woken up, the next
+/// poll is queued on the pool again. [`spawn_compute`](RayonExec::spawn_compute) runs a closure instead, and returns
+/// a [`JoinHandle`] for it's result. Within futures and closures, rayon's parallel iterators, `join` and `scope` use
+/// the same pool.
+///
+/// Use [`RayonExec::new`] to run on a pool you configured with `rayon::ThreadPoolBuilder`, or
+/// [`RayonExec::global`] (the default) for the global rayon pool.
+///
+/// Rayon is made for computations, so while a future is waiting on IO, it doesn't block a worker thread,
+/// but nothing in rayon drives IO or timers. Those must come from a reactor elsewhere.
+///
+/// ## Panics
+///
+/// Rayon aborts the process when a job panics, unless the pool has a panic handler. This executor catches panics
+/// in the tasks it spawns. A task spawned with [`Spawn`] is dropped when it panics. For a task with a
+/// [`JoinHandle`], the panic is resumed in the task awaiting the handle.
+///
+/// ```
+/// use
+/// {
+///    async_executors :: { RayonExec, SpawnHandleExt } ,
+///    futures         :: { executor::block_on       } ,
+///    rayon_crate     :: { ThreadPoolBuilder        } ,
+/// };
+///
+/// let exec = RayonExec::new( ThreadPoolBuilder::new().num_threads( 2 ).build().expect( "create pool" ) );
+///
+/// let sum = exec.spawn_compute( ||
+/// {
+///    use rayon_crate::prelude::*;
+///
+///    (0..1_000u64).into_par_iter().sum::<u64>()
+///
+/// }).expect( "spawn" );
+///
+/// let future = exec.spawn_handle( async { 5u8 } ).expect( "spawn" );
+///
+/// assert_eq!( block_on( sum    ), 499_500 );
+/// assert_eq!( block_on( future ), 5       );
+/// ```
+//
+#[ derive( Debug, Clone, Default ) ]
+//
+#[ cfg_attr( nightly, doc(cfg( feature = "rayon" )) ) ]
+//
+pub struct RayonExec
+{
+	// None means the global pool.
+	//
+	pool: Option< Arc<ThreadPool> >,
+}
+
+
+
+impl RayonExec
+{
+	/// Spawn on the given threadpool.
+	//
+	pub fn new( pool: ThreadPool ) -> Self
+	{
+		Self { pool: Some( Arc::new( pool ) ) }
+	}
+
+
+	/// Spawn on the global rayon threadpool.
+	//
+	pub fn global() -> Self
+	{
+		Self { pool: None }
+	}
+
+
+	/// The threadpool this executor spawns on, `None` for the global pool.
+	//
+	pub fn pool( &self ) -> Option<&Arc<ThreadPool>>
+	{
+		self.pool.as_ref()
+	}
+
+
+	/// Run a closure on the threadpool and get a [`JoinHandle`] to await it's result, eg. from a task on another
+	/// executor. If the handle is dropped before the closure starts, it doesn't run. Once it runs, it can't be
+	/// interrupted.
+	///
+	/// The error is there for symmetry with [`SpawnHandle`], rayon always accepts new jobs.
+	//
+	pub fn spawn_compute<F, R>( &self, f: F ) -> Result<JoinHandle<R>, SpawnError>
+
+		where F: FnOnce() -> R + Send + 'static ,
+		      R: Send + 'static                 ,
+	{
+		self.spawn_handle_generic( async move { f() } )
+	}
+}
+
+
+impl From<ThreadPool> for RayonExec
+{
+	fn from( pool: ThreadPool ) -> Self
+	{
+		Self::new( pool )
+	}
+}
+
+
+impl From< Arc<ThreadPool> > for RayonExec
+{
+	fn from( pool: Arc<ThreadPool> ) -> Self
+	{
+		Self { pool: Some( pool ) }
+	}
+}
+
+
+
+impl Spawn for RayonExec
+{
+	fn spawn_obj( &self, future: FutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		let task = Arc::new( Task
+		{
+			future: Mutex::new( Some(future) ) ,
+			pool  : self.pool.clone()           ,
+			state : TaskState::new()            ,
+		});
+
+		ArcWake::wake( task );
+
+		Ok(())
+	}
+}
+
+
+
+impl SpawnMany for RayonExec {}
+
+
+
+impl<Out: 'static + Send> SpawnHandle<Out> for RayonExec
+{
+	fn spawn_handle_obj( &self, future: FutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>
+	{
+		self.spawn_handle_generic( future )
+	}
+}
+
+
+
+impl<Out: 'static + Send> SpawnHandleGeneric<Out> for RayonExec
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		// The remote future catches panics and resumes them in the handle.
+		//
+		let (fut, handle) = future.remote_handle();
+
+		self.spawn_obj( FutureObj::new( Box::new( fut ) ) )?;
+
+		Ok( JoinHandle{ inner: InnerJh::RemoteHandle( Some(handle) ) } )
+	}
+}
+
+
+
+// A future that gets polled as a rayon job every time it's woken up.
+//
+struct Task
+{
+	// Only locked by the worker that polls it, TaskState makes sure there is only one.
+	//
+	future: Mutex< Option< FutureObj<'static, ()> > > ,
+	pool  : Option< Arc<ThreadPool> >                 ,
+	state : TaskState                                 ,
+}
+
+
+impl Task
+{
+	fn queue( self: Arc<Self> )
+	{
+		match self.pool.clone()
+		{
+			Some(pool) => pool.spawn( move || self.run() ),
+			None       => rayon_crate::spawn( move || self.run() ),
+		}
+	}
+
+
+	fn run( self: Arc<Self> )
+	{
+		if !self.state.start() { return; }
+
+		let done =
+		{
+			let mut slot = self.future.lock().expect( "lock task" );
+
+			let done = match slot.as_mut()
+			{
+				None => true,
+
+				Some(future) =>
+				{
+					let waker  = waker_ref( &self );
+					let mut cx = Context::from_waker( &waker );
+
+					// A panic in a rayon job aborts the process, so drop the task instead.
+					//
+					!matches!( catch_unwind( AssertUnwindSafe( || future.poll_unpin( &mut cx ) ) ), Ok( Poll::Pending ) )
+				}
+			};
+
+			if done { *slot = None; }
+
+			done
+		};
+
+		// A wake up during the poll didn't queue the task, so it doesn't wait for the lock. Queue it now.
+		//
+		if      done                 { self.state.complete(); }
+		else if self.state.pending() { self.queue();          }
+	}
+}
+
+
+impl ArcWake for Task
+{
+	fn wake_by_ref( arc_self: &Arc<Self> )
+	{
+		if arc_self.state.wake()
+		{
+			arc_self.clone().queue();
+		}
+	}
+}
@@ -0,0 +1,253 @@
+//! Provides RemoteSpawner, a Send handle to spawn on single threaded executors from other threads.
+//
+use
+{
+	crate           :: { SpawnHandle, JoinHandle, iface::join_handle::InnerJh, SpawnMany                   } ,
+	crate           :: { SpawnHandleGeneric                                                                } ,
+	futures_task    :: { FutureObj, LocalFutureObj, Spawn, LocalSpawn, SpawnError                          } ,
+	futures_util    :: { future::{ FutureExt, select, Either, ready }, stream::StreamExt                   } ,
+	futures_channel :: { mpsc::{ unbounded, UnboundedSender, UnboundedReceiver }, oneshot                  } ,
+	std             :: { future::Future, panic::{ catch_unwind, resume_unwind, AssertUnwindSafe }         } ,
+};
+
+
+// Builds the future to spawn on the owning thread.
+//
+type Job = Box< dyn FnOnce() -> LocalFutureObj<'static, ()> + Send >;
+
+
+/// A handle to spawn tasks on a single threaded executor from other threads. It is `Send + Sync + Clone`.
+///
+/// Futures are sent over a channel to a task on the executor which spawns them there. Besides `Send` futures,
+/// it can take `Send` closures that create a `!Send` future on the owning thread, see
+/// [`spawn_with`](RemoteSpawner::spawn_with).
+///
+/// Obtain one with [`TokioCt::remote_spawner`](crate::TokioCt::remote_spawner),
+/// [`GlommioCt::remote_spawner`](crate::GlommioCt::remote_spawner) or [`RemoteSpawner::new`] for a
+/// `LocalSpawner`. Once the executor is dropped, spawning returns a
+/// [`SpawnError`]. Tasks only make progress when the executor runs, eg. during `block_on` or `LocalPool::run`.
+///
+/// A panic in a task spawned through the remote spawner doesn't take down the executor thread. If the task has a
+/// [`JoinHandle`], the panic is resumed in the task that awaits it, otherwise the task is dropped.
+///
+/// ```
+/// use
+/// {
+///    async_executors :: { RemoteSpawner, SpawnHandleExt } ,
+///    futures         :: { executor::LocalPool           } ,
+///    std             :: { rc::Rc, thread                } ,
+/// };
+///
+/// let mut pool = LocalPool::new();
+/// let remote   = RemoteSpawner::new( pool.spawner() ).expect( "spawn" );
+///
+/// let handle = thread::spawn( move ||
+/// {
+///    // The closure is Send, the future it creates on the pool's thread is not.
+///    //
+///    remote.spawn_handle_with( || async { *Rc::new( 5u8 ) } ).expect( "spawn" )
+///
+/// }).join().expect( "join thread" );
+///
+/// assert_eq!( pool.run_until( handle ), 5 );
+/// ```
+//
+#[ derive( Clone ) ]
+//
+pub struct RemoteSpawner
+{
+	tx: UnboundedSender<Job>,
+}
+
+
+impl RemoteSpawner
+{
+	/// Create a remote spawner for `exec`, by spawning a task on it that receives futures from other threads.
+	///
+	/// That task holds a clone of `exec`, so this is meant for handles that don't keep the executor alive, like
+	/// `LocalSpawner`. Otherwise, the executor and the task keep each other alive.
+	/// For [`TokioCt`](crate::TokioCt) and [`GlommioCt`](crate::GlommioCt), use their `remote_spawner` method.
+	//
+	pub fn new<E>( exec: E ) -> Result<Self, SpawnError>
+
+		where E: LocalSpawn + Clone + 'static
+	{
+		let spawner      = exec.clone();
+		let (this, pump) = Self::channel( move |fut| { let _ = spawner.spawn_local_obj( fut ); } );
+
+		exec.spawn_local_obj( LocalFutureObj::new( pump.boxed_local() ) )?;
+
+		Ok( this )
+	}
+
+
+	/// Create a remote spawner and the task that needs to be spawned on the owning executor, which uses `spawn`
+	/// to spawn the futures it receives.
+	//
+	pub(crate) fn channel( spawn: impl Fn( LocalFutureObj<'static, ()> ) + 'static ) -> ( Self, impl Future<Output = ()> )
+	{
+		let (tx, rx): (UnboundedSender<Job>, UnboundedReceiver<Job>) = unbounded();
+
+		let pump = rx.for_each( move |job|
+		{
+			// The closure and the future come from other threads, so catch their panics, which would otherwise
+			// unwind out of the executor on some backends, like LocalPool.
+			//
+			if let Ok( fut ) = catch_unwind( AssertUnwindSafe( job ) )
+			{
+				spawn( LocalFutureObj::new( AssertUnwindSafe( fut ).catch_unwind().map( drop ).boxed_local() ) );
+			}
+
+			ready(())
+		});
+
+		( Self{ tx }, pump )
+	}
+
+
+	/// Send a closure to the owning thread which creates a future to spawn there. This allows spawning `!Send` futures
+	/// from other threads.
+	//
+	pub fn spawn_with<F, Fut>( &self, f: F ) -> Result<(), SpawnError>
+
+		where F  : FnOnce() -> Fut + Send + 'static ,
+		      Fut: Future<Output = ()> + 'static    ,
+	{
+		self.send( Box::new( move || LocalFutureObj::new( f().boxed_local() ) ) )
+	}
+
+
+	/// Send a closure to the owning thread which creates a future to spawn there and return a [`JoinHandle`] for
+	/// it's output. Dropping the handle cancels the task.
+	//
+	pub fn spawn_handle_with<F, Fut, Out>( &self, f: F ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where F  : FnOnce() -> Fut + Send + 'static ,
+		      Fut: Future<Output = Out> + 'static   ,
+		      Out: Send + 'static                   ,
+	{
+		// The future is !Send, so the output goes through a channel which is polled by a remote_handle. A panic in
+		// the closure or the future is sent as well, and resumed by the remote_handle, which passes it on to the
+		// JoinHandle.
+		//
+		let (tx, rx) = oneshot::channel();
+
+		let (remote, handle) = rx.map( |out| match out.expect( "RemoteSpawner: the task was dropped" )
+		{
+			Ok ( out   ) => out,
+			Err( panic ) => resume_unwind( panic ),
+
+		}).remote_handle();
+
+		self.send( Box::new( move ||
+		{
+			let work = AssertUnwindSafe( async move { f().await } ).catch_unwind().map( move |out| { let _ = tx.send( out ); } );
+
+			LocalFutureObj::new( async move
+			{
+				// When the JoinHandle is dropped, remote resolves, which cancels the work.
+				// When the work is done, remote still has to deliver the output.
+				//
+				if let Either::Right( (_, remote) ) = select( remote, work.boxed_local() ).await
+				{
+					remote.await;
+				}
+
+			}.boxed_local() )
+		}))?;
+
+		Ok( JoinHandle{ inner: InnerJh::RemoteHandle( Some(handle) ) } )
+	}
+
+
+	/// Whether the executor is gone, so that spawning will fail.
+	//
+	pub fn is_closed( &self ) -> bool
+	{
+		self.tx.is_closed()
+	}
+
+
+	fn send( &self, job: Job ) -> Result<(), SpawnError>
+	{
+		self.tx.unbounded_send( job ).map_err( |_| SpawnError::shutdown() )
+	}
+}
+
+
+
+impl Spawn for RemoteSpawner
+{
+	fn spawn_obj( &self, future: FutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		self.send( Box::new( move || future.into() ) )
+	}
+
+
+	fn status( &self ) -> Result<(), SpawnError>
+	{
+		if self.is_closed() { Err( SpawnError::shutdown() ) } else { Ok(()) }
+	}
+}
+
+
+
+impl SpawnMany for RemoteSpawner {}
+
+
+
+impl<Out: 'static + Send> SpawnHandle<Out> for RemoteSpawner
+{
+	fn spawn_handle_obj( &self, future: FutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>
+	{
+		let (remote, handle) = future.remote_handle();
+
+		self.send( Box::new( move || LocalFutureObj::new( remote.boxed() ) ) )?;
+
+		Ok( JoinHandle{ inner: InnerJh::RemoteHandle( Some(handle) ) } )
+	}
+}
+
+
+
+impl<Out: 'static + Send> SpawnHandleGeneric<Out> for RemoteSpawner
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		// The job and the task still get boxed, but the future isn't boxed a second time to make a FutureObj.
+		//
+		let (remote, handle) = future.remote_handle();
+
+		self.send( Box::new( move || LocalFutureObj::new( Box::new( remote ) ) ) )?;
+
+		Ok( JoinHandle{ inner: InnerJh::RemoteHandle( Some(handle) ) } )
+	}
+}
+
+
+
+impl std::fmt::Debug for RemoteSpawner
+{
+	fn fmt( &self, f: &mut std::fmt::Formatter<'_> ) -> std::fmt::Result
+	{
+		f.debug_struct( "RemoteSpawner" )
+
+			.field( "closed", &self.is_closed() )
+			.finish()
+	}
+}
+
+
+
+#[ cfg(test) ]
+//
+mod tests
+{
+	use super::*;
+
+	// The whole point is to be able to move it to other threads.
+	//
+	static_assertions::assert_impl_all!( RemoteSpawner: Send, Sync, Clone );
+}
@@ -0,0 +1,336 @@
+//! Provides Sharded, which routes tasks to one of several executors by key.
+//
+use
+{
+	crate        :: { SpawnHandle, LocalSpawnHandle, JoinHandle, SpawnHandleExt, LocalSpawnHandleExt            } ,
+	crate        :: { SpawnMany, LocalSpawnMany, SpawnHandleGeneric, LocalSpawnHandleGeneric                     } ,
+	futures_task :: { FutureObj, LocalFutureObj, Spawn, LocalSpawn, SpawnError                                   } ,
+	std          :: { future::Future, collections::hash_map::DefaultHasher, hash::{ Hash, Hasher }              } ,
+	std          :: { sync::{ Arc, atomic::{ AtomicUsize, Ordering } }                                           } ,
+};
+
+
+/// Holds one executor per shard and routes tasks to them by key, so that all work for the same key,
+/// eg. a connection or a partition, runs on the same executor.
+///
+/// [`spawn_handle_on`](Sharded::spawn_handle_on) picks the shard from a hash of the key. The same key always goes
+/// to the same shard for the lifetime of the process, but the hash is not guaranteed to be stable across
+/// Rust versions, so don't persist the mapping. Use [`shard_for`](Sharded::shard_for) to find out which shard
+/// a key maps to.
+///
+/// The spawn traits route tasks that have no key round-robin over the shards.
+///
+/// For a thread-per-core design, run a single threaded executor like [`TokioCt`](crate::TokioCt) or
+/// [`GlommioCt`](crate::GlommioCt) on each thread and shard over their [`RemoteSpawner`](crate::RemoteSpawner)s,
+/// which can be used from any thread.
+///
+/// Clones share the shards and the round-robin counter.
+///
+/// ```
+/// use
+/// {
+///    async_executors :: { Sharded, SpawnHandleExt } ,
+///    futures         :: { executor::LocalPool     } ,
+/// };
+///
+/// let mut first  = LocalPool::new();
+/// let mut second = LocalPool::new();
+/// let exec       = Sharded::new( vec![ first.spawner(), second.spawner() ] );
+///
+/// let handle = exec.spawn_handle_on( "connection 7", async { 5u8 } ).expect( "spawn" );
+///
+/// let out = match exec.shard_for( "connection 7" )
+/// {
+///    0 => first .run_until( handle ),
+///    _ => second.run_until( handle ),
+/// };
+///
+/// assert_eq!( out, 5 );
+/// ```
+//
+pub struct Sharded<E>
+{
+	shards: Arc< Vec<E> >     ,
+	next  : Arc< AtomicUsize >,
+}
+
+
+impl<E> Sharded<E>
+{
+	/// Create a sharded executor with one shard per executor, in order.
+	///
+	/// # Panics
+	///
+	/// When `shards` is empty.
+	//
+	pub fn new( shards: impl IntoIterator<Item = E> ) -> Self
+	{
+		let shards: Vec<E> = shards.into_iter().collect();
+
+		assert!( !shards.is_empty(), "Sharded: there must be at least 1 shard" );
+
+		Self
+		{
+			shards: Arc::new( shards ),
+			next  : Arc::new( AtomicUsize::new(0) ),
+		}
+	}
+
+
+	/// The number of shards.
+	//
+	pub fn len( &self ) -> usize
+	{
+		self.shards.len()
+	}
+
+
+	/// Always false, as there is at least one shard.
+	//
+	pub fn is_empty( &self ) -> bool
+	{
+		false
+	}
+
+
+	/// The executor of the shard with the given index.
+	///
+	/// # Panics
+	///
+	/// When `index` is not smaller than [`len`](Sharded::len).
+	//
+	pub fn shard( &self, index: usize ) -> &E
+	{
+		&self.shards[ index ]
+	}
+
+
+	/// The index of the shard that tasks for `key` are spawned on.
+	//
+	pub fn shard_for<K: Hash + ?Sized>( &self, key: &K ) -> usize
+	{
+		let mut hasher = DefaultHasher::new();
+		key.hash( &mut hasher );
+
+		( hasher.finish() % self.shards.len() as u64 ) as usize
+	}
+
+
+	/// Spawn a future on the shard for `key`.
+	//
+	pub fn spawn_on<K, Fut>( &self, key: &K, future: Fut ) -> Result<(), SpawnError>
+
+		where K  : Hash + ?Sized                      ,
+		      E  : Spawn                              ,
+		      Fut: Future<Output = ()> + Send + 'static,
+	{
+		self.shards[ self.shard_for( key ) ].spawn_obj( FutureObj::new( Box::new( future ) ) )
+	}
+
+
+	/// Spawn a future on the shard for `key` and get a [`JoinHandle`] for it's output.
+	//
+	pub fn spawn_handle_on<K, Fut, Out>( &self, key: &K, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where K  : Hash + ?Sized                       ,
+		      E  : SpawnHandle<Out>                    ,
+		      Fut: Future<Output = Out> + Send + 'static,
+		      Out: 'static + Send                       ,
+	{
+		self.shards[ self.shard_for( key ) ].spawn_handle( future )
+	}
+
+
+	/// Spawn a `!Send` future on the shard for `key`.
+	//
+	pub fn spawn_local_on<K, Fut>( &self, key: &K, future: Fut ) -> Result<(), SpawnError>
+
+		where K  : Hash + ?Sized              ,
+		      E  : LocalSpawn                 ,
+		      Fut: Future<Output = ()> + 'static,
+	{
+		self.shards[ self.shard_for( key ) ].spawn_local_obj( LocalFutureObj::new( Box::new( future ) ) )
+	}
+
+
+	/// Spawn a `!Send` future on the shard for `key` and get a [`JoinHandle`] for it's output.
+	//
+	pub fn spawn_handle_local_on<K, Fut, Out>( &self, key: &K, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where K  : Hash + ?Sized               ,
+		      E  : LocalSpawnHandle<Out>       ,
+		      Fut: Future<Output = Out> + 'static,
+		      Out: 'static                       ,
+	{
+		self.shards[ self.shard_for( key ) ].spawn_handle_local( future )
+	}
+
+
+	// The next shard for tasks without a key.
+	//
+	fn round_robin( &self ) -> &E
+	{
+		&self.shards[ self.next.fetch_add( 1, Ordering::Relaxed ) % self.shards.len() ]
+	}
+
+
+	// Splits a batch into one chunk per shard, so every shard can spawn it's part in bulk. The chunks are
+	// contiguous, so the handles come back in the order of the futures.
+	//
+	fn chunks<T>( &self, futures: &mut dyn Iterator<Item = T> ) -> Vec<( &E, Vec<T> )>
+	{
+		let mut futures: Vec<T> = futures.collect();
+		let size                = futures.len().div_ceil( self.shards.len() ).max( 1 );
+		let mut chunks          = Vec::with_capacity( self.shards.len() );
+
+		while !futures.is_empty()
+		{
+			let rest = futures.split_off( size.min( futures.len() ) );
+
+			chunks.push(( self.round_robin(), std::mem::replace( &mut futures, rest ) ));
+		}
+
+		chunks
+	}
+}
+
+
+
+impl<E> Clone for Sharded<E>
+{
+	fn clone( &self ) -> Self
+	{
+		Self
+		{
+			shards: self.shards.clone(),
+			next  : self.next  .clone(),
+		}
+	}
+}
+
+
+
+impl<E: Spawn> Spawn for Sharded<E>
+{
+	fn spawn_obj( &self, future: FutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		self.round_robin().spawn_obj( future )
+	}
+}
+
+
+
+impl<E: SpawnMany> SpawnMany for Sharded<E>
+{
+	fn spawn_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		self.chunks( futures ).into_iter().try_for_each( |(shard, chunk)| shard.spawn_obj_many( &mut chunk.into_iter() ) )
+	}
+}
+
+
+
+impl<E: LocalSpawn> LocalSpawn for Sharded<E>
+{
+	fn spawn_local_obj( &self, future: LocalFutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		self.round_robin().spawn_local_obj( future )
+	}
+}
+
+
+
+impl<E: LocalSpawnMany> LocalSpawnMany for Sharded<E>
+{
+	fn spawn_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		self.chunks( futures ).into_iter().try_for_each( |(shard, chunk)| shard.spawn_local_obj_many( &mut chunk.into_iter() ) )
+	}
+}
+
+
+
+impl<E: SpawnHandle<Out>, Out: 'static + Send> SpawnHandle<Out> for Sharded<E>
+{
+	fn spawn_handle_obj( &self, future: FutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>
+	{
+		self.round_robin().spawn_handle_obj( future )
+	}
+
+
+	fn spawn_handle_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		let mut handles = Vec::new();
+
+		for (shard, chunk) in self.chunks( futures )
+		{
+			handles.extend( shard.spawn_handle_obj_many( &mut chunk.into_iter() )? );
+		}
+
+		Ok( handles )
+	}
+}
+
+
+
+impl<E: LocalSpawnHandle<Out>, Out: 'static> LocalSpawnHandle<Out> for Sharded<E>
+{
+	fn spawn_handle_local_obj( &self, future: LocalFutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>
+	{
+		self.round_robin().spawn_handle_local_obj( future )
+	}
+
+
+	fn spawn_handle_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		let mut handles = Vec::new();
+
+		for (shard, chunk) in self.chunks( futures )
+		{
+			handles.extend( shard.spawn_handle_local_obj_many( &mut chunk.into_iter() )? );
+		}
+
+		Ok( handles )
+	}
+}
+
+
+
+impl<E: SpawnHandleGeneric<Out>, Out: 'static + Send> SpawnHandleGeneric<Out> for Sharded<E>
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		self.round_robin().spawn_handle_generic( future )
+	}
+}
+
+
+
+impl<E: LocalSpawnHandleGeneric<Out>, Out: 'static> LocalSpawnHandleGeneric<Out> for Sharded<E>
+{
+	fn spawn_handle_local_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + 'static
+	{
+		self.round_robin().spawn_handle_local_generic( future )
+	}
+}
+
+
+
+impl<E> std::fmt::Debug for Sharded<E>
+{
+	fn fmt( &self, f: &mut std::fmt::Formatter<'_> ) -> std::fmt::Result
+	{
+		f.debug_struct( "Sharded" )
+
+			.field( "shards", &self.shards.len() )
+			.finish()
+	}
+}
@@ -0,0 +1,435 @@
+//! Provides Supervisor, which restarts background tasks when they fail.
+//
+use
+{
+	crate          :: { SpawnHandle, SpawnHandleExt, JoinHandle, Timer                  } ,
+	futures_task   :: { SpawnError                                                      } ,
+	futures_util   :: { future::FutureExt                                               } ,
+	futures_channel:: { mpsc::{ unbounded, UnboundedSender, UnboundedReceiver }         } ,
+	std            :: { any::Any, collections::VecDeque, fmt, future::Future                    } ,
+	std            :: { panic::{ catch_unwind, AssertUnwindSafe }                       } ,
+	std            :: { sync::{ Arc, Mutex, MutexGuard }, time::{ Duration, Instant }   } ,
+};
+
+
+/// When a [`Supervisor`] restarts a task.
+//
+#[ derive( Debug, Clone, Copy, PartialEq, Eq, Hash ) ]
+//
+pub enum Restart
+{
+	/// Never restart the task.
+	//
+	Never,
+
+	/// Restart the task when it returns an error or panics.
+	//
+	OnFailure,
+
+	/// Restart the task whenever it ends.
+	//
+	Always,
+}
+
+
+
+/// Describes when and how often a [`Supervisor`] restarts a task.
+///
+/// By default, a task can be restarted 5 times within a minute. The delay before a restart starts at 100ms and
+/// doubles with every restart that is still within the window, up to 30 seconds.
+///
+/// ```
+/// use { async_executors::RestartPolicy, std::time::Duration };
+///
+/// let policy = RestartPolicy::on_failure()
+///
+///    .max_restarts( 3, Duration::from_secs( 10 )                        )
+///    .backoff     ( Duration::from_millis( 10 ), Duration::from_secs(1) )
+/// ;
+/// ```
+//
+#[ derive( Debug, Clone, Copy, PartialEq, Eq ) ]
+//
+pub struct RestartPolicy
+{
+	restart     : Restart  ,
+	max_restarts: u32      ,
+	window      : Duration ,
+	backoff_min : Duration ,
+	backoff_max : Duration ,
+}
+
+
+impl RestartPolicy
+{
+	/// A policy with the given [`Restart`] strategy and the default limits.
+	//
+	pub fn new( restart: Restart ) -> Self
+	{
+		Self
+		{
+			restart                                     ,
+			max_restarts: 5                             ,
+			window      : Duration::from_secs( 60 )     ,
+			backoff_min : Duration::from_millis( 100 )  ,
+			backoff_max : Duration::from_secs( 30 )     ,
+		}
+	}
+
+
+	/// Never restart the task. It is still supervised in the sense that panics are caught and reported.
+	//
+	pub fn never() -> Self
+	{
+		Self::new( Restart::Never )
+	}
+
+
+	/// Restart the task when it returns an error or panics.
+	//
+	pub fn on_failure() -> Self
+	{
+		Self::new( Restart::OnFailure )
+	}
+
+
+	/// Restart the task whenever it ends.
+	//
+	pub fn always() -> Self
+	{
+		Self::new( Restart::Always )
+	}
+
+
+	/// Give up when the task would be restarted more than `max` times within `window`.
+	//
+	pub fn max_restarts( mut self, max: u32, window: Duration ) -> Self
+	{
+		self.max_restarts = max   ;
+		self.window       = window;
+		self
+	}
+
+
+	/// Wait `min` before the first restart. The delay doubles for every earlier restart within the window,
+	/// up to `max`.
+	//
+	pub fn backoff( mut self, min: Duration, max: Duration ) -> Self
+	{
+		self.backoff_min = min;
+		self.backoff_max = max;
+		self
+	}
+
+
+	/// The restart strategy.
+	//
+	pub fn restart( &self ) -> Restart
+	{
+		self.restart
+	}
+
+
+	fn should_restart( &self, exit: &TaskExit ) -> bool
+	{
+		match self.restart
+		{
+			Restart::Never     => false                              ,
+			Restart::OnFailure => !matches!( exit, TaskExit::Completed ),
+			Restart::Always    => true                               ,
+		}
+	}
+
+
+	// The delay before the next restart, given the number of restarts already in the window.
+	//
+	fn delay( &self, recent: u32 ) -> Duration
+	{
+		let factor = 1u32.checked_shl( recent ).unwrap_or( u32::MAX );
+
+		self.backoff_min.checked_mul( factor ).unwrap_or( self.backoff_max ).min( self.backoff_max )
+	}
+}
+
+
+impl Default for RestartPolicy
+{
+	fn default() -> Self
+	{
+		Self::on_failure()
+	}
+}
+
+
+
+/// How a run of a supervised task ended.
+//
+#[ derive( Debug, Clone, PartialEq, Eq ) ]
+//
+pub enum TaskExit
+{
+	/// The task returned `Ok`.
+	//
+	Completed,
+
+	/// The task returned an error. Contains it's `Display` output.
+	//
+	Failed( String ),
+
+	/// The task panicked. Contains the panic message if it was a string.
+	//
+	Panicked( String ),
+}
+
+
+
+/// Something that happened to a supervised task. Observe them with [`Supervisor::events`].
+//
+#[ derive( Debug, Clone, PartialEq, Eq ) ]
+//
+pub struct SupervisorEvent
+{
+	/// The name the task was registered with.
+	//
+	pub name: Arc<str>,
+
+	/// What happened.
+	//
+	pub kind: EventKind,
+}
+
+
+/// The kind of a [`SupervisorEvent`].
+//
+#[ derive( Debug, Clone, PartialEq, Eq ) ]
+//
+#[ non_exhaustive ]
+//
+pub enum EventKind
+{
+	/// A run of the task was started.
+	//
+	Started
+	{
+		/// The number of restarts before this run, so 0 for the first run.
+		//
+		attempt: u32,
+	},
+
+	/// A run of the task ended.
+	//
+	Exited( TaskExit ),
+
+	/// The task will be restarted.
+	//
+	Restarting
+	{
+		/// How long the supervisor waits before the restart.
+		//
+		delay: Duration,
+	},
+
+	/// The task was restarted too often within the window of the policy and won't be restarted again.
+	//
+	GaveUp,
+}
+
+
+
+/// Runs background tasks and restarts them according to a [`RestartPolicy`] when they end, so that long-lived
+/// workers don't silently die when they return an error or panic.
+///
+/// A task is registered as a factory that creates a new future for every run. Panics are caught, so the future
+/// must be unwind safe in the sense of [`std::panic::UnwindSafe`], which is not checked. What happens can be observed
+/// through [`events`](Supervisor::events).
+///
+/// The backoff uses the [`Timer`] of the executor. Clones share the event subscribers.
+///
+/// ```
+/// use
+/// {
+///    async_executors :: { Supervisor, RestartPolicy, TaskExit, ThreadPool } ,
+///    futures         :: { executor::block_on                            } ,
+///    std             :: { sync::{ Arc, atomic::{ AtomicU32, Ordering } }, time::Duration } ,
+/// };
+///
+/// let sup    = Supervisor::new( ThreadPool::new().expect( "create threadpool" ) );
+/// let runs   = Arc::new( AtomicU32::new(0) );
+/// let runs2  = runs.clone();
+/// let policy = RestartPolicy::on_failure().backoff( Duration::from_millis(1), Duration::from_millis(10) );
+///
+/// let handle = sup.supervise( "worker", policy, move ||
+/// {
+///    let run = runs2.fetch_add( 1, Ordering::SeqCst );
+///
+///    async move
+///    {
+///       if run < 2 { Err( "flaky" ) } else { Ok(()) }
+///    }
+///
+/// }).expect( "spawn" );
+///
+/// assert_eq!( block_on( handle ), TaskExit::Completed );
+/// assert_eq!( runs.load( Ordering::SeqCst ), 3 );
+/// ```
+//
+#[ derive( Clone ) ]
+//
+pub struct Supervisor<Exec>
+{
+	exec       : Exec                                              ,
+	subscribers: Arc< Mutex< Vec< UnboundedSender<SupervisorEvent> > > > ,
+}
+
+
+impl<Exec> Supervisor<Exec>
+{
+	/// Create a supervisor that spawns tasks on `exec`.
+	//
+	pub fn new( exec: Exec ) -> Self
+	{
+		Self { exec, subscribers: Arc::new( Mutex::new( Vec::new() ) ) }
+	}
+
+
+	/// Subscribe to the events of all tasks supervised by this supervisor or it's clones from now on.
+	//
+	pub fn events( &self ) -> UnboundedReceiver<SupervisorEvent>
+	{
+		let (tx, rx) = unbounded();
+
+		lock( &self.subscribers ).push( tx );
+
+		rx
+	}
+
+
+	/// Access the wrapped executor.
+	//
+	pub fn inner( &self ) -> &Exec
+	{
+		&self.exec
+	}
+}
+
+
+impl<Exec> Supervisor<Exec> where Exec: SpawnHandle<TaskExit> + Timer + Clone + Send + 'static
+{
+	/// Spawn a task and restart it according to `policy` when it ends. `factory` is called to create the future
+	/// for every run. A panic in `factory` ends the run like a panic in the future does.
+	///
+	/// The [`JoinHandle`] resolves to how the last run ended, once the task is no longer restarted.
+	/// Dropping it stops the supervision and the running task.
+	//
+	pub fn supervise<F, Fut, Err>( &self, name: impl Into<Arc<str>>, policy: RestartPolicy, factory: F )
+
+		-> Result<JoinHandle<TaskExit>, SpawnError>
+
+		where F  : Fn() -> Fut + Send + 'static                  ,
+		      Fut: Future< Output = Result<(), Err> > + Send + 'static ,
+		      Err: fmt::Display                                  ,
+	{
+		let name        = name.into();
+		let timer       = self.exec.clone();
+		let subscribers = self.subscribers.clone();
+
+		let report = move |kind| emit( &subscribers, SupervisorEvent { name: name.clone(), kind } );
+
+		let task = async move
+		{
+			let mut restarts: VecDeque<Instant> = VecDeque::new();
+			let mut attempt = 0;
+
+			loop
+			{
+				report( EventKind::Started{ attempt } );
+
+				// A panic in the factory counts as a panic of this run, like one in the future it returns.
+				//
+				let made = catch_unwind( AssertUnwindSafe( &factory ) );
+
+				// In a block, so the outcome, which isn't Send, is gone before the next await.
+				//
+				let exit =
+				{
+					let outcome = match made
+					{
+						Ok ( fut   ) => AssertUnwindSafe( fut ).catch_unwind().await ,
+						Err( panic ) => Err( panic )                                  ,
+					};
+
+					match outcome
+					{
+						Ok ( Ok (()) ) => TaskExit::Completed                      ,
+						Ok ( Err(e)  ) => TaskExit::Failed  ( e.to_string()       ),
+						Err( panic   ) => TaskExit::Panicked( panic_msg( &panic ) ),
+					}
+				};
+
+				report( EventKind::Exited( exit.clone() ) );
+
+				if !policy.should_restart( &exit ) { return exit; }
+
+				let now = timer.now();
+
+				restarts.retain( |t| now.saturating_duration_since( *t ) <= policy.window );
+
+				if restarts.len() >= policy.max_restarts as usize
+				{
+					report( EventKind::GaveUp );
+					return exit;
+				}
+
+				let delay = policy.delay( restarts.len() as u32 );
+
+				report( EventKind::Restarting{ delay } );
+
+				timer.sleep( delay ).await;
+
+				restarts.push_back( timer.now() );
+				attempt += 1;
+			}
+		};
+
+		self.exec.spawn_handle( task )
+	}
+}
+
+
+impl<Exec: fmt::Debug> fmt::Debug for Supervisor<Exec>
+{
+	fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result
+	{
+		f.debug_struct( "Supervisor" )
+
+			.field( "exec"       , &self.exec                       )
+			.field( "subscribers", &lock( &self.subscribers ).len() )
+			.finish()
+	}
+}
+
+
+
+fn lock<T>( mutex: &Mutex<T> ) -> MutexGuard<'_, T>
+{
+	// A panic while holding the lock cannot leave the subscribers in an inconsistent state.
+	//
+	mutex.lock().unwrap_or_else( |e| e.into_inner() )
+}
+
+
+// Send the event to all subscribers, forgetting the ones that are gone.
+//
+fn emit( subscribers: &Mutex< Vec< UnboundedSender<SupervisorEvent> > >, event: SupervisorEvent )
+{
+	lock( subscribers ).retain( |tx| tx.unbounded_send( event.clone() ).is_ok() );
+}
+
+
+fn panic_msg( panic: &Box<dyn Any + Send> ) -> String
+{
+	if let Some( s ) = panic.downcast_ref::<&str>() { return (*s).to_string(); }
+	if let Some( s ) = panic.downcast_ref::<String>() { return s.clone(); }
+
+	"Box<dyn Any>".to_string()
+}
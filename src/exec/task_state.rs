@@ -0,0 +1,86 @@
+//! The scheduling state of a task on the executors that poll futures on a threadpool of their own.
+//
+use std::sync::atomic::{ AtomicUsize, Ordering::SeqCst };
+
+
+const IDLE     : usize = 0; // Waiting to be woken up.
+const SCHEDULED: usize = 1; // In a queue, waiting for a worker.
+const RUNNING  : usize = 2; // Being polled.
+const NOTIFIED : usize = 3; // Woken up while being polled. The worker queues it again after the poll.
+const COMPLETE : usize = 4; // Finished, wake ups are ignored.
+
+
+/// Makes sure a task is in a queue at most once and only one worker polls it at a time. A wake up during a poll
+/// doesn't queue the task, so no other worker has to wait for the poll to end. Instead the worker that polls it
+/// queues it again afterwards.
+//
+#[ derive( Debug ) ]
+//
+pub(crate) struct TaskState( AtomicUsize );
+
+
+impl TaskState
+{
+	pub(crate) fn new() -> Self
+	{
+		Self( AtomicUsize::new( IDLE ) )
+	}
+
+
+	/// Call when the task is woken up. Returns true if the caller must queue the task.
+	//
+	pub(crate) fn wake( &self ) -> bool
+	{
+		let mut state = self.0.load( SeqCst );
+
+		loop
+		{
+			let next = match state
+			{
+				IDLE    => SCHEDULED,
+				RUNNING => NOTIFIED ,
+				_       => return false,
+			};
+
+			match self.0.compare_exchange( state, next, SeqCst, SeqCst )
+			{
+				Ok (_     ) => return next == SCHEDULED,
+				Err(actual) => state = actual,
+			}
+		}
+	}
+
+
+	/// Call when a worker takes the task from a queue. Returns false if the task is complete and must not be polled.
+	//
+	pub(crate) fn start( &self ) -> bool
+	{
+		self.0.compare_exchange( SCHEDULED, RUNNING, SeqCst, SeqCst ).is_ok()
+	}
+
+
+	/// Call after a poll that returned pending. Returns true if the task was woken up during the poll, in which case
+	/// it is scheduled again and the caller must queue it.
+	//
+	pub(crate) fn pending( &self ) -> bool
+	{
+		// Only the polling worker moves the state out of NOTIFIED, so there is no race here.
+		//
+		if self.0.compare_exchange( RUNNING, IDLE, SeqCst, SeqCst ).is_ok()
+		{
+			return false;
+		}
+
+		self.0.store( SCHEDULED, SeqCst );
+
+		true
+	}
+
+
+	/// Call when the future is done or dropped.
+	//
+	pub(crate) fn complete( &self )
+	{
+		self.0.store( COMPLETE, SeqCst );
+	}
+}
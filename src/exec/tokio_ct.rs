@@ -1,9 +1,11 @@
 use
 {
-	crate        :: { SpawnHandle, LocalSpawnHandle, JoinHandle, iface::join_handle::InnerJh } ,
-	std          :: { rc::Rc, future::Future, sync::atomic::AtomicBool                } ,
-	tokio        :: { task::LocalSet, runtime::{  Runtime }                           } ,
-	futures_task :: { FutureObj, LocalFutureObj, Spawn, LocalSpawn, SpawnError        } ,
+	crate        :: { SpawnHandle, LocalSpawnHandle, JoinHandle, Timer, RemoteSpawner, iface::join_handle::InnerJh } ,
+	crate        :: { SpawnHandleGeneric, LocalSpawnHandleGeneric, SpawnMany, LocalSpawnMany  } ,
+	std          :: { rc::Rc, future::Future, sync::atomic::AtomicBool, time::Duration        } ,
+	tokio        :: { task::{ LocalSet, JoinHandle as TokioJoinHandle }, runtime::Runtime      } ,
+	futures_task :: { FutureObj, LocalFutureObj, Spawn, LocalSpawn, SpawnError               } ,
+	futures_util :: { future::BoxFuture                                                      } ,
 };
 
 
@@ -45,6 +47,20 @@ use
 /// });
 ///```
 ///
+/// ## Placement of `Send` tasks
+///
+/// `!Send` futures, from [`LocalSpawn`], [`LocalSpawnHandle`] and [`LocalSpawnHandleGeneric`], always go into the
+/// `LocalSet`. Where `Send` futures, from [`Spawn`], [`SpawnHandle`] and [`SpawnHandleGeneric`], go is decided by
+/// [`SendPlacement`], which you can set with [`TokioCtBuilder::send_placement`](crate::TokioCtBuilder::send_placement).
+/// All three traits follow the same setting.
+///
+/// The default is [`SendPlacement::LocalSet`], so all tasks share one queue and get polled in turn while `block_on`
+/// runs. With [`SendPlacement::Runtime`], `Send` tasks are spawned on the runtime, outside the `LocalSet`. They also
+/// run on the thread calling `block_on`, but are scheduled by tokio, independently of the `LocalSet`.
+///
+/// Before [`SendPlacement`] was added, [`SpawnHandle`] spawned on the runtime. Use [`SendPlacement::Runtime`] to keep
+/// that behavior.
+///
 /// ## Unwind Safety.
 ///
 /// When a future spawned on this wrapper panics, the panic will be caught by tokio in the poll function.
@@ -75,8 +91,31 @@ use
 //
 pub struct TokioCt
 {
-	pub(crate) exec : Rc< Runtime  > ,
-	pub(crate) local: Rc< LocalSet > ,
+	pub(crate) exec     : Rc< Runtime  > ,
+	pub(crate) local    : Rc< LocalSet > ,
+	pub(crate) placement: SendPlacement  ,
+}
+
+
+
+/// Where [`TokioCt`] puts `Send` futures. `!Send` futures always go into the `LocalSet`.
+/// See [`TokioCtBuilder::send_placement`](crate::TokioCtBuilder::send_placement).
+//
+#[ derive( Debug, Clone, Copy, PartialEq, Eq, Default ) ]
+//
+#[ cfg_attr( nightly, doc(cfg( feature = "tokio_ct" )) ) ]
+//
+pub enum SendPlacement
+{
+	/// Spawn `Send` futures into the `LocalSet`, like `!Send` ones. This is the default.
+	//
+	#[ default ]
+	//
+	LocalSet,
+
+	/// Spawn `Send` futures on the tokio runtime, outside the `LocalSet`.
+	//
+	Runtime,
 }
 
 
@@ -101,6 +140,43 @@ impl TokioCt
 	{
 		self.exec.block_on( self.local.run_until( f ) )
 	}
+
+
+	/// Get a handle that lets other threads spawn on this executor, see [`RemoteSpawner`]. The tasks start running
+	/// when this executor runs.
+	//
+	pub fn remote_spawner( &self ) -> RemoteSpawner
+	{
+		// The task receiving the futures does not hold on to the executor, as that would keep it alive.
+		//
+		let (remote, pump) = RemoteSpawner::channel( |fut| drop( tokio::task::spawn_local( fut ) ) );
+
+		drop( self.local.spawn_local( pump ) );
+
+		remote
+	}
+
+
+	/// Where `Send` futures are spawned.
+	//
+	pub fn send_placement( &self ) -> SendPlacement
+	{
+		self.placement
+	}
+
+
+	// Spawn a `Send` future according to the placement.
+	//
+	fn spawn_send<Fut>( &self, future: Fut ) -> TokioJoinHandle<Fut::Output>
+
+		where Fut: Future + Send + 'static, Fut::Output: Send + 'static
+	{
+		match self.placement
+		{
+			SendPlacement::LocalSet => self.local.spawn_local( future ) ,
+			SendPlacement::Runtime  => self.exec .spawn      ( future ) ,
+		}
+	}
 }
 
 
@@ -110,7 +186,7 @@ impl Spawn for TokioCt
 	{
 		// We drop the tokio JoinHandle, so the task becomes detached.
 		//
-		let _ = self.local.spawn_local( future );
+		let _ = self.spawn_send( future );
 
 		Ok(())
 	}
@@ -118,6 +194,10 @@ impl Spawn for TokioCt
 
 
 
+impl SpawnMany for TokioCt {}
+
+
+
 impl LocalSpawn for TokioCt
 {
 	fn spawn_local_obj( &self, future: LocalFutureObj<'static, ()> ) -> Result<(), SpawnError>
@@ -132,13 +212,17 @@ impl LocalSpawn for TokioCt
 
 
 
+impl LocalSpawnMany for TokioCt {}
+
+
+
 impl<Out: 'static + Send> SpawnHandle<Out> for TokioCt
 {
 	fn spawn_handle_obj( &self, future: FutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>
 	{
 		Ok( JoinHandle{ inner: InnerJh::Tokio
 		{
-			handle  : self.exec.spawn( future ) ,
+			handle  : self.spawn_send( future ) ,
 			detached: AtomicBool::new( false  ) ,
 		}})
 	}
@@ -161,6 +245,53 @@ impl<Out: 'static> LocalSpawnHandle<Out> for TokioCt
 
 
 
+impl<Out: 'static + Send> SpawnHandleGeneric<Out> for TokioCt
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		Ok( JoinHandle{ inner: InnerJh::Tokio
+		{
+			handle  : self.spawn_send( future ) ,
+			detached: AtomicBool::new( false  ) ,
+		}})
+	}
+}
+
+
+
+impl<Out: 'static> LocalSpawnHandleGeneric<Out> for TokioCt
+{
+	fn spawn_handle_local_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + 'static
+	{
+		Ok( JoinHandle{ inner: InnerJh::Tokio
+		{
+			handle  : self.local.spawn_local( future ) ,
+			detached: AtomicBool::new( false )         ,
+		}})
+	}
+}
+
+
+
+impl Timer for TokioCt
+{
+	fn sleep( &self, dur: Duration ) -> BoxFuture<'static, ()>
+	{
+		// The sleep future registers with the time driver of the runtime in who's context it is created,
+		// so enter ours in case we are called from elsewhere.
+		//
+		let _guard = self.exec.enter();
+
+		Box::pin( tokio::time::sleep( dur ) )
+	}
+}
+
+
+
 #[ cfg(test) ]
 //
 mod tests
@@ -2,7 +2,7 @@
 //
 use
 {
-	crate :: { TokioCt                          } ,
+	crate :: { TokioCt, SendPlacement           } ,
 	std   :: { rc::Rc                           } ,
 	tokio :: { task::LocalSet, runtime::Builder } ,
 };
@@ -12,12 +12,16 @@ use
 /// as tokio does not make this information available on it's `Runtime` type.
 ///
 /// Further allows you access to the tokio builder so you can set the other configuration options on it as you see fit.
+/// The time driver is enabled by default, as [`TokioCt`] implements [`Timer`](crate::Timer).
+///
+/// With [`send_placement`](TokioCtBuilder::send_placement) you choose where `Send` futures are spawned.
 //
 #[ derive(Debug) ]
 //
 pub struct TokioCtBuilder
 {
-	builder: Builder,
+	builder  : Builder       ,
+	placement: SendPlacement ,
 }
 
 
@@ -28,10 +32,13 @@ impl TokioCtBuilder
 	//
 	pub fn new() -> Self
 	{
-		Self
-		{
-			builder: Builder::new_current_thread(),
-		}
+		let mut builder = Builder::new_current_thread();
+
+		// Needed for the `Timer` impl.
+		//
+		builder.enable_time();
+
+		Self{ builder, placement: SendPlacement::default() }
 	}
 
 
@@ -44,6 +51,17 @@ impl TokioCtBuilder
 	}
 
 
+	/// Choose where the executor spawns `Send` futures, from [`Spawn`](futures_task::Spawn),
+	/// [`SpawnHandle`](crate::SpawnHandle) and [`SpawnHandleGeneric`](crate::SpawnHandleGeneric).
+	/// The default is [`SendPlacement::LocalSet`]. See [`TokioCt`] for the details.
+	//
+	pub fn send_placement( &mut self, placement: SendPlacement ) -> &mut Self
+	{
+		self.placement = placement;
+		self
+	}
+
+
 	/// Create the actual executor.
 	///
 	/// The error comes from tokio. From their docs, no idea why it is there or what could go wrong.
@@ -54,8 +72,9 @@ impl TokioCtBuilder
 
 		Ok( TokioCt
 		{
-			exec : Rc::new( exec            ) ,
-			local: Rc::new( LocalSet::new() ) ,
+			exec     : Rc::new( exec            ) ,
+			local    : Rc::new( LocalSet::new() ) ,
+			placement: self.placement             ,
 		})
 	}
 }
@@ -2,10 +2,12 @@
 //
 use
 {
-	crate          :: { SpawnHandle, JoinHandle, join_handle::InnerJh     } ,
-	std            :: { sync::{ Arc, atomic::AtomicBool }, future::Future } ,
-	futures_task   :: { FutureObj, Spawn, SpawnError                      } ,
-	tokio::runtime :: { Runtime                                           } ,
+	crate          :: { SpawnHandle, SpawnHandleGeneric, JoinHandle, Timer, join_handle::InnerJh } ,
+	crate          :: { SpawnMany                                                         } ,
+	std            :: { sync::{ Arc, atomic::AtomicBool }, future::Future, time::Duration } ,
+	futures_task   :: { FutureObj, Spawn, SpawnError                                      } ,
+	futures_util   :: { future::BoxFuture                                                 } ,
+	tokio::runtime :: { Runtime                                                           } ,
 };
 
 
@@ -104,7 +106,7 @@ impl TokioTp
 	///  only reference, self will be returned to you as an error. It means you cannot shutdown the runtime because there are
 	///  other clones of the executor still alive.
 	//
-	pub fn shutdown_timeout( mut self, duration: std::time::Duration ) -> Result<(), Self>
+	pub fn shutdown_timeout( mut self, duration: Duration ) -> Result<(), Self>
 	{
 		let arc = self.exec.take().unwrap();
 
@@ -165,6 +167,10 @@ impl Spawn for TokioTp
 
 
 
+impl SpawnMany for TokioTp {}
+
+
+
 impl<Out: 'static + Send> SpawnHandle<Out> for TokioTp
 {
 	fn spawn_handle_obj( &self, future: FutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>
@@ -176,3 +182,34 @@ impl<Out: 'static + Send> SpawnHandle<Out> for TokioTp
 		}})
 	}
 }
+
+
+
+impl<Out: 'static + Send> SpawnHandleGeneric<Out> for TokioTp
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		Ok( JoinHandle{ inner: InnerJh::Tokio
+		{
+			handle  : self.exec.as_ref().unwrap().spawn( future ) ,
+			detached: AtomicBool::new( false  ) ,
+		}})
+	}
+}
+
+
+
+impl Timer for TokioTp
+{
+	fn sleep( &self, dur: Duration ) -> BoxFuture<'static, ()>
+	{
+		// The sleep future registers with the time driver of the runtime in who's context it is created,
+		// so enter ours in case we are called from elsewhere.
+		//
+		let _guard = self.exec.as_ref().unwrap().enter();
+
+		Box::pin( tokio::time::sleep( dur ) )
+	}
+}
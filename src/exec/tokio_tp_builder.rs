@@ -2,9 +2,9 @@
 //
 use
 {
-	crate          :: { TokioTp   } ,
-	std            :: { sync::Arc } ,
-	tokio::runtime :: { Builder   } ,
+	crate          :: { TokioTp, DedicatedExec } ,
+	std            :: { sync::Arc              } ,
+	tokio::runtime :: { Builder                } ,
 };
 
 
@@ -12,6 +12,7 @@ use
 /// as tokio does not make this information available on it's `Runtime` type.
 ///
 /// Further allows you access to the tokio builder so you can set the other configuration options on it as you see fit.
+/// The time driver is enabled by default, as [`TokioTp`] implements [`Timer`](crate::Timer).
 //
 #[ derive(Debug) ]
 //
@@ -27,10 +28,13 @@ impl TokioTpBuilder
 	//
 	pub fn new() -> Self
 	{
-		Self
-		{
-			builder: Builder::new_multi_thread(),
-		}
+		let mut builder = Builder::new_multi_thread();
+
+		// Needed for the `Timer` impl.
+		//
+		builder.enable_time();
+
+		Self{ builder }
 	}
 
 	/// Returns the builder from tokio so you can configure it, see: [Builder].
@@ -56,6 +60,17 @@ impl TokioTpBuilder
 			exec: Some( Arc::new(exec) ),
 		})
 	}
+
+
+	/// Create a [`DedicatedExec`] for CPU heavy work. The runtime gets a thread of it's own, so it can be used and
+	/// dropped from within other executors.
+	///
+	/// Use [`tokio_builder`](TokioTpBuilder::tokio_builder) to set the number of worker threads and their names.
+	//
+	pub fn build_dedicated( &mut self ) -> Result<DedicatedExec, std::io::Error>
+	{
+		DedicatedExec::new( self.builder.build()? )
+	}
 }
 
 
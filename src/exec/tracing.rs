@@ -3,6 +3,9 @@ use
 	futures_util    :: { future::{ FutureExt }                     } ,
 	futures_task    :: { SpawnError, LocalFutureObj, FutureObj     } ,
 	crate           :: { JoinHandle, SpawnHandle, LocalSpawnHandle } ,
+	crate           :: { SpawnMany, LocalSpawnMany                 } ,
+	crate           :: { SpawnHandleGeneric, LocalSpawnHandleGeneric } ,
+	std             :: { future::Future                            } ,
 	tracing_futures :: { Instrument, Instrumented, WithDispatch    } ,
 };
 
@@ -16,6 +19,16 @@ impl<T, Out> SpawnHandle<Out> for Instrumented<T> where T: SpawnHandle<Out>, Out
 
 		self.inner().spawn_handle_obj( FutureObj::new(fut.boxed()) )
 	}
+
+
+	fn spawn_handle_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		let span = self.span();
+
+		self.inner().spawn_handle_obj_many( &mut futures.map( |f| FutureObj::new( f.instrument( span.clone() ).boxed() ) ) )
+	}
 }
 
 
@@ -28,6 +41,14 @@ impl<T, Out> SpawnHandle<Out> for WithDispatch<T> where T: SpawnHandle<Out>, Out
 
 		self.inner().spawn_handle_obj( FutureObj::new(fut.boxed()) )
 	}
+
+
+	fn spawn_handle_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		self.inner().spawn_handle_obj_many( &mut futures.map( |f| FutureObj::new( self.with_dispatch( f ).boxed() ) ) )
+	}
 }
 
 
@@ -40,6 +61,16 @@ impl<T, Out> LocalSpawnHandle<Out> for Instrumented<T> where T: LocalSpawnHandle
 
 		self.inner().spawn_handle_local_obj( LocalFutureObj::new(fut.boxed_local()) )
 	}
+
+
+	fn spawn_handle_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		let span = self.span();
+
+		self.inner().spawn_handle_local_obj_many( &mut futures.map( |f| LocalFutureObj::new( f.instrument( span.clone() ).boxed_local() ) ) )
+	}
 }
 
 
@@ -53,4 +84,104 @@ impl<T, Out> LocalSpawnHandle<Out> for WithDispatch<T> where T: LocalSpawnHandle
 
 		self.inner().spawn_handle_local_obj( LocalFutureObj::new(fut.boxed_local()) )
 	}
+
+
+	fn spawn_handle_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		self.inner().spawn_handle_local_obj_many( &mut futures.map( |f| LocalFutureObj::new( self.with_dispatch( f ).boxed_local() ) ) )
+	}
+}
+
+
+
+impl<T, Out> SpawnHandleGeneric<Out> for Instrumented<T> where T: SpawnHandleGeneric<Out>, Out: 'static + Send
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		self.inner().spawn_handle_generic( future.instrument( self.span().clone() ) )
+	}
+}
+
+
+
+impl<T, Out> SpawnHandleGeneric<Out> for WithDispatch<T> where T: SpawnHandleGeneric<Out>, Out: 'static + Send
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		self.inner().spawn_handle_generic( self.with_dispatch( future ) )
+	}
+}
+
+
+
+impl<T, Out> LocalSpawnHandleGeneric<Out> for Instrumented<T> where T: LocalSpawnHandleGeneric<Out>, Out: 'static
+{
+	fn spawn_handle_local_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + 'static
+	{
+		self.inner().spawn_handle_local_generic( future.instrument( self.span().clone() ) )
+	}
+}
+
+
+
+impl<T, Out> LocalSpawnHandleGeneric<Out> for WithDispatch<T> where T: LocalSpawnHandleGeneric<Out>, Out: 'static
+{
+	fn spawn_handle_local_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + 'static
+	{
+		self.inner().spawn_handle_local_generic( self.with_dispatch( future ) )
+	}
+}
+
+
+
+impl<T: SpawnMany> SpawnMany for Instrumented<T>
+{
+	fn spawn_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		let span = self.span();
+
+		self.inner().spawn_obj_many( &mut futures.map( |f| FutureObj::new( f.instrument( span.clone() ).boxed() ) ) )
+	}
+}
+
+
+
+impl<T: SpawnMany> SpawnMany for WithDispatch<T>
+{
+	fn spawn_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		self.inner().spawn_obj_many( &mut futures.map( |f| FutureObj::new( self.with_dispatch( f ).boxed() ) ) )
+	}
+}
+
+
+
+impl<T: LocalSpawnMany> LocalSpawnMany for Instrumented<T>
+{
+	fn spawn_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		let span = self.span();
+
+		self.inner().spawn_local_obj_many( &mut futures.map( |f| LocalFutureObj::new( f.instrument( span.clone() ).boxed_local() ) ) )
+	}
+}
+
+
+
+impl<T: LocalSpawnMany> LocalSpawnMany for WithDispatch<T>
+{
+	fn spawn_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		self.inner().spawn_local_obj_many( &mut futures.map( |f| LocalFutureObj::new( self.with_dispatch( f ).boxed_local() ) ) )
+	}
 }
@@ -0,0 +1,248 @@
+//! Provides WithHandle and WithLocalHandle, adaptors that give any executor implementing the futures spawn traits
+//! SpawnHandle and LocalSpawnHandle.
+//
+use
+{
+	crate        :: { SpawnHandle, LocalSpawnHandle, JoinHandle, Timer, iface::join_handle::InnerJh } ,
+	crate        :: { SpawnMany, LocalSpawnMany, SpawnHandleGeneric, LocalSpawnHandleGeneric        } ,
+	futures_task :: { FutureObj, LocalFutureObj, Spawn, LocalSpawn, SpawnError                      } ,
+	futures_util :: { future::{ BoxFuture, FutureExt }, task::{ SpawnExt, LocalSpawnExt }           } ,
+	std          :: { future::Future, time::{ Duration, Instant }                                   } ,
+};
+
+
+/// An adaptor that implements [`SpawnHandle`] for any executor that implements [`Spawn`], like executors from other
+/// crates or your own.
+///
+/// This uses [`RemoteHandle`](futures_util::future::RemoteHandle), so the [`JoinHandle`] behaves like the ones of
+/// the other executors: dropping it cancels the task and [`detach`](JoinHandle::detach) lets it run to completion.
+/// [`Spawn`] is forwarded to the wrapped executor, as are [`Timer`] and [`SpawnMany`] when it implements them.
+/// As the wrapped executor only needs to implement [`Spawn`], `spawn_handle_obj_many` spawns one by one.
+///
+/// ```
+/// use
+/// {
+///    async_executors :: { WithHandle, SpawnHandleExt  } ,
+///    futures         :: { executor::{ block_on, ThreadPool } } ,
+/// };
+///
+/// // Any type that implements futures::task::Spawn.
+/// //
+/// let exec   = WithHandle::new( ThreadPool::new().expect( "create threadpool" ) );
+/// let handle = exec.spawn_handle( async { 5u8 } ).expect( "spawn" );
+///
+/// assert_eq!( block_on( handle ), 5 );
+/// ```
+//
+#[ derive( Debug, Clone, Default ) ]
+//
+pub struct WithHandle<E>
+{
+	exec: E,
+}
+
+
+impl<E: Spawn> WithHandle<E>
+{
+	/// Wrap `exec`.
+	//
+	pub fn new( exec: E ) -> Self
+	{
+		Self { exec }
+	}
+
+
+	/// Access the wrapped executor.
+	//
+	pub fn inner( &self ) -> &E
+	{
+		&self.exec
+	}
+
+
+	/// Unwrap the executor.
+	//
+	pub fn into_inner( self ) -> E
+	{
+		self.exec
+	}
+}
+
+
+
+impl<E: Spawn> Spawn for WithHandle<E>
+{
+	fn spawn_obj( &self, future: FutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		self.exec.spawn_obj( future )
+	}
+}
+
+
+
+impl<E: SpawnMany> SpawnMany for WithHandle<E>
+{
+	fn spawn_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		self.exec.spawn_obj_many( futures )
+	}
+}
+
+
+
+impl<E: Spawn, Out: 'static + Send> SpawnHandle<Out> for WithHandle<E>
+{
+	fn spawn_handle_obj( &self, future: FutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>
+	{
+		let (fut, handle) = future.remote_handle();
+
+		self.exec.spawn( fut )?;
+
+		Ok( JoinHandle{ inner: InnerJh::RemoteHandle( Some(handle) ) } )
+	}
+}
+
+
+
+impl<E: Spawn, Out: 'static + Send> SpawnHandleGeneric<Out> for WithHandle<E>
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		let (fut, handle) = future.remote_handle();
+
+		self.exec.spawn( fut )?;
+
+		Ok( JoinHandle{ inner: InnerJh::RemoteHandle( Some(handle) ) } )
+	}
+}
+
+
+
+impl<E: Timer> Timer for WithHandle<E>
+{
+	fn sleep      ( &self, dur     : Duration ) -> BoxFuture<'static, ()> { self.exec.sleep( dur )            }
+	fn now        ( &self                     ) -> Instant                { self.exec.now()                   }
+	fn sleep_until( &self, deadline: Instant  ) -> BoxFuture<'static, ()> { self.exec.sleep_until( deadline ) }
+}
+
+
+
+/// An adaptor that implements [`LocalSpawnHandle`] for any executor that implements [`LocalSpawn`].
+///
+/// This is the `!Send` counterpart of [`WithHandle`]. [`LocalSpawn`] and [`Spawn`] are forwarded to the wrapped
+/// executor, as are [`Timer`], [`SpawnMany`] and [`LocalSpawnMany`] when it implements them.
+//
+#[ derive( Debug, Clone, Default ) ]
+//
+pub struct WithLocalHandle<E>
+{
+	exec: E,
+}
+
+
+impl<E: LocalSpawn> WithLocalHandle<E>
+{
+	/// Wrap `exec`.
+	//
+	pub fn new( exec: E ) -> Self
+	{
+		Self { exec }
+	}
+
+
+	/// Access the wrapped executor.
+	//
+	pub fn inner( &self ) -> &E
+	{
+		&self.exec
+	}
+
+
+	/// Unwrap the executor.
+	//
+	pub fn into_inner( self ) -> E
+	{
+		self.exec
+	}
+}
+
+
+
+impl<E: Spawn> Spawn for WithLocalHandle<E>
+{
+	fn spawn_obj( &self, future: FutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		self.exec.spawn_obj( future )
+	}
+}
+
+
+
+impl<E: SpawnMany> SpawnMany for WithLocalHandle<E>
+{
+	fn spawn_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		self.exec.spawn_obj_many( futures )
+	}
+}
+
+
+
+impl<E: LocalSpawn> LocalSpawn for WithLocalHandle<E>
+{
+	fn spawn_local_obj( &self, future: LocalFutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		self.exec.spawn_local_obj( future )
+	}
+}
+
+
+
+impl<E: LocalSpawnMany> LocalSpawnMany for WithLocalHandle<E>
+{
+	fn spawn_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		self.exec.spawn_local_obj_many( futures )
+	}
+}
+
+
+
+impl<E: LocalSpawn, Out: 'static> LocalSpawnHandle<Out> for WithLocalHandle<E>
+{
+	fn spawn_handle_local_obj( &self, future: LocalFutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>
+	{
+		let (fut, handle) = future.remote_handle();
+
+		self.exec.spawn_local( fut )?;
+
+		Ok( JoinHandle{ inner: InnerJh::RemoteHandle( Some(handle) ) } )
+	}
+}
+
+
+
+impl<E: LocalSpawn, Out: 'static> LocalSpawnHandleGeneric<Out> for WithLocalHandle<E>
+{
+	fn spawn_handle_local_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + 'static
+	{
+		let (fut, handle) = future.remote_handle();
+
+		self.exec.spawn_local( fut )?;
+
+		Ok( JoinHandle{ inner: InnerJh::RemoteHandle( Some(handle) ) } )
+	}
+}
+
+
+
+impl<E: Timer> Timer for WithLocalHandle<E>
+{
+	fn sleep      ( &self, dur     : Duration ) -> BoxFuture<'static, ()> { self.exec.sleep( dur )            }
+	fn now        ( &self                     ) -> Instant                { self.exec.now()                   }
+	fn sleep_until( &self, deadline: Instant  ) -> BoxFuture<'static, ()> { self.exec.sleep_until( deadline ) }
+}
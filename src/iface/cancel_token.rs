@@ -0,0 +1,305 @@
+use
+{
+	futures_util :: { future::{ select, Either }                                                 } ,
+	std          :: { error::Error, fmt, future::Future, pin::Pin, collections::HashMap          } ,
+	std          :: { sync::{ Arc, Weak, Mutex, MutexGuard }, task::{ Context, Poll, Waker }     } ,
+};
+
+
+/// A token to cancel a tree of tasks cooperatively.
+///
+/// Tokens form a tree: [`child`](CancelToken::child) creates a token that is cancelled when it's parent is,
+/// but that can also be cancelled on it's own without affecting the parent. Clones refer to the same token.
+///
+/// Tasks can wait for [`cancelled`](CancelToken::cancelled) to clean up gracefully. To make sure that a task
+/// stops, attach a token when spawning it with
+/// [`spawn_handle_with_token`](crate::SpawnHandleExt::spawn_handle_with_token) or
+/// [`spawn_handle_local_with_token`](crate::LocalSpawnHandleExt::spawn_handle_local_with_token). When the token
+/// is cancelled, the task gets a grace period to finish, after which it is aborted. The grace period is a future you
+/// pass in, eg. a sleep from the [`Timer`](crate::Timer) of your executor. This extends the cancel on drop
+/// behavior of [`JoinHandle`](crate::JoinHandle) to cooperative, tree shaped cancellation.
+///
+/// ```
+/// use
+/// {
+///    async_executors :: { CancelToken, SpawnHandleExt, ThreadPool, Timer } ,
+///    futures         :: { executor::block_on                            } ,
+///    std             :: { time::Duration                                } ,
+/// };
+///
+/// let exec   = ThreadPool::new().expect( "create threadpool" );
+/// let timer  = exec.clone();
+/// let parent = CancelToken::new();
+/// let child  = parent.child();
+/// let token  = child.clone();
+///
+/// let handle = exec.spawn_handle_with_token( async move
+/// {
+///    token.cancelled().await;
+///
+///    // Clean up here...
+///
+///    "cleaned up"
+///
+/// }, &child, async move { timer.sleep( Duration::from_secs(1) ).await } ).expect( "spawn" );
+///
+/// parent.cancel();
+///
+/// assert!( child.is_cancelled() );
+/// assert_eq!( block_on( handle ), Ok( "cleaned up" ) );
+/// ```
+//
+#[ derive( Clone ) ]
+//
+pub struct CancelToken
+{
+	node: Arc<Node>,
+}
+
+
+struct Node
+{
+	state: Mutex<State>,
+}
+
+
+struct State
+{
+	cancelled: bool                  ,
+	next_id  : u64                   ,
+	wakers   : HashMap<u64, Waker>   ,
+	children : Vec< Weak<Node> >     ,
+}
+
+
+impl Node
+{
+	fn new( cancelled: bool ) -> Self
+	{
+		let state = State { cancelled, next_id: 0, wakers: HashMap::new(), children: Vec::new() };
+
+		Self { state: Mutex::new( state ) }
+	}
+
+
+	fn lock( &self ) -> MutexGuard<'_, State>
+	{
+		// A panic while holding the lock cannot leave the token in an inconsistent state.
+		//
+		self.state.lock().unwrap_or_else( |e| e.into_inner() )
+	}
+
+
+	fn cancel( &self )
+	{
+		let mut state = self.lock();
+
+		if state.cancelled { return; }
+
+		state.cancelled = true;
+
+		let wakers   = std::mem::take( &mut state.wakers   );
+		let children = std::mem::take( &mut state.children );
+
+		drop( state );
+
+		wakers.into_values().for_each( Waker::wake );
+
+		for child in children.iter().filter_map( Weak::upgrade )
+		{
+			child.cancel();
+		}
+	}
+}
+
+
+
+impl CancelToken
+{
+	/// Create a new token that is not cancelled and has no parent.
+	//
+	pub fn new() -> Self
+	{
+		Self { node: Arc::new( Node::new( false ) ) }
+	}
+
+
+	/// Create a token that gets cancelled when this one is. Cancelling the child does not affect the parent.
+	/// If this token is already cancelled, so is the child.
+	//
+	pub fn child( &self ) -> Self
+	{
+		let mut state = self.node.lock();
+		let child     = Arc::new( Node::new( state.cancelled ) );
+
+		if !state.cancelled
+		{
+			// Don't accumulate children that are gone.
+			//
+			state.children.retain( |c| c.strong_count() > 0 );
+			state.children.push( Arc::downgrade( &child ) );
+		}
+
+		Self { node: child }
+	}
+
+
+	/// Cancel this token and all of it's descendants. Does nothing if it is already cancelled.
+	//
+	pub fn cancel( &self )
+	{
+		self.node.cancel();
+	}
+
+
+	/// Whether this token has been cancelled, either directly or through one of it's ancestors.
+	//
+	pub fn is_cancelled( &self ) -> bool
+	{
+		self.node.lock().cancelled
+	}
+
+
+	/// A future that resolves once this token is cancelled.
+	//
+	pub fn cancelled( &self ) -> OnCancel
+	{
+		OnCancel { node: self.node.clone(), id: None }
+	}
+}
+
+
+impl Default for CancelToken
+{
+	fn default() -> Self
+	{
+		Self::new()
+	}
+}
+
+
+impl fmt::Debug for CancelToken
+{
+	fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result
+	{
+		f.debug_struct( "CancelToken" )
+
+			.field( "cancelled", &self.is_cancelled() )
+			.finish()
+	}
+}
+
+
+
+/// The future returned by [`CancelToken::cancelled`]. Resolves once the token is cancelled.
+//
+#[ must_use = "futures do nothing unless you `.await` or poll them" ]
+//
+pub struct OnCancel
+{
+	node: Arc<Node>  ,
+	id  : Option<u64>,
+}
+
+
+impl Future for OnCancel
+{
+	type Output = ();
+
+	fn poll( mut self: Pin<&mut Self>, cx: &mut Context<'_> ) -> Poll<()>
+	{
+		let mut state = self.node.lock();
+
+		if state.cancelled
+		{
+			drop( state );
+			self.id = None;
+
+			return Poll::Ready(());
+		}
+
+		let id = match self.id
+		{
+			Some( id ) => id,
+
+			None =>
+			{
+				state.next_id += 1;
+				state.next_id
+			}
+		};
+
+		state.wakers.insert( id, cx.waker().clone() );
+
+		drop( state );
+		self.id = Some( id );
+
+		Poll::Pending
+	}
+}
+
+
+impl Drop for OnCancel
+{
+	fn drop( &mut self )
+	{
+		if let Some( id ) = self.id
+		{
+			self.node.lock().wakers.remove( &id );
+		}
+	}
+}
+
+
+impl fmt::Debug for OnCancel
+{
+	fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result
+	{
+		f.debug_struct( "OnCancel" ).finish()
+	}
+}
+
+
+
+/// The outcome of a task that was aborted because it's [`CancelToken`] was cancelled and it did not finish within
+/// the grace period.
+//
+#[ derive( Debug, Clone, Copy, PartialEq, Eq, Hash ) ]
+//
+pub struct Cancelled;
+
+
+impl fmt::Display for Cancelled
+{
+	fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result
+	{
+		f.write_str( "the task was aborted because it's cancel token was cancelled" )
+	}
+}
+
+
+impl Error for Cancelled {}
+
+
+
+// Run `future` until it finishes or `token` is cancelled. In the latter case, let it run until `grace` resolves
+// before dropping it.
+//
+pub(crate) async fn with_token<F, G>( mut future: F, token: CancelToken, grace: G )
+
+	-> Result<F::Output, Cancelled>
+
+	where F: Future + Unpin              ,
+	      G: Future<Output = ()> + Unpin ,
+{
+	if let Either::Left( (out, _) ) = select( &mut future, token.cancelled() ).await
+	{
+		return Ok( out );
+	}
+
+	match select( future, grace ).await
+	{
+		Either::Left ( (out, _) ) => Ok ( out       ),
+		Either::Right( _        ) => Err( Cancelled ),
+	}
+}
@@ -0,0 +1,22 @@
+use std::{ error::Error, fmt };
+
+
+/// The outcome of a task that was cancelled because it did not finish before it's deadline. See
+/// [`SpawnHandleExt::spawn_handle_with_deadline`](crate::SpawnHandleExt::spawn_handle_with_deadline) and
+/// [`Deadlined`](crate::Deadlined).
+//
+#[ derive( Debug, Clone, Copy, PartialEq, Eq, Hash ) ]
+//
+pub struct DeadlineExceeded;
+
+
+impl fmt::Display for DeadlineExceeded
+{
+	fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result
+	{
+		f.write_str( "the task was cancelled because it's deadline passed" )
+	}
+}
+
+
+impl Error for DeadlineExceeded {}
@@ -4,7 +4,7 @@ use
 {
 	std         :: { future::Future, sync::atomic::{ AtomicBool, Ordering } } ,
 	std         :: { task::{ Poll, Context }, pin::Pin                      } ,
-	futures_util:: { future::{ AbortHandle, Aborted, RemoteHandle }, ready  } ,
+	futures_util:: { future::{ FutureExt, RemoteHandle }, ready             } ,
 };
 
 
@@ -12,7 +12,7 @@ use
 //
 use async_global_executor::{ Task as AsyncGlobalTask };
 
-#[ cfg( feature = "async_std" ) ]
+#[ cfg(all( feature = "async_std", not(target_arch = "wasm32") )) ]
 //
 use async_std_crate::{ task::JoinHandle as AsyncStdJoinHandle };
 
@@ -20,6 +20,10 @@ use async_std_crate::{ task::JoinHandle as AsyncStdJoinHandle };
 //
 use tokio::{ task::JoinHandle as TokioJoinHandle };
 
+#[ cfg( feature = "native" ) ]
+//
+use crate::exec::NativeJoinHandle;
+
 
 /// A framework agnostic JoinHandle type. Cancels the future on dropping the handle.
 /// You can call [`detach`](JoinHandle::detach) to leave the future running when dropping the handle.
@@ -27,8 +31,12 @@ use tokio::{ task::JoinHandle as TokioJoinHandle };
 /// This leverages the performance gains from the native join handles compared to
 /// [RemoteHandle](futures_util::future::RemoteHandle) where possible.
 ///
-/// It does wrap futures in [Abortable](futures_util::future::Abortable) where needed as
-/// [tokio] and [async-std](async_std_crate) don't support canceling out of the box.
+/// On Wasm, async-std can't cancel tasks, so there it goes through a `RemoteHandle` as well.
+///
+/// For glommio, the handle is a `RemoteHandle`. Glommio's own task handles are bound to the thread of
+/// the executor, so storing them in here would make `JoinHandle` `!Send` for all executors as soon as the
+/// `glommio` feature is enabled. If you don't need `Send`, `GlommioCt::spawn_handle_native` returns a
+/// `GlommioJoinHandle` which wraps the native task. The `glommio` benchmark compares both.
 ///
 /// # Panics
 ///
@@ -70,17 +78,21 @@ pub(crate) enum InnerJh<T>
 		task: Option< AsyncGlobalTask<T> > ,
 	},
 
-	/// Wrapper around AsyncStd JoinHandle.
+	/// Wrapper around AsyncStd JoinHandle. It is only taken out on detach or drop.
 	//
-	#[ cfg( feature = "async_std" ) ]
+	#[ cfg(all( feature = "async_std", not(target_arch = "wasm32") )) ]
 	//
 	AsyncStd
 	{
-		handle  : AsyncStdJoinHandle<Result<T, Aborted>> ,
-		a_handle: AbortHandle                            ,
-		detached: AtomicBool                             ,
+		handle: Option< AsyncStdJoinHandle<T> > ,
 	},
 
+	/// Handle of a task on the Native executor.
+	//
+	#[ cfg( feature = "native" ) ]
+	//
+	Native( NativeJoinHandle<T> ),
+
 	/// Wrapper around futures RemoteHandle.
 	//
 	RemoteHandle( Option<RemoteHandle<T>> ),
@@ -115,11 +127,15 @@ impl<T> JoinHandle<T>
 				task.unwrap().detach();
 			}
 
-			#[ cfg( feature = "async_std" ) ] InnerJh::AsyncStd{ ref detached, .. } =>
+			// Dropping an async-std JoinHandle detaches the task.
+			//
+			#[ cfg(all( feature = "async_std", not(target_arch = "wasm32") )) ] InnerJh::AsyncStd{ handle } =>
 			{
-				detached.store( true, Ordering::Relaxed );
+				drop( handle.take() );
 			}
 
+			#[ cfg( feature = "native" ) ] InnerJh::Native( handle ) => handle.detach(),
+
 			InnerJh::RemoteHandle( handle ) =>
 			{
 				if let Some(rh) = handle.take() { rh.forget() };
@@ -154,13 +170,9 @@ impl<T: 'static> Future for JoinHandle<T>
 			}
 
 
-			#[ cfg( feature = "async_std" ) ] InnerJh::AsyncStd{ handle, .. } =>
+			#[ cfg(all( feature = "async_std", not(target_arch = "wasm32") )) ] InnerJh::AsyncStd{ handle } =>
 			{
-				match ready!( Pin::new( handle ).poll( cx ) )
-				{
-					Ok (t) => Poll::Ready( t ),
-					Err(_) => unreachable!(),
-				}
+				Pin::new( handle.as_mut().expect( "no polling after detach" ) ).poll( cx )
 			}
 
 
@@ -170,6 +182,9 @@ impl<T: 'static> Future for JoinHandle<T>
 			}
 
 
+			#[ cfg( feature = "native" ) ] InnerJh::Native( handle ) => Pin::new( handle ).poll( cx ),
+
+
 			InnerJh::RemoteHandle( ref mut handle ) => Pin::new( handle ).as_pin_mut().expect( "no polling after detach" ).poll( cx ),
 		}
 	}
@@ -192,9 +207,12 @@ impl<T> Drop for JoinHandle<T>
 				if !detached.load( Ordering::Relaxed ) { handle.abort() },
 
 
-			#[ cfg( feature = "async_std" ) ] InnerJh::AsyncStd { a_handle, detached, .. } =>
+			// Cancelling is async in async-std, but it marks the task as canceled the first time it's polled.
+			// Dropping the future after that doesn't undo it.
+			//
+			#[ cfg(all( feature = "async_std", not(target_arch = "wasm32") )) ] InnerJh::AsyncStd { handle } =>
 
-				if !detached.load( Ordering::Relaxed ) { a_handle.abort() },
+				if let Some( handle ) = handle.take() { let _ = handle.cancel().now_or_never(); },
 
 
 			// Nothing needs to be done, just drop it.
@@ -202,6 +220,11 @@ impl<T> Drop for JoinHandle<T>
 			#[ cfg( feature = "async_global" ) ] InnerJh::AsyncGlobal { .. } => {}
 
 
+			// It cancels the task when dropped, unless it's detached.
+			//
+			#[ cfg( feature = "native" ) ] InnerJh::Native( _ ) => {}
+
+
 			InnerJh::RemoteHandle( _ ) => {},
 		};
 	}
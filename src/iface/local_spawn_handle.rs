@@ -3,9 +3,10 @@
 use
 {
 	futures_task :: { SpawnError, LocalFutureObj                                          } ,
-	futures_util :: { task::{ LocalSpawnExt }, future::{ FutureExt, abortable }           } ,
-	crate        :: { JoinHandle                                                          } ,
+	futures_util :: { task::{ LocalSpawnExt }, future::{ FutureExt, abortable, select, Either } } ,
+	crate        :: { JoinHandle, Timer, DeadlineExceeded, CancelToken, Cancelled         } ,
 	std          :: { pin::Pin, future::Future, sync::{ Arc, atomic::AtomicBool }, rc::Rc } ,
+	std          :: { time::Instant                                                       } ,
 };
 
 
@@ -17,6 +18,24 @@ pub trait LocalSpawnHandle<Out: 'static>
 	/// Spawn a future and return a [`JoinHandle`] that can be awaited for the output of the future.
 	//
 	fn spawn_handle_local_obj( &self, future: LocalFutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>;
+
+
+	/// Spawn several futures and return their [`JoinHandle`]s in the same order. See
+	/// [`SpawnHandle::spawn_handle_obj_many`](crate::SpawnHandle::spawn_handle_obj_many).
+	//
+	fn spawn_handle_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		let mut handles = Vec::with_capacity( futures.size_hint().0 );
+
+		for future in futures
+		{
+			handles.push( self.spawn_handle_local_obj( future )? );
+		}
+
+		Ok( handles )
+	}
 }
 
 
@@ -28,6 +47,42 @@ pub trait LocalSpawnHandleExt<Out: 'static> : LocalSpawnHandle<Out>
 	/// futures library.
 	//
 	fn spawn_handle_local( &self, future: impl Future<Output = Out> + 'static ) -> Result<JoinHandle<Out>, SpawnError>;
+
+
+	/// Spawn several `!Send` futures and return their [JoinHandle]s in the same order. See
+	/// [`SpawnHandle::spawn_handle_obj_many`](crate::SpawnHandle::spawn_handle_obj_many).
+	//
+	fn spawn_handle_local_many<Fut>( &self, futures: impl IntoIterator<Item = Fut> ) -> Result<Vec< JoinHandle<Out> >, SpawnError>
+
+		where Fut: Future<Output = Out> + 'static
+	;
+
+
+	/// Spawn a `!Send` future that gets cancelled when it hasn't finished by `deadline`. See
+	/// [`SpawnHandleExt::spawn_handle_with_deadline`](crate::SpawnHandleExt::spawn_handle_with_deadline).
+	//
+	fn spawn_handle_local_with_deadline( &self, future: impl Future<Output = Out> + 'static, deadline: Instant )
+
+		-> Result<JoinHandle< Result<Out, DeadlineExceeded> >, SpawnError>
+
+		where Self: Timer + LocalSpawnHandle< Result<Out, DeadlineExceeded> >
+	;
+
+
+	/// Spawn a `!Send` future that gets aborted when `token` is cancelled, after a grace period. See
+	/// [`SpawnHandleExt::spawn_handle_with_token`](crate::SpawnHandleExt::spawn_handle_with_token).
+	//
+	fn spawn_handle_local_with_token
+	(
+		&self                                         ,
+		future: impl Future<Output = Out> + 'static  ,
+		token : &CancelToken                          ,
+		grace : impl Future<Output = ()>  + 'static  ,
+	)
+		-> Result<JoinHandle< Result<Out, Cancelled> >, SpawnError>
+
+		where Self: LocalSpawnHandle< Result<Out, Cancelled> >
+	;
 }
 
 
@@ -40,6 +95,52 @@ impl<T, Out> LocalSpawnHandleExt<Out> for T
 	{
 		self.spawn_handle_local_obj( LocalFutureObj::new(future.boxed_local()) )
 	}
+
+
+	fn spawn_handle_local_many<Fut>( &self, futures: impl IntoIterator<Item = Fut> ) -> Result<Vec< JoinHandle<Out> >, SpawnError>
+
+		where Fut: Future<Output = Out> + 'static
+	{
+		self.spawn_handle_local_obj_many( &mut futures.into_iter().map( |f| LocalFutureObj::new(f.boxed_local()) ) )
+	}
+
+
+	fn spawn_handle_local_with_deadline( &self, future: impl Future<Output = Out> + 'static, deadline: Instant )
+
+		-> Result<JoinHandle< Result<Out, DeadlineExceeded> >, SpawnError>
+
+		where Self: Timer + LocalSpawnHandle< Result<Out, DeadlineExceeded> >
+	{
+		let sleep = self.sleep_until( deadline );
+
+		let fut = async move
+		{
+			match select( future.boxed_local(), sleep ).await
+			{
+				Either::Left ( (out, _) ) => Ok ( out              ),
+				Either::Right( _        ) => Err( DeadlineExceeded ),
+			}
+		};
+
+		self.spawn_handle_local_obj( LocalFutureObj::new(fut.boxed_local()) )
+	}
+
+
+	fn spawn_handle_local_with_token
+	(
+		&self                                         ,
+		future: impl Future<Output = Out> + 'static  ,
+		token : &CancelToken                          ,
+		grace : impl Future<Output = ()>  + 'static  ,
+	)
+		-> Result<JoinHandle< Result<Out, Cancelled> >, SpawnError>
+
+		where Self: LocalSpawnHandle< Result<Out, Cancelled> >
+	{
+		let fut = super::cancel_token::with_token( future.boxed_local(), token.clone(), grace.boxed_local() );
+
+		self.spawn_handle_local_obj( LocalFutureObj::new(fut.boxed_local()) )
+	}
 }
 
 
@@ -49,6 +150,14 @@ impl<T: ?Sized, Out> LocalSpawnHandle<Out> for Box<T> where T: LocalSpawnHandle<
 	{
 		(**self).spawn_handle_local_obj( future )
 	}
+
+
+	fn spawn_handle_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		(**self).spawn_handle_local_obj_many( futures )
+	}
 }
 
 
@@ -59,6 +168,14 @@ impl<T: ?Sized, Out> LocalSpawnHandle<Out> for Arc<T> where T: LocalSpawnHandle<
 	{
 		(**self).spawn_handle_local_obj( future )
 	}
+
+
+	fn spawn_handle_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		(**self).spawn_handle_local_obj_many( futures )
+	}
 }
 
 
@@ -69,6 +186,14 @@ impl<T: ?Sized, Out> LocalSpawnHandle<Out> for Rc<T> where T: LocalSpawnHandle<O
 	{
 		(**self).spawn_handle_local_obj( future )
 	}
+
+
+	fn spawn_handle_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		(**self).spawn_handle_local_obj_many( futures )
+	}
 }
 
 
@@ -79,6 +204,14 @@ impl<T, Out> LocalSpawnHandle<Out> for &T where T: LocalSpawnHandle<Out>, Out: '
 	{
 		(**self).spawn_handle_local_obj( future )
 	}
+
+
+	fn spawn_handle_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		(**self).spawn_handle_local_obj_many( futures )
+	}
 }
 
 
@@ -89,6 +222,14 @@ impl<T, Out> LocalSpawnHandle<Out> for &mut T where T: LocalSpawnHandle<Out>, Ou
 	{
 		(**self).spawn_handle_local_obj( future )
 	}
+
+
+	fn spawn_handle_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		(**self).spawn_handle_local_obj_many( futures )
+	}
 }
 
 
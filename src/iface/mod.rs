@@ -1,7 +1,21 @@
-pub(crate) mod spawn_handle       ;
-pub(crate) mod local_spawn_handle ;
-pub(crate) mod join_handle        ;
+pub(crate) mod spawn_handle        ;
+pub(crate) mod spawn_handle_generic;
+pub(crate) mod spawn_many          ;
+pub(crate) mod local_spawn_handle  ;
+pub(crate) mod join_handle         ;
+pub(crate) mod timer               ;
+pub(crate) mod deadline            ;
+pub(crate) mod cancel_token        ;
+pub(crate) mod task_local          ;
+pub(crate) mod schedule            ;
 
-pub use spawn_handle       ::*;
-pub use local_spawn_handle ::*;
-pub use join_handle        ::*;
+pub use spawn_handle        ::*;
+pub use spawn_handle_generic::*;
+pub use spawn_many          ::*;
+pub use local_spawn_handle  ::*;
+pub use join_handle         ::*;
+pub use timer               ::*;
+pub use deadline            ::*;
+pub use cancel_token        ::*;
+pub use task_local          ::{ LocalKey, Scope, AccessError };
+pub use schedule            ::*;
@@ -0,0 +1,326 @@
+#[ allow(unused_imports) ]
+//
+use
+{
+	crate        :: { SpawnHandle, SpawnHandleExt, LocalSpawnHandle, LocalSpawnHandleExt, JoinHandle, Timer } ,
+	futures_task :: { SpawnError                                                                             } ,
+	std          :: { future::Future, pin::Pin, task::{ Context, Poll }, time::{ Duration, Instant }         } ,
+};
+
+
+/// What [`spawn_periodic`](ScheduleExt::spawn_periodic) does when a run takes so long that the next one should
+/// already have started. Runs of a periodic task never overlap.
+///
+/// On `wasm32`, where [`Instant::now`] is not available, runs are always `interval` apart from the end of the
+/// previous one, whatever the policy.
+//
+#[ derive( Debug, Default, Clone, Copy, PartialEq, Eq, Hash ) ]
+//
+pub enum MissedTick
+{
+	/// Start the missed runs right away, one after the other, until the schedule has caught up. This is the default.
+	//
+	#[ default ]
+	//
+	Burst,
+
+	/// Start the next run right away and shift the schedule, so that the following runs are `interval` apart
+	/// from this one.
+	//
+	Delay,
+
+	/// Skip the missed runs and wait for the next tick on the original schedule.
+	//
+	Skip,
+}
+
+
+/// Spawn tasks that start later or repeatedly, using the [`Timer`] of the executor. This is implemented for every
+/// type that implements [`Timer`] and the methods are available when it also implements [`SpawnHandle`] or
+/// [`LocalSpawnHandle`].
+///
+/// The returned [`JoinHandle`] cancels the task, including any runs that haven't started yet, when dropped.
+/// Call [`detach`](JoinHandle::detach) on it to let the task run on it's own.
+///
+/// On `wasm32`, [`Instant::now`] is not available, so there is no `spawn_at` or `spawn_at_local`.
+///
+/// ```
+/// use
+/// {
+///    async_executors :: { ScheduleExt, MissedTick, ThreadPool } ,
+///    futures         :: { executor::block_on, channel::mpsc, StreamExt } ,
+///    std             :: { time::Duration                   } ,
+/// };
+///
+/// let exec     = ThreadPool::new().expect( "create threadpool" );
+/// let later    = exec.spawn_after( Duration::from_millis(5), async { 5u8 } ).expect( "spawn" );
+/// let (tx, rx) = mpsc::unbounded();
+///
+/// let ticks = exec.spawn_periodic( Duration::from_millis(1), MissedTick::Skip, move ||
+/// {
+///    let tx = tx.clone();
+///    async move { let _ = tx.unbounded_send(()); }
+///
+/// }).expect( "spawn" );
+///
+/// assert_eq!( block_on( later ), 5 );
+/// assert_eq!( block_on( rx.take(3).count() ), 3 );
+///
+/// // Stops the periodic task.
+/// //
+/// drop( ticks );
+/// ```
+//
+pub trait ScheduleExt : Timer
+{
+	/// Spawn a future that starts after `delay`.
+	//
+	fn spawn_after<Out>( &self, delay: Duration, future: impl Future<Output = Out> + Send + 'static )
+
+		-> Result<JoinHandle<Out>, SpawnError>
+
+		where Self: SpawnHandle<Out>, Out: 'static + Send
+	;
+
+
+	/// Spawn a future that starts at `at`, as measured by [`Timer::now`].
+	//
+	#[ cfg(not( target_arch = "wasm32" )) ]
+	//
+	fn spawn_at<Out>( &self, at: Instant, future: impl Future<Output = Out> + Send + 'static )
+
+		-> Result<JoinHandle<Out>, SpawnError>
+
+		where Self: SpawnHandle<Out>, Out: 'static + Send
+	;
+
+
+	/// Run the future created by `task` right away and then every `interval`. When a run takes longer than
+	/// `interval`, `missed` decides when the next one starts. The executor is cloned into the task for it's timer.
+	///
+	/// # Panics
+	///
+	/// When `interval` is zero.
+	//
+	fn spawn_periodic<F, Fut>( &self, interval: Duration, missed: MissedTick, task: F )
+
+		-> Result<JoinHandle<()>, SpawnError>
+
+		where Self: SpawnHandle<()> + Clone + Send + 'static           ,
+		      F   : FnMut() -> Fut + Send + 'static                    ,
+		      Fut : Future<Output = ()> + Send + 'static               ,
+	;
+
+
+	/// Spawn a `!Send` future that starts after `delay`.
+	//
+	fn spawn_after_local<Out>( &self, delay: Duration, future: impl Future<Output = Out> + 'static )
+
+		-> Result<JoinHandle<Out>, SpawnError>
+
+		where Self: LocalSpawnHandle<Out>, Out: 'static
+	;
+
+
+	/// Spawn a `!Send` future that starts at `at`, as measured by [`Timer::now`].
+	//
+	#[ cfg(not( target_arch = "wasm32" )) ]
+	//
+	fn spawn_at_local<Out>( &self, at: Instant, future: impl Future<Output = Out> + 'static )
+
+		-> Result<JoinHandle<Out>, SpawnError>
+
+		where Self: LocalSpawnHandle<Out>, Out: 'static
+	;
+
+
+	/// Like [`spawn_periodic`](ScheduleExt::spawn_periodic) for `!Send` tasks.
+	///
+	/// # Panics
+	///
+	/// When `interval` is zero.
+	//
+	fn spawn_periodic_local<F, Fut>( &self, interval: Duration, missed: MissedTick, task: F )
+
+		-> Result<JoinHandle<()>, SpawnError>
+
+		where Self: LocalSpawnHandle<()> + Clone + 'static ,
+		      F   : FnMut() -> Fut + 'static               ,
+		      Fut : Future<Output = ()> + 'static          ,
+	;
+}
+
+
+
+impl<T: Timer + ?Sized> ScheduleExt for T
+{
+	fn spawn_after<Out>( &self, delay: Duration, future: impl Future<Output = Out> + Send + 'static )
+
+		-> Result<JoinHandle<Out>, SpawnError>
+
+		where Self: SpawnHandle<Out>, Out: 'static + Send
+	{
+		let sleep = self.sleep( delay );
+
+		self.spawn_handle( async move { sleep.await; future.await } )
+	}
+
+
+	#[ cfg(not( target_arch = "wasm32" )) ]
+	//
+	fn spawn_at<Out>( &self, at: Instant, future: impl Future<Output = Out> + Send + 'static )
+
+		-> Result<JoinHandle<Out>, SpawnError>
+
+		where Self: SpawnHandle<Out>, Out: 'static + Send
+	{
+		let sleep = self.sleep_until( at );
+
+		self.spawn_handle( async move { sleep.await; future.await } )
+	}
+
+
+	fn spawn_periodic<F, Fut>( &self, interval: Duration, missed: MissedTick, task: F )
+
+		-> Result<JoinHandle<()>, SpawnError>
+
+		where Self: SpawnHandle<()> + Clone + Send + 'static           ,
+		      F   : FnMut() -> Fut + Send + 'static                    ,
+		      Fut : Future<Output = ()> + Send + 'static               ,
+	{
+		assert!( interval > Duration::ZERO, "spawn_periodic: the interval must not be zero" );
+
+		self.spawn_handle( periodic( self.clone(), interval, missed, task ) )
+	}
+
+
+	fn spawn_after_local<Out>( &self, delay: Duration, future: impl Future<Output = Out> + 'static )
+
+		-> Result<JoinHandle<Out>, SpawnError>
+
+		where Self: LocalSpawnHandle<Out>, Out: 'static
+	{
+		let sleep = self.sleep( delay );
+
+		self.spawn_handle_local( async move { sleep.await; future.await } )
+	}
+
+
+	#[ cfg(not( target_arch = "wasm32" )) ]
+	//
+	fn spawn_at_local<Out>( &self, at: Instant, future: impl Future<Output = Out> + 'static )
+
+		-> Result<JoinHandle<Out>, SpawnError>
+
+		where Self: LocalSpawnHandle<Out>, Out: 'static
+	{
+		let sleep = self.sleep_until( at );
+
+		self.spawn_handle_local( async move { sleep.await; future.await } )
+	}
+
+
+	fn spawn_periodic_local<F, Fut>( &self, interval: Duration, missed: MissedTick, task: F )
+
+		-> Result<JoinHandle<()>, SpawnError>
+
+		where Self: LocalSpawnHandle<()> + Clone + 'static ,
+		      F   : FnMut() -> Fut + 'static               ,
+		      Fut : Future<Output = ()> + 'static          ,
+	{
+		assert!( interval > Duration::ZERO, "spawn_periodic_local: the interval must not be zero" );
+
+		self.spawn_handle_local( periodic( self.clone(), interval, missed, task ) )
+	}
+}
+
+
+
+// The body of a periodic task.
+//
+#[ cfg(not( target_arch = "wasm32" )) ]
+//
+async fn periodic<T, F, Fut>( timer: T, interval: Duration, missed: MissedTick, mut task: F )
+
+	where T  : Timer                ,
+	      F  : FnMut() -> Fut       ,
+	      Fut: Future<Output = ()>  ,
+{
+	let mut next = timer.now();
+
+	loop
+	{
+		task().await;
+
+		next += interval;
+
+		let now = timer.now();
+
+		if next < now
+		{
+			match missed
+			{
+				MissedTick::Burst => {}
+				MissedTick::Delay => next = now,
+
+				MissedTick::Skip  => while next < now { next += interval },
+			}
+		}
+
+		if next > now
+		{
+			timer.sleep_until( next ).await;
+		}
+
+		// When behind schedule, the next run might be ready right away as well. Give the executor a chance to run
+		// other tasks, and to notice that the JoinHandle was dropped.
+		//
+		else
+		{
+			YieldNow( false ).await;
+		}
+	}
+}
+
+
+// Returns pending once, after waking up the task.
+//
+#[ cfg(not( target_arch = "wasm32" )) ]
+//
+struct YieldNow( bool );
+
+
+#[ cfg(not( target_arch = "wasm32" )) ]
+//
+impl Future for YieldNow
+{
+	type Output = ();
+
+	fn poll( mut self: Pin<&mut Self>, cx: &mut Context<'_> ) -> Poll<()>
+	{
+		if self.0 { return Poll::Ready(()); }
+
+		self.0 = true;
+		cx.waker().wake_by_ref();
+
+		Poll::Pending
+	}
+}
+
+
+// Instant::now is not available on wasm, so just wait `interval` between runs.
+//
+#[ cfg( target_arch = "wasm32" ) ]
+//
+async fn periodic<T, F, Fut>( timer: T, interval: Duration, _missed: MissedTick, mut task: F )
+
+	where T  : Timer                ,
+	      F  : FnMut() -> Fut       ,
+	      Fut: Future<Output = ()>  ,
+{
+	loop
+	{
+		task().await;
+		timer.sleep( interval ).await;
+	}
+}
@@ -2,10 +2,11 @@
 //
 use
 {
-	futures_util :: { future::{ FutureExt, abortable }, task::SpawnExt                    } ,
+	futures_util :: { future::{ FutureExt, abortable, select, Either }, task::SpawnExt    } ,
 	futures_task :: { SpawnError, FutureObj                                               } ,
-	crate        :: { JoinHandle                                                          } ,
+	crate        :: { JoinHandle, Timer, DeadlineExceeded, CancelToken, Cancelled         } ,
 	std          :: { pin::Pin, future::Future, sync::{ Arc, atomic::AtomicBool }, rc::Rc } ,
+	std          :: { time::Instant                                                       } ,
 };
 
 
@@ -55,6 +56,27 @@ pub trait SpawnHandle<Out: 'static + Send>
 	/// Spawn a future and return a [`JoinHandle`] that can be awaited for the output of the future.
 	//
 	fn spawn_handle_obj( &self, future: FutureObj<'static, Out> ) -> Result<JoinHandle<Out>, SpawnError>;
+
+
+	/// Spawn several futures and return their [`JoinHandle`]s in the same order.
+	///
+	/// The default implementation spawns them one by one. Executors that can enqueue tasks in bulk can override it.
+	/// If spawning one of them fails, the error is returned and the handles of the tasks spawned so far are
+	/// dropped, which cancels them.
+	//
+	fn spawn_handle_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		let mut handles = Vec::with_capacity( futures.size_hint().0 );
+
+		for future in futures
+		{
+			handles.push( self.spawn_handle_obj( future )? );
+		}
+
+		Ok( handles )
+	}
 }
 
 /// Convenience trait for passing in a generic future to [`SpawnHandle`]. Much akin to `Spawn` and `SpawnExt` in the
@@ -65,6 +87,82 @@ pub trait SpawnHandleExt<Out: 'static + Send> : SpawnHandle<Out>
 	/// Spawn a future and return a [JoinHandle] that can be awaited for the output of the future.
 	//
 	fn spawn_handle( &self, future: impl Future<Output = Out> + Send + 'static ) -> Result<JoinHandle<Out>, SpawnError>;
+
+
+	/// Spawn several futures and return their [JoinHandle]s in the same order. See
+	/// [`SpawnHandle::spawn_handle_obj_many`].
+	///
+	/// ```
+	/// use
+	/// {
+	///    async_executors :: { SpawnHandleExt, ThreadPool } ,
+	///    futures         :: { executor::block_on, future::join_all } ,
+	/// };
+	///
+	/// let exec    = ThreadPool::new().expect( "create threadpool" );
+	/// let handles = exec.spawn_handle_many( (0..10u8).map( |i| async move { i * 2 } ) ).expect( "spawn" );
+	///
+	/// assert_eq!( block_on( join_all( handles ) ), (0..10u8).map( |i| i * 2 ).collect::<Vec<_>>() );
+	/// ```
+	//
+	fn spawn_handle_many<Fut>( &self, futures: impl IntoIterator<Item = Fut> ) -> Result<Vec< JoinHandle<Out> >, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	;
+
+
+	/// Spawn a future that gets cancelled when it hasn't finished by `deadline`. The [`JoinHandle`] resolves
+	/// to the output of the future, or to [`DeadlineExceeded`] if the deadline passed first. The future is dropped
+	/// at that point.
+	///
+	/// This uses the [`Timer`] of the executor, so on the executors of this crate, the native timer of the runtime.
+	///
+	/// ```
+	/// use
+	/// {
+	///    async_executors :: { SpawnHandleExt, ThreadPool, Timer, DeadlineExceeded } ,
+	///    futures         :: { executor::block_on, future::pending                 } ,
+	///    std             :: { time::Duration                                      } ,
+	/// };
+	///
+	/// let exec     = ThreadPool::new().expect( "create threadpool" );
+	/// let deadline = exec.now() + Duration::from_millis( 10 );
+	///
+	/// let handle = exec.spawn_handle_with_deadline( pending::<()>(), deadline ).expect( "spawn" );
+	///
+	/// assert_eq!( block_on( handle ), Err( DeadlineExceeded ) );
+	/// ```
+	//
+	fn spawn_handle_with_deadline( &self, future: impl Future<Output = Out> + Send + 'static, deadline: Instant )
+
+		-> Result<JoinHandle< Result<Out, DeadlineExceeded> >, SpawnError>
+
+		where Self: Timer + SpawnHandle< Result<Out, DeadlineExceeded> >
+	;
+
+
+	/// Spawn a future that gets aborted when `token` is cancelled. The future can await
+	/// [`CancelToken::cancelled`] on (a clone of) the token to clean up. Once the token is cancelled, the task
+	/// awaits `grace` alongside the future, and when `grace` resolves first, the future is dropped. The
+	/// [`JoinHandle`] resolves to the output of the future, or to [`Cancelled`] if it was aborted.
+	///
+	/// `grace` is only polled after cancellation, so an async block like `async move { timer.sleep( dur ).await }`
+	/// gives the task `dur` from that moment. Pass `futures::future::ready(())` to abort right away. Dropping the
+	/// [`JoinHandle`] still cancels the task immediately.
+	///
+	/// See [`CancelToken`] for an example.
+	//
+	fn spawn_handle_with_token
+	(
+		&self                                                ,
+		future: impl Future<Output = Out> + Send + 'static  ,
+		token : &CancelToken                                 ,
+		grace : impl Future<Output = ()>  + Send + 'static  ,
+	)
+		-> Result<JoinHandle< Result<Out, Cancelled> >, SpawnError>
+
+		where Self: SpawnHandle< Result<Out, Cancelled> >
+	;
 }
 
 
@@ -77,6 +175,52 @@ impl<T, Out> SpawnHandleExt<Out> for T
 	{
 		self.spawn_handle_obj( FutureObj::new(future.boxed()) )
 	}
+
+
+	fn spawn_handle_many<Fut>( &self, futures: impl IntoIterator<Item = Fut> ) -> Result<Vec< JoinHandle<Out> >, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		self.spawn_handle_obj_many( &mut futures.into_iter().map( |f| FutureObj::new(f.boxed()) ) )
+	}
+
+
+	fn spawn_handle_with_deadline( &self, future: impl Future<Output = Out> + Send + 'static, deadline: Instant )
+
+		-> Result<JoinHandle< Result<Out, DeadlineExceeded> >, SpawnError>
+
+		where Self: Timer + SpawnHandle< Result<Out, DeadlineExceeded> >
+	{
+		let sleep = self.sleep_until( deadline );
+
+		let fut = async move
+		{
+			match select( future.boxed(), sleep ).await
+			{
+				Either::Left ( (out, _) ) => Ok ( out              ),
+				Either::Right( _        ) => Err( DeadlineExceeded ),
+			}
+		};
+
+		self.spawn_handle_obj( FutureObj::new(fut.boxed()) )
+	}
+
+
+	fn spawn_handle_with_token
+	(
+		&self                                                ,
+		future: impl Future<Output = Out> + Send + 'static  ,
+		token : &CancelToken                                 ,
+		grace : impl Future<Output = ()>  + Send + 'static  ,
+	)
+		-> Result<JoinHandle< Result<Out, Cancelled> >, SpawnError>
+
+		where Self: SpawnHandle< Result<Out, Cancelled> >
+	{
+		let fut = super::cancel_token::with_token( future.boxed(), token.clone(), grace.boxed() );
+
+		self.spawn_handle_obj( FutureObj::new(fut.boxed()) )
+	}
 }
 
 
@@ -86,6 +230,14 @@ impl<T: ?Sized, Out> SpawnHandle<Out> for Box<T> where T: SpawnHandle<Out>, Out:
 	{
 		(**self).spawn_handle_obj( future )
 	}
+
+
+	fn spawn_handle_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		(**self).spawn_handle_obj_many( futures )
+	}
 }
 
 
@@ -95,6 +247,14 @@ impl<T: ?Sized, Out> SpawnHandle<Out> for Arc<T> where T: SpawnHandle<Out>, Out:
 	{
 		(**self).spawn_handle_obj( future )
 	}
+
+
+	fn spawn_handle_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		(**self).spawn_handle_obj_many( futures )
+	}
 }
 
 
@@ -104,6 +264,14 @@ impl<T: ?Sized, Out> SpawnHandle<Out> for Rc<T> where T: SpawnHandle<Out>, Out:
 	{
 		(**self).spawn_handle_obj( future )
 	}
+
+
+	fn spawn_handle_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		(**self).spawn_handle_obj_many( futures )
+	}
 }
 
 
@@ -113,6 +281,14 @@ impl<T, Out> SpawnHandle<Out> for &T where T: SpawnHandle<Out>, Out: 'static + S
 	{
 		(**self).spawn_handle_obj( future )
 	}
+
+
+	fn spawn_handle_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		(**self).spawn_handle_obj_many( futures )
+	}
 }
 
 
@@ -123,6 +299,14 @@ impl<T, Out> SpawnHandle<Out> for &mut T where T: SpawnHandle<Out>, Out: 'static
 	{
 		(**self).spawn_handle_obj( future )
 	}
+
+
+	fn spawn_handle_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, Out> > )
+
+		-> Result<Vec< JoinHandle<Out> >, SpawnError>
+	{
+		(**self).spawn_handle_obj_many( futures )
+	}
 }
 
 
@@ -0,0 +1,323 @@
+#[ allow(unused_imports) ]
+//
+use
+{
+	futures_util :: { future::FutureExt, task::{ SpawnExt, LocalSpawnExt } } ,
+	futures_task :: { SpawnError, FutureObj, LocalFutureObj               } ,
+	crate        :: { JoinHandle, SpawnHandle, LocalSpawnHandle           } ,
+	std          :: { future::Future, sync::Arc, rc::Rc                   } ,
+};
+
+
+/// Lets you spawn a generic future and get a [`JoinHandle`] to await it's output, without boxing it first.
+///
+/// [`SpawnHandleExt::spawn_handle`](crate::SpawnHandleExt::spawn_handle) always boxes the future to create the
+/// [`FutureObj`] that [`SpawnHandle`] needs to stay object safe. Executors that take generic futures, like tokio,
+/// async-std and async-global-executor, don't need that allocation. This trait hands them the future as is. For
+/// the other executors, it still saves a layer of indirection.
+///
+/// The method is generic, so this trait is not object safe. Like [`SpawnHandle`], it's generic over the output
+/// type, so the bound reads the same and code can switch between the two. Trait objects of [`SpawnHandle`]
+/// implement it too, by boxing the future and going through [`SpawnHandle::spawn_handle_obj`]. That way code that
+/// takes `impl SpawnHandleGeneric<Out>` avoids the allocation on the executors of this crate and still accepts a
+/// `Box<dyn SpawnHandle<Out>>`.
+///
+/// ```
+/// use
+/// {
+///    async_executors :: { SpawnHandle, SpawnHandleGeneric, ThreadPool } ,
+///    futures         :: { executor::block_on                           } ,
+/// };
+///
+/// fn need_exec( exec: impl SpawnHandleGeneric<u8> ) -> u8
+/// {
+///    let handle = exec.spawn_handle_generic( async { 5u8 } ).expect( "spawn" );
+///
+///    block_on( handle )
+/// }
+///
+/// let exec = ThreadPool::new().expect( "create threadpool" );
+///
+/// assert_eq!( need_exec( exec.clone() ), 5 );
+/// assert_eq!( need_exec( Box::new( exec ) as Box< dyn SpawnHandle<u8> > ), 5 );
+/// ```
+//
+pub trait SpawnHandleGeneric<Out: 'static + Send>
+{
+	/// Spawn a future and return a [`JoinHandle`] that can be awaited for the output of the future.
+	//
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	;
+}
+
+
+/// Lets you spawn a generic `!Send` future and get a [`JoinHandle`] to await it's output, without boxing it first.
+///
+/// This is the local counterpart of [`SpawnHandleGeneric`], see there for the details. It's implemented for trait
+/// objects of [`LocalSpawnHandle`].
+//
+pub trait LocalSpawnHandleGeneric<Out: 'static>
+{
+	/// Spawn a future and return a [`JoinHandle`] that can be awaited for the output of the future.
+	//
+	fn spawn_handle_local_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + 'static
+	;
+}
+
+
+
+impl<T: ?Sized, Out> SpawnHandleGeneric<Out> for Box<T> where T: SpawnHandleGeneric<Out>, Out: 'static + Send
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		(**self).spawn_handle_generic( future )
+	}
+}
+
+
+impl<T: ?Sized, Out> SpawnHandleGeneric<Out> for Arc<T> where T: SpawnHandleGeneric<Out>, Out: 'static + Send
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		(**self).spawn_handle_generic( future )
+	}
+}
+
+
+impl<T: ?Sized, Out> SpawnHandleGeneric<Out> for Rc<T> where T: SpawnHandleGeneric<Out>, Out: 'static + Send
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		(**self).spawn_handle_generic( future )
+	}
+}
+
+
+impl<T: ?Sized, Out> SpawnHandleGeneric<Out> for &T where T: SpawnHandleGeneric<Out>, Out: 'static + Send
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		(**self).spawn_handle_generic( future )
+	}
+}
+
+
+impl<T: ?Sized, Out> SpawnHandleGeneric<Out> for &mut T where T: SpawnHandleGeneric<Out>, Out: 'static + Send
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		(**self).spawn_handle_generic( future )
+	}
+}
+
+
+
+impl<T: ?Sized, Out> LocalSpawnHandleGeneric<Out> for Box<T> where T: LocalSpawnHandleGeneric<Out>, Out: 'static
+{
+	fn spawn_handle_local_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + 'static
+	{
+		(**self).spawn_handle_local_generic( future )
+	}
+}
+
+
+impl<T: ?Sized, Out> LocalSpawnHandleGeneric<Out> for Arc<T> where T: LocalSpawnHandleGeneric<Out>, Out: 'static
+{
+	fn spawn_handle_local_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + 'static
+	{
+		(**self).spawn_handle_local_generic( future )
+	}
+}
+
+
+impl<T: ?Sized, Out> LocalSpawnHandleGeneric<Out> for Rc<T> where T: LocalSpawnHandleGeneric<Out>, Out: 'static
+{
+	fn spawn_handle_local_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + 'static
+	{
+		(**self).spawn_handle_local_generic( future )
+	}
+}
+
+
+impl<T: ?Sized, Out> LocalSpawnHandleGeneric<Out> for &T where T: LocalSpawnHandleGeneric<Out>, Out: 'static
+{
+	fn spawn_handle_local_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + 'static
+	{
+		(**self).spawn_handle_local_generic( future )
+	}
+}
+
+
+impl<T: ?Sized, Out> LocalSpawnHandleGeneric<Out> for &mut T where T: LocalSpawnHandleGeneric<Out>, Out: 'static
+{
+	fn spawn_handle_local_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + 'static
+	{
+		(**self).spawn_handle_local_generic( future )
+	}
+}
+
+
+
+// Trait objects can't take generic futures, so these box them.
+//
+impl<Out: 'static + Send> SpawnHandleGeneric<Out> for dyn SpawnHandle<Out>
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		self.spawn_handle_obj( FutureObj::new( future.boxed() ) )
+	}
+}
+
+
+impl<Out: 'static + Send> SpawnHandleGeneric<Out> for dyn SpawnHandle<Out> + Send
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		self.spawn_handle_obj( FutureObj::new( future.boxed() ) )
+	}
+}
+
+
+impl<Out: 'static + Send> SpawnHandleGeneric<Out> for dyn SpawnHandle<Out> + Sync
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		self.spawn_handle_obj( FutureObj::new( future.boxed() ) )
+	}
+}
+
+
+impl<Out: 'static + Send> SpawnHandleGeneric<Out> for dyn SpawnHandle<Out> + Send + Sync
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		self.spawn_handle_obj( FutureObj::new( future.boxed() ) )
+	}
+}
+
+
+
+impl<Out: 'static> LocalSpawnHandleGeneric<Out> for dyn LocalSpawnHandle<Out>
+{
+	fn spawn_handle_local_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + 'static
+	{
+		self.spawn_handle_local_obj( LocalFutureObj::new( future.boxed_local() ) )
+	}
+}
+
+
+impl<Out: 'static> LocalSpawnHandleGeneric<Out> for dyn LocalSpawnHandle<Out> + Send
+{
+	fn spawn_handle_local_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + 'static
+	{
+		self.spawn_handle_local_obj( LocalFutureObj::new( future.boxed_local() ) )
+	}
+}
+
+
+impl<Out: 'static> LocalSpawnHandleGeneric<Out> for dyn LocalSpawnHandle<Out> + Sync
+{
+	fn spawn_handle_local_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + 'static
+	{
+		self.spawn_handle_local_obj( LocalFutureObj::new( future.boxed_local() ) )
+	}
+}
+
+
+impl<Out: 'static> LocalSpawnHandleGeneric<Out> for dyn LocalSpawnHandle<Out> + Send + Sync
+{
+	fn spawn_handle_local_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + 'static
+	{
+		self.spawn_handle_local_obj( LocalFutureObj::new( future.boxed_local() ) )
+	}
+}
+
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+impl<Out: 'static + Send> SpawnHandleGeneric<Out> for crate::LocalSpawner
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		self.spawn_handle_local_generic( future )
+	}
+}
+
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+impl<Out: 'static> LocalSpawnHandleGeneric<Out> for crate::LocalSpawner
+{
+	fn spawn_handle_local_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + 'static
+	{
+		let (fut, handle) = future.remote_handle();
+
+		self.spawn_local( fut )?;
+
+		Ok( JoinHandle{ inner: super::join_handle::InnerJh::RemoteHandle( Some(handle) ) } )
+	}
+}
+
+
+
+#[ cfg( feature = "threadpool" ) ]
+//
+impl<Out: 'static + Send> SpawnHandleGeneric<Out> for crate::ThreadPool
+{
+	fn spawn_handle_generic<Fut>( &self, future: Fut ) -> Result<JoinHandle<Out>, SpawnError>
+
+		where Fut: Future<Output = Out> + Send + 'static
+	{
+		let (fut, handle) = future.remote_handle();
+
+		self.spawn( fut )?;
+
+		Ok( JoinHandle{ inner: super::join_handle::InnerJh::RemoteHandle( Some(handle) ) } )
+	}
+}
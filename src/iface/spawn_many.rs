@@ -0,0 +1,197 @@
+use
+{
+	futures_task :: { SpawnError, Spawn, LocalSpawn, FutureObj, LocalFutureObj } ,
+	futures_util :: { future::FutureExt                                        } ,
+	std          :: { future::Future, sync::Arc, rc::Rc                        } ,
+};
+
+
+/// Lets you spawn several futures at once without getting [`JoinHandle`](crate::JoinHandle)s.
+///
+/// This trait is object safe. The default implementation spawns the futures one by one. Executors that can
+/// enqueue tasks in bulk override it, so a batch only takes their locks and wakes up their threads once. Use
+/// [`SpawnManyExt`] to spawn generic futures. For the versions with handles, see
+/// [`SpawnHandle::spawn_handle_obj_many`](crate::SpawnHandle::spawn_handle_obj_many).
+///
+/// If spawning one of the futures fails, the error is returned. The tasks spawned before keep running and
+/// the remaining futures are dropped.
+//
+pub trait SpawnMany: Spawn
+{
+	/// Spawn all `futures` on this executor.
+	//
+	fn spawn_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		for future in futures
+		{
+			self.spawn_obj( future )?;
+		}
+
+		Ok(())
+	}
+}
+
+
+
+/// Lets you spawn several `!Send` futures at once without getting [`JoinHandle`](crate::JoinHandle)s.
+/// See [`SpawnMany`].
+//
+pub trait LocalSpawnMany: LocalSpawn
+{
+	/// Spawn all `futures` on this executor.
+	//
+	fn spawn_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		for future in futures
+		{
+			self.spawn_local_obj( future )?;
+		}
+
+		Ok(())
+	}
+}
+
+
+
+/// Spawn several generic futures at once on anything that implements [`SpawnMany`] or [`LocalSpawnMany`].
+//
+pub trait SpawnManyExt
+{
+	/// Spawn all `futures` on this executor. See [`SpawnMany::spawn_obj_many`].
+	//
+	fn spawn_many<Fut>( &self, futures: impl IntoIterator<Item = Fut> ) -> Result<(), SpawnError>
+
+		where Self: SpawnMany                             ,
+		      Fut : Future<Output = ()> + Send + 'static ,
+	;
+
+
+	/// Spawn all `!Send` `futures` on this executor. See [`LocalSpawnMany::spawn_local_obj_many`].
+	//
+	fn spawn_local_many<Fut>( &self, futures: impl IntoIterator<Item = Fut> ) -> Result<(), SpawnError>
+
+		where Self: LocalSpawnMany                 ,
+		      Fut : Future<Output = ()> + 'static ,
+	;
+}
+
+
+impl<T: ?Sized> SpawnManyExt for T
+{
+	fn spawn_many<Fut>( &self, futures: impl IntoIterator<Item = Fut> ) -> Result<(), SpawnError>
+
+		where Self: SpawnMany                             ,
+		      Fut : Future<Output = ()> + Send + 'static ,
+	{
+		self.spawn_obj_many( &mut futures.into_iter().map( |f| FutureObj::new(f.boxed()) ) )
+	}
+
+
+	fn spawn_local_many<Fut>( &self, futures: impl IntoIterator<Item = Fut> ) -> Result<(), SpawnError>
+
+		where Self: LocalSpawnMany                 ,
+		      Fut : Future<Output = ()> + 'static ,
+	{
+		self.spawn_local_obj_many( &mut futures.into_iter().map( |f| LocalFutureObj::new(f.boxed_local()) ) )
+	}
+}
+
+
+
+impl<T: ?Sized + SpawnMany> SpawnMany for Box<T>
+{
+	fn spawn_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		(**self).spawn_obj_many( futures )
+	}
+}
+
+
+impl<T: ?Sized + SpawnMany> SpawnMany for Arc<T>
+{
+	fn spawn_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		(**self).spawn_obj_many( futures )
+	}
+}
+
+
+impl<T: ?Sized + SpawnMany> SpawnMany for Rc<T>
+{
+	fn spawn_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		(**self).spawn_obj_many( futures )
+	}
+}
+
+
+impl<T: ?Sized + SpawnMany> SpawnMany for &T
+{
+	fn spawn_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		(**self).spawn_obj_many( futures )
+	}
+}
+
+
+impl<T: ?Sized + SpawnMany> SpawnMany for &mut T
+{
+	fn spawn_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		(**self).spawn_obj_many( futures )
+	}
+}
+
+
+
+impl<T: ?Sized + LocalSpawnMany> LocalSpawnMany for Box<T>
+{
+	fn spawn_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		(**self).spawn_local_obj_many( futures )
+	}
+}
+
+
+impl<T: ?Sized + LocalSpawnMany> LocalSpawnMany for Arc<T>
+{
+	fn spawn_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		(**self).spawn_local_obj_many( futures )
+	}
+}
+
+
+impl<T: ?Sized + LocalSpawnMany> LocalSpawnMany for Rc<T>
+{
+	fn spawn_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		(**self).spawn_local_obj_many( futures )
+	}
+}
+
+
+impl<T: ?Sized + LocalSpawnMany> LocalSpawnMany for &T
+{
+	fn spawn_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		(**self).spawn_local_obj_many( futures )
+	}
+}
+
+
+impl<T: ?Sized + LocalSpawnMany> LocalSpawnMany for &mut T
+{
+	fn spawn_local_obj_many( &self, futures: &mut dyn Iterator< Item = LocalFutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		(**self).spawn_local_obj_many( futures )
+	}
+}
+
+
+
+// Neither has an API to enqueue in bulk.
+//
+#[ cfg( feature = "localpool" ) ] impl SpawnMany      for crate::LocalSpawner {}
+#[ cfg( feature = "localpool" ) ] impl LocalSpawnMany for crate::LocalSpawner {}
+#[ cfg( feature = "threadpool") ] impl SpawnMany      for crate::ThreadPool   {}
@@ -0,0 +1,306 @@
+use
+{
+	std :: { any::Any, cell::RefCell, collections::HashMap, error::Error, fmt, future::Future } ,
+	std :: { marker::PhantomData, pin::Pin, sync::Arc, task::{ Context, Poll }               } ,
+};
+
+
+/// Declare task-local values that work on every executor.
+///
+/// This declares a `static` [`LocalKey`]. A value is set for the duration of a future with
+/// [`LocalKey::scope`] and read with [`LocalKey::with`]. Unlike the `task_local!` macros of tokio and async-std,
+/// this doesn't depend on the executor, as the value is set around each poll by the [`Scope`] future itself.
+///
+/// Tasks spawned from within a scope don't see the values, unless they are spawned through the [`Inherit`](crate::Inherit)
+/// wrapper, which captures the values of the spawning task.
+///
+/// The values must be `Send + Sync + 'static` as they can be shared with inheriting tasks on other threads.
+///
+/// ```
+/// use
+/// {
+///    async_executors :: { task_local, SpawnHandleExt, ThreadPool, Inherit } ,
+///    futures         :: { executor::block_on                             } ,
+/// };
+///
+/// task_local!
+/// {
+///    static REQUEST_ID: u64;
+/// }
+///
+/// let exec = Inherit::new( ThreadPool::new().expect( "create threadpool" ) );
+///
+/// let id = block_on( REQUEST_ID.scope( 7, async
+/// {
+///    // Inherit captures the request id of this task.
+///    //
+///    exec.spawn_handle( async { REQUEST_ID.with( |id| *id ) } ).expect( "spawn" ).await
+/// }));
+///
+/// assert_eq!( id, 7 );
+/// ```
+//
+#[ macro_export ]
+//
+macro_rules! task_local
+{
+	() => {};
+
+	( $(#[$attr:meta])* $vis:vis static $name:ident : $t:ty ; $($rest:tt)* ) =>
+	{
+		$(#[$attr])*
+		$vis static $name: $crate::LocalKey<$t> = $crate::LocalKey::__new();
+
+		$crate::task_local!( $($rest)* );
+	};
+
+	( $(#[$attr:meta])* $vis:vis static $name:ident : $t:ty ) =>
+	{
+		$crate::task_local!( $(#[$attr])* $vis static $name: $t; );
+	};
+}
+
+
+
+type Value = Arc< dyn Any + Send + Sync >;
+
+
+/// The task-local values of a task, keyed by the address of their [`LocalKey`].
+//
+#[ derive( Clone, Default ) ]
+//
+pub(crate) struct Locals
+{
+	values: Option< Arc< HashMap<usize, Value> > >,
+}
+
+
+thread_local!
+{
+	static CURRENT: RefCell<Locals> = RefCell::new( Locals::default() );
+}
+
+
+impl Locals
+{
+	/// The values of the task that is currently being polled on this thread.
+	//
+	pub(crate) fn current() -> Self
+	{
+		CURRENT.with( |c| c.borrow().clone() )
+	}
+
+
+	/// Make these the current values while running `f`.
+	//
+	pub(crate) fn enter<R>( &self, f: impl FnOnce() -> R ) -> R
+	{
+		// Restores the previous values, also when `f` panics.
+		//
+		struct Reset( Option<Locals> );
+
+		impl Drop for Reset
+		{
+			fn drop( &mut self )
+			{
+				if let Some( prev ) = self.0.take()
+				{
+					CURRENT.with( |c| *c.borrow_mut() = prev );
+				}
+			}
+		}
+
+		let prev   = CURRENT.with( |c| c.replace( self.clone() ) );
+		let _reset = Reset( Some( prev ) );
+
+		f()
+	}
+
+
+	fn get( &self, key: usize ) -> Option<Value>
+	{
+		self.values.as_ref()?.get( &key ).cloned()
+	}
+
+
+	fn with( &self, key: usize, value: Value ) -> Self
+	{
+		let mut values = self.values.as_deref().cloned().unwrap_or_default();
+
+		values.insert( key, value );
+
+		Self { values: Some( Arc::new( values ) ) }
+	}
+}
+
+
+
+/// A key for task-local values, declared with [`task_local!`](crate::task_local!).
+//
+pub struct LocalKey<T>
+{
+	// Makes sure every key has a distinct address.
+	//
+	_id  : u8           ,
+	_type: PhantomData<T>,
+}
+
+
+impl<T: Send + Sync + 'static> LocalKey<T>
+{
+	#[ doc( hidden ) ]
+	//
+	pub const fn __new() -> Self
+	{
+		Self { _id: 0, _type: PhantomData }
+	}
+
+
+	/// Run `future` with `value` set for this key. The value is set every time the future is polled, so it is visible
+	/// to the future and to anything it calls, no matter on which executor it runs.
+	//
+	pub fn scope<F: Future>( &'static self, value: T, future: F ) -> Scope<F>
+	{
+		Scope { key: self.id(), value: Some( Arc::new( value ) ), locals: None, future: Box::pin( future ) }
+	}
+
+
+	/// Access the current value of this key.
+	///
+	/// # Panics
+	///
+	/// When called outside of a [`scope`](LocalKey::scope) for this key.
+	//
+	pub fn with<R>( &'static self, f: impl FnOnce( &T ) -> R ) -> R
+	{
+		self.try_with( f ).expect( "task_local: cannot access a task-local value outside of a scope for it" )
+	}
+
+
+	/// Access the current value of this key, or return an error when called outside of a
+	/// [`scope`](LocalKey::scope) for this key.
+	//
+	pub fn try_with<R>( &'static self, f: impl FnOnce( &T ) -> R ) -> Result<R, AccessError>
+	{
+		let value = Locals::current().get( self.id() ).ok_or( AccessError )?;
+
+		Ok( f( value.downcast_ref::<T>().expect( "task_local: value has the type of the key" ) ) )
+	}
+
+
+	/// Get a clone of the current value of this key.
+	///
+	/// # Panics
+	///
+	/// When called outside of a [`scope`](LocalKey::scope) for this key.
+	//
+	pub fn get( &'static self ) -> T where T: Clone
+	{
+		self.with( T::clone )
+	}
+
+
+	fn id( &'static self ) -> usize
+	{
+		let ptr: *const Self = self;
+
+		ptr as usize
+	}
+}
+
+
+impl<T> fmt::Debug for LocalKey<T>
+{
+	fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result
+	{
+		f.debug_struct( "LocalKey" ).finish()
+	}
+}
+
+
+
+/// The future returned by [`LocalKey::scope`].
+//
+#[ must_use = "futures do nothing unless you `.await` or poll them" ]
+//
+pub struct Scope<F>
+{
+	key   : usize               ,
+	value : Option<Value>       ,
+	locals: Option<Locals>      ,
+	future: Pin<Box<F>>         ,
+}
+
+
+impl<F: Future> Future for Scope<F>
+{
+	type Output = F::Output;
+
+	fn poll( mut self: Pin<&mut Self>, cx: &mut Context<'_> ) -> Poll<F::Output>
+	{
+		// The values of enclosing scopes don't change between polls, so merge them only once.
+		//
+		if let Some( value ) = self.value.take()
+		{
+			self.locals = Some( Locals::current().with( self.key, value ) );
+		}
+
+		let this   = &mut *self;
+		let future = &mut this.future;
+
+		this.locals.as_ref().expect( "task_local: locals are set on first poll" ).enter( || future.as_mut().poll( cx ) )
+	}
+}
+
+
+impl<F> fmt::Debug for Scope<F>
+{
+	fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result
+	{
+		f.debug_struct( "Scope" ).finish()
+	}
+}
+
+
+
+// Runs a future with the task-local values captured when it was spawned. Used by Inherit.
+//
+pub(crate) struct Inherited<F>
+{
+	pub(crate) locals: Locals ,
+	pub(crate) future: F      ,
+}
+
+
+impl<F: Future + Unpin> Future for Inherited<F>
+{
+	type Output = F::Output;
+
+	fn poll( mut self: Pin<&mut Self>, cx: &mut Context<'_> ) -> Poll<F::Output>
+	{
+		let this   = &mut *self;
+		let future = &mut this.future;
+
+		this.locals.enter( || Pin::new( future ).poll( cx ) )
+	}
+}
+
+
+
+/// The error returned by [`LocalKey::try_with`] when called outside of a scope for the key.
+//
+#[ derive( Debug, Clone, Copy, PartialEq, Eq, Hash ) ]
+//
+pub struct AccessError;
+
+
+impl fmt::Display for AccessError
+{
+	fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result
+	{
+		f.write_str( "task-local value accessed outside of a scope for it" )
+	}
+}
+
+
+impl Error for AccessError {}
@@ -0,0 +1,113 @@
+use
+{
+	futures_util :: { future::BoxFuture                          } ,
+	std          :: { time::{ Duration, Instant }, sync::Arc, rc::Rc } ,
+};
+
+
+/// An executor agnostic timer. This lets libraries that take an executor as a parameter also sleep
+/// without picking a runtime for their clients.
+///
+/// The executors in this crate implement it with the native timer of the runtime where one exists, so
+/// the returned futures are driven by the same reactor as the tasks that await them:
+///
+/// - [`TokioCt`](crate::TokioCt) and [`TokioTp`](crate::TokioTp) use `tokio::time::sleep`. The builders turn on
+///   the time driver for you.
+/// - [`AsyncStd`](crate::AsyncStd) uses `async_std::task::sleep`.
+/// - [`GlommioCt`](crate::GlommioCt) uses glommio's `Timer`.
+/// - the others use [futures-timer](https://docs.rs/futures-timer).
+///
+/// For testing, there is [`MockClock`](crate::MockClock) which implements this trait with virtual time.
+///
+/// Like [`SpawnHandle`](crate::SpawnHandle), this trait is object safe and the futures it returns are `Send`,
+/// so they can be used in tasks spawned on any executor.
+//
+pub trait Timer
+{
+	/// Returns a future that resolves once `dur` has elapsed.
+	//
+	fn sleep( &self, dur: Duration ) -> BoxFuture<'static, ()>;
+
+
+	/// The current time as seen by this timer. Defaults to [`Instant::now`].
+	///
+	/// Note that [`Instant::now`] is not available on `wasm32-unknown-unknown`.
+	//
+	fn now( &self ) -> Instant
+	{
+		Instant::now()
+	}
+
+
+	/// Returns a future that resolves once [`now`](Timer::now) has reached `deadline`.
+	//
+	fn sleep_until( &self, deadline: Instant ) -> BoxFuture<'static, ()>
+	{
+		self.sleep( deadline.saturating_duration_since( self.now() ) )
+	}
+}
+
+
+
+impl<T: ?Sized> Timer for Box<T> where T: Timer
+{
+	fn sleep      ( &self, dur     : Duration ) -> BoxFuture<'static, ()> { (**self).sleep( dur )            }
+	fn now        ( &self                     ) -> Instant                { (**self).now()                   }
+	fn sleep_until( &self, deadline: Instant  ) -> BoxFuture<'static, ()> { (**self).sleep_until( deadline ) }
+}
+
+
+impl<T: ?Sized> Timer for Arc<T> where T: Timer
+{
+	fn sleep      ( &self, dur     : Duration ) -> BoxFuture<'static, ()> { (**self).sleep( dur )            }
+	fn now        ( &self                     ) -> Instant                { (**self).now()                   }
+	fn sleep_until( &self, deadline: Instant  ) -> BoxFuture<'static, ()> { (**self).sleep_until( deadline ) }
+}
+
+
+impl<T: ?Sized> Timer for Rc<T> where T: Timer
+{
+	fn sleep      ( &self, dur     : Duration ) -> BoxFuture<'static, ()> { (**self).sleep( dur )            }
+	fn now        ( &self                     ) -> Instant                { (**self).now()                   }
+	fn sleep_until( &self, deadline: Instant  ) -> BoxFuture<'static, ()> { (**self).sleep_until( deadline ) }
+}
+
+
+impl<T: ?Sized> Timer for &T where T: Timer
+{
+	fn sleep      ( &self, dur     : Duration ) -> BoxFuture<'static, ()> { (**self).sleep( dur )            }
+	fn now        ( &self                     ) -> Instant                { (**self).now()                   }
+	fn sleep_until( &self, deadline: Instant  ) -> BoxFuture<'static, ()> { (**self).sleep_until( deadline ) }
+}
+
+
+impl<T: ?Sized> Timer for &mut T where T: Timer
+{
+	fn sleep      ( &self, dur     : Duration ) -> BoxFuture<'static, ()> { (**self).sleep( dur )            }
+	fn now        ( &self                     ) -> Instant                { (**self).now()                   }
+	fn sleep_until( &self, deadline: Instant  ) -> BoxFuture<'static, ()> { (**self).sleep_until( deadline ) }
+}
+
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+impl Timer for crate::LocalSpawner
+{
+	fn sleep( &self, dur: Duration ) -> BoxFuture<'static, ()>
+	{
+		Box::pin( futures_timer::Delay::new( dur ) )
+	}
+}
+
+
+
+#[ cfg( feature = "threadpool" ) ]
+//
+impl Timer for crate::ThreadPool
+{
+	fn sleep( &self, dur: Duration ) -> BoxFuture<'static, ()>
+	{
+		Box::pin( futures_timer::Delay::new( dur ) )
+	}
+}
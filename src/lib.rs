@@ -32,6 +32,22 @@ pub mod exec;
 //
 pub mod iface;
 
+/// A test suite to check that an executor conforms to the semantics of the traits in this crate.
+//
+#[ cfg( feature = "conformance" ) ]
+#[ cfg_attr( nightly, doc(cfg( feature = "conformance" )) ) ]
+//
+pub mod conformance;
+
+/// Attribute macros to run an async test on every executor, or an async main function on the executor of your
+/// choice. They live in this module rather than at the crate root, so a glob import of this crate doesn't make
+/// `#[test]` ambiguous.
+//
+#[ cfg( feature = "macros" ) ]
+#[ cfg_attr( nightly, doc(cfg( feature = "macros" )) ) ]
+//
+pub mod macros;
+
 pub use exec::*;
 pub use iface::*;
 
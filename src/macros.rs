@@ -0,0 +1,30 @@
+// Besides the macros, this holds support for the code they generate. The proc macros can't see which features of
+// this crate are enabled, so each test they generate is wrapped in one of the `__if_*` macros below, which drop
+// it when the executor is not available.
+//
+pub use async_executors_macros::{ test, main };
+
+#[ cfg( feature = "glommio"    ) ] #[ doc(hidden) ] pub use glommio_crate::LocalExecutorBuilder;
+#[ cfg( feature = "threadpool" ) ] #[ doc(hidden) ] pub use futures_executor::block_on;
+
+
+#[ cfg(     feature = "tokio_ct"      ) ] #[ doc(hidden) ] #[ macro_export ] macro_rules! __if_tokio_ct     { ($($i:item)*) => { $($i)* } }
+#[ cfg( not(feature = "tokio_ct")     ) ] #[ doc(hidden) ] #[ macro_export ] macro_rules! __if_tokio_ct     { ($($i:item)*) => {}         }
+
+#[ cfg(     feature = "tokio_tp"      ) ] #[ doc(hidden) ] #[ macro_export ] macro_rules! __if_tokio_tp     { ($($i:item)*) => { $($i)* } }
+#[ cfg( not(feature = "tokio_tp")     ) ] #[ doc(hidden) ] #[ macro_export ] macro_rules! __if_tokio_tp     { ($($i:item)*) => {}         }
+
+#[ cfg(     feature = "async_std"     ) ] #[ doc(hidden) ] #[ macro_export ] macro_rules! __if_async_std    { ($($i:item)*) => { $($i)* } }
+#[ cfg( not(feature = "async_std")    ) ] #[ doc(hidden) ] #[ macro_export ] macro_rules! __if_async_std    { ($($i:item)*) => {}         }
+
+#[ cfg(     feature = "async_global"  ) ] #[ doc(hidden) ] #[ macro_export ] macro_rules! __if_async_global { ($($i:item)*) => { $($i)* } }
+#[ cfg( not(feature = "async_global") ) ] #[ doc(hidden) ] #[ macro_export ] macro_rules! __if_async_global { ($($i:item)*) => {}         }
+
+#[ cfg(     feature = "glommio"       ) ] #[ doc(hidden) ] #[ macro_export ] macro_rules! __if_glommio      { ($($i:item)*) => { $($i)* } }
+#[ cfg( not(feature = "glommio")      ) ] #[ doc(hidden) ] #[ macro_export ] macro_rules! __if_glommio      { ($($i:item)*) => {}         }
+
+#[ cfg(     feature = "localpool"     ) ] #[ doc(hidden) ] #[ macro_export ] macro_rules! __if_localpool    { ($($i:item)*) => { $($i)* } }
+#[ cfg( not(feature = "localpool")    ) ] #[ doc(hidden) ] #[ macro_export ] macro_rules! __if_localpool    { ($($i:item)*) => {}         }
+
+#[ cfg(     feature = "threadpool"    ) ] #[ doc(hidden) ] #[ macro_export ] macro_rules! __if_threadpool   { ($($i:item)*) => { $($i)* } }
+#[ cfg( not(feature = "threadpool")   ) ] #[ doc(hidden) ] #[ macro_export ] macro_rules! __if_threadpool   { ($($i:item)*) => {}         }
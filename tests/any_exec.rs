@@ -0,0 +1,211 @@
+#![ cfg(all( not(target_os = "unknown"), any( feature = "tokio_ct", feature = "tokio_tp", feature = "async_std", feature = "async_global", feature = "threadpool", feature = "localpool" ) )) ]
+
+// Tested:
+//
+// ✔ parse the textual configuration.
+// ✔ reject invalid configurations.
+// ✔ read the configuration from the environment.
+// ✔ deserialize the configuration with serde.
+// ✔ build every executor that is compiled in, spawn on it and block_on.
+// ✔ spawn !Send futures on the executors that support it.
+// ✔ spawning !Send futures on TokioTp and ThreadPool returns an error.
+// ✔ building an executor that is not compiled in returns an error.
+//
+mod common;
+
+use
+{
+	common :: { *                } ,
+	std    :: { time::Duration   } ,
+};
+
+
+// parse the textual configuration.
+//
+#[ test ]
+//
+fn parse()
+{
+	let config: ExecConfig = "tokio_tp:threads=4".parse().expect( "parse" );
+
+	assert_eq!( config, ExecConfig{ exec: ExecKind::TokioTp, threads: Some(4) } );
+
+
+	let config: ExecConfig = " localpool ".parse().expect( "parse" );
+
+	assert_eq!( config, ExecConfig::from( ExecKind::LocalPool ) );
+
+
+	let config: ExecConfig = "threadpool: threads = 2,".parse().expect( "parse" );
+
+	assert_eq!( config, ExecConfig{ exec: ExecKind::ThreadPool, threads: Some(2) } );
+}
+
+
+
+// reject invalid configurations.
+//
+#[ test ]
+//
+fn parse_invalid()
+{
+	for invalid in &[ "", "tokio", "tokio_tp:threads", "tokio_tp:threads=four", "tokio_tp:stack=4" ]
+	{
+		let result = invalid.parse::<ExecConfig>();
+
+		assert!( matches!( result, Err( ExecConfigError::Parse(_) ) ), "{:?} should not parse", invalid );
+	}
+
+
+	let config = ExecConfig{ exec: ExecKind::AsyncStd, threads: Some(2) };
+
+	assert!( matches!( config.build(), Err( ExecConfigError::Parse(_) ) ) );
+
+
+	let config = ExecConfig{ exec: ExecKind::TokioTp, threads: Some(0) };
+
+	assert!( matches!( config.build(), Err( ExecConfigError::Parse(_) ) ) );
+}
+
+
+
+// read the configuration from the environment.
+//
+#[ test ]
+//
+fn from_env()
+{
+	std::env::set_var( ExecConfig::ENV_VAR, "tokio_ct" );
+
+	let config = ExecConfig::from_env().expect( "read env" );
+
+	assert_eq!( config, ExecConfig::from( ExecKind::TokioCt ) );
+
+
+	std::env::remove_var( ExecConfig::ENV_VAR );
+
+	assert!( matches!( ExecConfig::from_env(), Err( ExecConfigError::Parse(_) ) ) );
+}
+
+
+
+// deserialize the configuration with serde.
+//
+#[ cfg( feature = "serde" ) ]
+//
+#[ test ]
+//
+fn deserialize()
+{
+	let config: ExecConfig = serde_json::from_str( r#"{ "exec": "tokio_tp", "threads": 4 }"# ).expect( "deserialize" );
+
+	assert_eq!( config, ExecConfig{ exec: ExecKind::TokioTp, threads: Some(4) } );
+
+
+	let config: ExecConfig = serde_json::from_str( r#"{ "exec": "async_global" }"# ).expect( "deserialize" );
+
+	assert_eq!( config, ExecConfig::from( ExecKind::AsyncGlobal ) );
+
+
+	assert!( serde_json::from_str::<ExecConfig>( r#"{ "exec": "tokio" }"#                 ).is_err() );
+	assert!( serde_json::from_str::<ExecConfig>( r#"{ "exec": "tokio_ct", "stack": 4 }"# ).is_err() );
+}
+
+
+
+// build every executor that is compiled in, spawn on it and block_on.
+//
+#[ test ]
+//
+fn build()
+{
+	let mut kinds = Vec::new();
+
+	#[ cfg( feature = "tokio_tp"     ) ] kinds.push( "tokio_tp:threads=2" );
+	#[ cfg( feature = "tokio_ct"     ) ] kinds.push( "tokio_ct" );
+	#[ cfg( feature = "async_std"    ) ] kinds.push( "async_std" );
+	#[ cfg( feature = "async_global" ) ] kinds.push( "async_global" );
+	#[ cfg( feature = "threadpool"   ) ] kinds.push( "threadpool:threads=2" );
+	#[ cfg( feature = "localpool"    ) ] kinds.push( "localpool" );
+
+	for kind in &kinds
+	{
+		let exec = kind.parse::<ExecConfig>().expect( "parse" ).build().expect( "build" );
+
+		let out = exec.block_on( async
+		{
+			exec.sleep( Duration::from_millis(1) ).await;
+
+			exec.spawn_handle( async { 5u8 } ).expect( "spawn" ).await
+		});
+
+		assert_eq!( out, 5, "{}", kind );
+	}
+}
+
+
+
+// spawn !Send futures on the executors that support it.
+//
+#[ cfg(any( feature = "tokio_ct", feature = "localpool" )) ]
+//
+#[ test ]
+//
+fn spawn_local()
+{
+	let mut kinds = Vec::new();
+
+	#[ cfg( feature = "tokio_ct"  ) ] kinds.push( ExecKind::TokioCt );
+	#[ cfg( feature = "localpool" ) ] kinds.push( ExecKind::LocalPool );
+
+	for kind in &kinds
+	{
+		let exec = ExecConfig::from( *kind ).build().expect( "build" );
+
+		let out = exec.block_on( async
+		{
+			exec.spawn_handle_local( async { Rc::new( 5u8 ) } ).expect( "spawn" ).await
+		});
+
+		assert_eq!( *out, 5, "{}", kind );
+	}
+}
+
+
+
+// spawning !Send futures on TokioTp and ThreadPool returns an error.
+//
+#[ cfg(any( feature = "tokio_tp", feature = "threadpool" )) ]
+//
+#[ test ]
+//
+fn spawn_local_unsupported()
+{
+	let mut kinds = Vec::new();
+
+	#[ cfg( feature = "tokio_tp"   ) ] kinds.push( ExecKind::TokioTp );
+	#[ cfg( feature = "threadpool" ) ] kinds.push( ExecKind::ThreadPool );
+
+	for kind in &kinds
+	{
+		let exec = ExecConfig::from( *kind ).build().expect( "build" );
+
+		assert!( exec.spawn_local( async {} ).is_err() );
+		assert!( exec.spawn_handle_local( async { Rc::new( 5u8 ) } ).is_err() );
+	}
+}
+
+
+
+// building an executor that is not compiled in returns an error.
+//
+#[ cfg(not( feature = "glommio" )) ]
+//
+#[ test ]
+//
+fn disabled()
+{
+	let config = ExecConfig::from( ExecKind::GlommioCt );
+
+	assert!( matches!( config.build(), Err( ExecConfigError::Disabled( ExecKind::GlommioCt ) ) ) );
+}
@@ -13,6 +13,7 @@
 //
 // ✔ Joinhandle::detach allows task to keep running.
 // ✔ Joinhandle::drop aborts the task.
+// ✔ AsyncGlobalBuilder::init fails after tasks were spawned.
 //
 mod common;
 
@@ -374,3 +375,18 @@ fn spawn_handle_local_os()
 
 	assert_eq!( 5u8, result );
 }
+
+
+
+// AsyncGlobalBuilder::init fails after tasks were spawned.
+//
+#[ test ]
+//
+fn builder_after_spawn()
+{
+	let handle = AsyncGlobal.spawn_handle( async { 5u8 } ).expect( "spawn" );
+
+	assert_eq!( AsyncGlobal::block_on( handle ), 5 );
+
+	assert_eq!( AsyncGlobalBuilder::new().min_threads( 1 ).init().expect_err( "already started" ), AlreadyStarted );
+}
@@ -0,0 +1,55 @@
+#![ cfg(all( feature = "async_global", not(target_os = "unknown") )) ]
+
+// The global executor can only be configured once per process, so everything is tested in order in one test.
+//
+// Tested:
+//
+// ✔ init starts min_threads threads, where the environment variable overrides min_threads.
+// ✔ the threads are named by thread_name_fn.
+// ✔ init fails when the global executor has already started.
+//
+mod common;
+
+use
+{
+	common :: { *                                           } ,
+	std    :: { sync::atomic::{ AtomicUsize, Ordering::SeqCst } } ,
+};
+
+
+#[ test ]
+//
+fn configure()
+{
+	static NAMED: AtomicUsize = AtomicUsize::new( 0 );
+
+	std::env::set_var( "ASYNC_EXECUTORS_TEST_THREADS", "3" );
+
+	let exec = AsyncGlobalBuilder::new()
+
+		.env_var       ( "ASYNC_EXECUTORS_TEST_THREADS" )
+		.min_threads   ( 1 )
+		.max_threads   ( 3 )
+		.thread_name_fn( || format!( "configured-{}", NAMED.fetch_add( 1, SeqCst ) ) )
+		.init()
+		.expect( "configure async-global-executor" )
+	;
+
+	// init starts min_threads threads, where the environment variable overrides min_threads.
+	//
+	assert_eq!( NAMED.load( SeqCst ), 3 );
+
+	// the threads are named by thread_name_fn.
+	//
+	let name = exec.spawn_handle( async { std::thread::current().name().map( str::to_string ) } ).expect( "spawn" );
+	let name = AsyncGlobal::block_on( name ).expect( "thread name" );
+
+	assert!( name.starts_with( "configured-" ), "{}", name );
+
+	// init fails when the global executor has already started.
+	//
+	let err = AsyncGlobalBuilder::new().min_threads( 2 ).init().expect_err( "already started" );
+
+	assert_eq!( err, AlreadyStarted );
+	assert_eq!( NAMED.load( SeqCst ), 3 );
+}
@@ -13,6 +13,7 @@
 //
 // ✔ Joinhandle::detach allows task to keep running.
 // ✔ Joinhandle::drop aborts the task.
+// ✔ Joinhandle::drop aborts a local task.
 //
 mod common;
 
@@ -369,3 +370,30 @@ fn spawn_handle_local_os()
 
 	assert_eq!( 5u8, result );
 }
+
+
+
+// Joinhandle::drop aborts a local task.
+//
+#[ async_std::test ]
+//
+async fn join_handle_abort_local()
+{
+	let exec      = AsyncStd::new();
+	let (tx , rx) = oneshot::channel::<()>();
+
+	let join_handle = exec.spawn_handle_local( async move
+	{
+		let _notify = DropNotify{ tx: Some(tx) };
+		let _rc     = Rc::new(());
+
+		let () = futures::future::pending().await;
+
+	}).expect( "spawn task" );
+
+	Delay::new( Duration::from_millis(10) ).await;
+
+	drop( join_handle );
+
+	assert!( rx.await.is_ok() );
+}
@@ -0,0 +1,207 @@
+#![ cfg(all( feature = "localpool", feature = "threadpool" )) ]
+
+// Tested:
+//
+// ✔ the traits fail fast when all slots are taken.
+// ✔ spawn_handle_when_ready waits for a free slot.
+// ✔ dropping the JoinHandle frees the slot.
+// ✔ no more tasks than the limit run concurrently on a threadpool.
+// ✔ dropping a waiting spawn passes the free slot on to the next waiter.
+// ✔ spawning in bulk takes a slot per task and fails when they run out.
+//
+mod common;
+
+use
+{
+	common  :: { *                                                   } ,
+	futures :: { executor::LocalPool, future::join_all, pin_mut      } ,
+	std     :: { sync::atomic::{ AtomicUsize, Ordering::SeqCst }    } ,
+};
+
+
+// the traits fail fast when all slots are taken.
+//
+#[ test ]
+//
+fn fail_fast()
+{
+	let mut pool = LocalPool::new();
+	let exec     = Bounded::new( pool.spawner(), 1 );
+	let (tx, rx) = oneshot::channel::<()>();
+
+	let handle = exec.spawn_handle( async { rx.await.expect( "receive" ); } ).expect( "spawn" );
+
+	assert_eq!( exec.running(), 1 );
+	assert!( exec.spawn( async {} ).is_err() );
+	assert!( exec.spawn_local( async {} ).is_err() );
+	assert!( exec.spawn_handle( async {} ).is_err() );
+
+	tx.send(()).expect( "send" );
+	pool.run_until( handle );
+
+	assert_eq!( exec.running(), 0 );
+	assert!( exec.spawn( async {} ).is_ok() );
+}
+
+
+
+// spawn_handle_when_ready waits for a free slot.
+//
+#[ test ]
+//
+fn when_ready()
+{
+	let mut pool = LocalPool::new();
+	let exec     = Bounded::new( pool.spawner(), 1 );
+	let (tx, rx) = oneshot::channel::<()>();
+
+	exec.spawn( async { rx.await.expect( "receive" ); } ).expect( "spawn" );
+
+	let second = exec.spawn_handle_when_ready( async { 5u8 } );
+	pin_mut!( second );
+
+	pool.run_until_stalled();
+	assert!( (&mut second).now_or_never().is_none() );
+
+	tx.send(()).expect( "send" );
+
+	let handle = pool.run_until( second ).expect( "spawn" );
+
+	assert_eq!( pool.run_until( handle ), 5 );
+}
+
+
+
+// dropping the JoinHandle frees the slot.
+//
+#[ test ]
+//
+fn drop_handle()
+{
+	let mut pool = LocalPool::new();
+	let exec     = Bounded::new( pool.spawner(), 1 );
+
+	let handle = exec.spawn_handle( futures::future::pending::<()>() ).expect( "spawn" );
+
+	drop( handle );
+	pool.run_until_stalled();
+
+	assert_eq!( exec.running(), 0 );
+	assert!( exec.spawn( async {} ).is_ok() );
+}
+
+
+
+// no more tasks than the limit run concurrently on a threadpool.
+//
+#[ test ]
+//
+fn limit_threadpool()
+{
+	let exec    = Bounded::new( ThreadPool::new().expect( "create threadpool" ), 3 );
+	let current = Arc::new( AtomicUsize::new(0) );
+	let max     = Arc::new( AtomicUsize::new(0) );
+
+	block_on( async
+	{
+		let mut handles = Vec::new();
+
+		for _ in 0..50
+		{
+			let current = current.clone();
+			let max     = max.clone();
+
+			let handle = exec.spawn_handle_when_ready( async move
+			{
+				let now = current.fetch_add( 1, SeqCst ) + 1;
+				max.fetch_max( now, SeqCst );
+
+				std::thread::sleep( std::time::Duration::from_millis(1) );
+
+				current.fetch_sub( 1, SeqCst );
+
+			}).await.expect( "spawn" );
+
+			handles.push( handle );
+		}
+
+		join_all( handles ).await;
+	});
+
+	assert!( max.load( SeqCst ) <= 3 );
+	assert_eq!( exec.running(), 0 );
+}
+
+
+
+// dropping a waiting spawn passes the free slot on to the next waiter.
+//
+#[ test ]
+//
+fn drop_waiter()
+{
+	let mut pool = LocalPool::new();
+	let exec     = Bounded::new( pool.spawner(), 1 );
+	let (tx, rx) = oneshot::channel::<()>();
+
+	exec.spawn( async { rx.await.expect( "receive" ); } ).expect( "spawn" );
+
+	let first  = exec.spawn_handle_when_ready( async { 1u8 } ).boxed_local();
+	let second = exec.spawn_handle_when_ready( async { 2u8 } );
+	pin_mut!( second );
+
+	let mut first = first;
+
+	assert!( (&mut first ).now_or_never().is_none() );
+	assert!( (&mut second).now_or_never().is_none() );
+
+	tx.send(()).expect( "send" );
+	pool.run_until_stalled();
+
+	// first has been woken up for the free slot, but it's no longer interested.
+	//
+	drop( first );
+
+	let handle = pool.run_until( second ).expect( "spawn" );
+
+	assert_eq!( pool.run_until( handle ), 2 );
+}
+
+
+
+// spawning in bulk takes a slot per task and fails when they run out.
+//
+#[ test ]
+//
+fn spawn_many()
+{
+	let mut pool = LocalPool::new();
+	let exec     = Bounded::new( pool.spawner(), 3 );
+	let count    = Arc::new( AtomicUsize::new(0) );
+
+	let result = exec.spawn_many( (0..5).map( |_|
+	{
+		let count = count.clone();
+		async move { count.fetch_add( 1, SeqCst ); }
+	}));
+
+	assert!( result.is_err() );
+	assert_eq!( exec.running(), 3 );
+
+	pool.run();
+
+	assert_eq!( count.load( SeqCst ), 3 );
+	assert_eq!( exec.running(), 0 );
+
+	let handles = exec.spawn_handle_many( (0..3u8).map( |i| async move { i } ) ).expect( "spawn" );
+
+	assert_eq!( pool.run_until( join_all( handles ) ), vec![ 0, 1, 2 ] );
+
+	// The handles of the tasks that got a slot are dropped, which frees the slots again.
+	//
+	assert!( exec.spawn_handle_many( (0..4u8).map( |i| async move { i } ) ).is_err() );
+
+	pool.run_until_stalled();
+
+	assert_eq!( exec.running(), 0 );
+}
@@ -0,0 +1,206 @@
+#![ cfg(all( feature = "localpool", feature = "threadpool" )) ]
+
+// Tested:
+//
+// ✔ cancelling a parent cancels all descendants, but not the other way around.
+// ✔ a child of a cancelled token starts out cancelled.
+// ✔ cancelled() wakes up tasks waiting on it.
+// ✔ a task that finishes before cancellation resolves to it's output.
+// ✔ a task that cleans up within the grace period resolves to it's output.
+// ✔ a task that ignores the token is aborted and dropped after the grace period, with MockClock.
+// ✔ a !Send task on TokioCt is aborted when the token is cancelled.
+// ✔ an executor without a Timer aborts the task when the grace future resolves.
+//
+mod common;
+
+use
+{
+	common  :: { *                                                } ,
+	futures :: { executor::LocalPool, future::{ pending, ready }  } ,
+	std     :: { time::Duration                                   } ,
+};
+
+
+// cancelling a parent cancels all descendants, but not the other way around.
+//
+#[ test ]
+//
+fn tree()
+{
+	let root       = CancelToken::new();
+	let child      = root.child();
+	let grandchild = child.child();
+	let sibling    = root.child();
+
+	grandchild.cancel();
+
+	assert!(  grandchild.is_cancelled() );
+	assert!( !child     .is_cancelled() );
+
+	child.cancel();
+
+	assert!(  child  .is_cancelled() );
+	assert!( !root   .is_cancelled() );
+	assert!( !sibling.is_cancelled() );
+
+	root.cancel();
+
+	assert!( sibling.is_cancelled() );
+}
+
+
+
+// a child of a cancelled token starts out cancelled.
+//
+#[ test ]
+//
+fn child_of_cancelled()
+{
+	let root = CancelToken::new();
+
+	root.cancel();
+
+	assert!( root.child().is_cancelled() );
+	assert!( root.child().cancelled().now_or_never().is_some() );
+}
+
+
+
+// cancelled() wakes up tasks waiting on it.
+//
+#[ test ]
+//
+fn wake()
+{
+	let exec   = ThreadPool::new().expect( "create threadpool" );
+	let root   = CancelToken::new();
+	let child  = root.child();
+
+	let handle = exec.spawn_handle( async move { child.child().cancelled().await; 5u8 } ).expect( "spawn" );
+
+	root.cancel();
+
+	assert_eq!( block_on( handle ), 5 );
+}
+
+
+
+// a task that finishes before cancellation resolves to it's output.
+//
+#[ test ]
+//
+fn finishes()
+{
+	let exec   = ThreadPool::new().expect( "create threadpool" );
+	let token  = CancelToken::new();
+	let handle = exec.spawn_handle_with_token( async { 5u8 }, &token, ready(()) ).expect( "spawn" );
+
+	assert_eq!( block_on( handle ), Ok(5) );
+}
+
+
+
+// a task that cleans up within the grace period resolves to it's output.
+//
+#[ test ]
+//
+fn graceful()
+{
+	let exec      = ThreadPool::new().expect( "create threadpool" );
+	let token     = CancelToken::new();
+	let inner     = token.child();
+	let (tx, rx)  = oneshot::channel::<()>();
+
+	let handle = exec.spawn_handle_with_token( async move
+	{
+		inner.cancelled().await;
+
+		// Cleanup that needs to await something.
+		//
+		rx.await.expect( "receive" );
+		"cleaned up"
+
+	}, &token, exec.sleep( Duration::from_secs( 60 ) ) ).expect( "spawn" );
+
+	token.cancel();
+	tx.send(()).expect( "send" );
+
+	assert_eq!( block_on( handle ), Ok( "cleaned up" ) );
+}
+
+
+
+// a task that ignores the token is aborted and dropped after the grace period, with MockClock.
+//
+#[ test ]
+//
+fn aborted()
+{
+	let clock            = MockClock::new();
+	let mut pool         = LocalPool::new();
+	let exec             = clock.track( pool.spawner() );
+	let timer            = clock.clone();
+	let token            = CancelToken::new();
+	let (guard, drop_rx) = oneshot::channel::<()>();
+
+	clock.set_auto_advance( true );
+
+	let handle = exec.spawn_handle_local_with_token( async move
+	{
+		let _guard = guard;
+		pending::<()>().await;
+
+	}, &token, async move { timer.sleep( Duration::from_secs( 10 ) ).await } ).expect( "spawn" );
+
+	pool.run_until_stalled();
+	assert_eq!( clock.elapsed(), Duration::ZERO );
+
+	token.cancel();
+
+	assert_eq!( pool.run_until( handle ), Err( Cancelled ) );
+	assert_eq!( clock.elapsed(), Duration::from_secs( 10 ) );
+	assert!( pool.run_until( drop_rx ).is_err() );
+}
+
+
+
+// a !Send task on TokioCt is aborted when the token is cancelled.
+//
+#[ cfg( feature = "tokio_ct" ) ]
+//
+#[ test ]
+//
+fn tokio_ct()
+{
+	let exec  = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let token = CancelToken::new();
+
+	let handle = exec.spawn_handle_local_with_token( async
+	{
+		let _not_send = Rc::new(());
+		pending::<()>().await;
+
+	}, &token, exec.sleep( Duration::from_millis( 10 ) ) ).expect( "spawn" );
+
+	token.cancel();
+
+	assert_eq!( exec.block_on( handle ), Err( Cancelled ) );
+}
+
+
+
+// an executor without a Timer aborts the task when the grace future resolves.
+//
+#[ test ]
+//
+fn without_timer()
+{
+	let exec  = WithHandle::new( Box::new( ThreadPool::new().expect( "create threadpool" ) ) as Box<dyn Spawn + Send + Sync> );
+	let token = CancelToken::new();
+
+	let handle = exec.spawn_handle_with_token( pending::<()>(), &token, ready(()) ).expect( "spawn" );
+
+	token.cancel();
+
+	assert_eq!( block_on( handle ), Err( Cancelled ) );
+}
@@ -0,0 +1,127 @@
+#![ cfg(all( feature = "conformance", not(target_os = "unknown") )) ]
+
+// Tested:
+//
+// ✔ run the conformance suite on every executor that is enabled.
+//
+use
+{
+	async_executors :: { *                  } ,
+	futures         :: { executor::block_on } ,
+};
+
+
+#[ cfg( feature = "tokio_ct" ) ]
+//
+#[ test ]
+//
+fn tokio_ct()
+{
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	exec.block_on( conformance::run_all      ( &exec ) );
+	exec.block_on( conformance::run_all_local( &exec ) );
+}
+
+
+
+#[ cfg( feature = "tokio_tp" ) ]
+//
+#[ test ]
+//
+fn tokio_tp()
+{
+	let exec = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
+
+	exec.block_on( conformance::run_all( &exec ) );
+}
+
+
+
+#[ cfg( feature = "async_std" ) ]
+//
+#[ test ]
+//
+fn async_std()
+{
+	AsyncStd::block_on( conformance::run_all      ( &AsyncStd ) );
+	AsyncStd::block_on( conformance::run_all_local( &AsyncStd ) );
+}
+
+
+
+#[ cfg( feature = "async_global" ) ]
+//
+#[ test ]
+//
+fn async_global()
+{
+	AsyncGlobal::block_on( conformance::run_all      ( &AsyncGlobal ) );
+	AsyncGlobal::block_on( conformance::run_all_local( &AsyncGlobal ) );
+}
+
+
+
+#[ cfg( feature = "glommio" ) ]
+//
+#[ test ]
+//
+fn glommio_ct()
+{
+	let exec = GlommioCt::new( glommio_crate::LocalExecutorBuilder::new() ).expect( "create glommio executor" );
+
+	exec.block_on( conformance::run_all      ( &exec ) );
+	exec.block_on( conformance::run_all_local( &exec ) );
+}
+
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+#[ test ]
+//
+fn localpool()
+{
+	let mut pool = LocalPool::new();
+	let exec     = pool.spawner();
+
+	pool.run_until( conformance::run_all      ( &exec ) );
+	pool.run_until( conformance::run_all_local( &exec ) );
+}
+
+
+
+#[ cfg( feature = "threadpool" ) ]
+//
+#[ test ]
+//
+fn threadpool()
+{
+	let exec = ThreadPool::new().expect( "create threadpool" );
+
+	block_on( conformance::run_all( &exec ) );
+}
+
+
+
+#[ cfg( feature = "rayon" ) ]
+//
+#[ test ]
+//
+fn rayon()
+{
+	block_on( conformance::run_all( &RayonExec::global() ) );
+}
+
+
+
+#[ cfg( feature = "native" ) ]
+//
+#[ test ]
+//
+fn native()
+{
+	let exec = NativeBuilder::new().build().expect( "create native executor" );
+
+	exec.block_on( conformance::run_all( &exec ) );
+}
@@ -0,0 +1,160 @@
+#![ cfg(all( feature = "localpool", feature = "threadpool" )) ]
+
+// Tested:
+//
+// ✔ a task that finishes in time resolves to it's output.
+// ✔ a task that misses it's deadline resolves to DeadlineExceeded and get's dropped.
+// ✔ a !Send task that misses it's deadline on TokioCt, with the tokio timer.
+// ✔ deadlines work with virtual time from MockClock.
+// ✔ Deadlined applies the budget to spawn_handle_deadlined.
+// ✔ Deadlined drops tasks spawned with Spawn when they run out of time.
+// ✔ Deadlined applies the budget to spawn_handle_local_deadlined.
+//
+mod common;
+
+use
+{
+	common  :: { *                                                } ,
+	futures :: { executor::LocalPool, future::pending             } ,
+	std     :: { time::Duration                                   } ,
+};
+
+
+// a task that finishes in time resolves to it's output.
+//
+#[ test ]
+//
+fn in_time()
+{
+	let exec     = ThreadPool::new().expect( "create threadpool" );
+	let deadline = exec.now() + Duration::from_secs( 60 );
+	let handle   = exec.spawn_handle_with_deadline( async { 5u8 }, deadline ).expect( "spawn" );
+
+	assert_eq!( block_on( handle ), Ok(5) );
+}
+
+
+
+// a task that misses it's deadline resolves to DeadlineExceeded and get's dropped.
+//
+#[ test ]
+//
+fn exceeded()
+{
+	let exec             = ThreadPool::new().expect( "create threadpool" );
+	let deadline         = exec.now() + Duration::from_millis( 10 );
+	let (guard, drop_rx) = oneshot::channel::<()>();
+
+	let handle = exec.spawn_handle_with_deadline( async move
+	{
+		let _guard = guard;
+		pending::<()>().await;
+
+	}, deadline ).expect( "spawn" );
+
+	assert_eq!( block_on( handle ), Err( DeadlineExceeded ) );
+	assert!( block_on( drop_rx ).is_err() );
+}
+
+
+
+// a !Send task that misses it's deadline on TokioCt, with the tokio timer.
+//
+#[ cfg( feature = "tokio_ct" ) ]
+//
+#[ test ]
+//
+fn exceeded_tokio_ct()
+{
+	let exec     = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let deadline = exec.now() + Duration::from_millis( 10 );
+
+	let handle = exec.spawn_handle_local_with_deadline( async
+	{
+		let _not_send = Rc::new(());
+		pending::<()>().await;
+
+	}, deadline ).expect( "spawn" );
+
+	assert_eq!( exec.block_on( handle ), Err( DeadlineExceeded ) );
+}
+
+
+
+// deadlines work with virtual time from MockClock.
+//
+#[ test ]
+//
+fn mock_clock()
+{
+	let clock    = MockClock::new();
+	let mut pool = LocalPool::new();
+	let exec     = clock.track( pool.spawner() );
+
+	clock.set_auto_advance( true );
+
+	let deadline = exec.now() + Duration::from_secs( 3600 );
+	let handle   = exec.spawn_handle_local_with_deadline( pending::<()>(), deadline ).expect( "spawn" );
+
+	assert_eq!( pool.run_until( handle ), Err( DeadlineExceeded ) );
+	assert_eq!( clock.elapsed(), Duration::from_secs( 3600 ) );
+}
+
+
+
+// Deadlined applies the budget to spawn_handle_deadlined.
+//
+#[ test ]
+//
+fn deadlined()
+{
+	let exec  = Deadlined::new( ThreadPool::new().expect( "create threadpool" ), Duration::from_millis( 10 ) );
+	let quick = exec.spawn_handle_deadlined( async { 5u8 }   ).expect( "spawn" );
+	let slow  = exec.spawn_handle_deadlined( pending::<u8>() ).expect( "spawn" );
+
+	assert_eq!( block_on( quick ), Ok(5)                  );
+	assert_eq!( block_on( slow  ), Err( DeadlineExceeded ) );
+}
+
+
+
+// Deadlined drops tasks spawned with Spawn when they run out of time.
+//
+#[ test ]
+//
+fn deadlined_spawn()
+{
+	let exec             = Deadlined::new( ThreadPool::new().expect( "create threadpool" ), Duration::from_millis( 10 ) );
+	let (guard, drop_rx) = oneshot::channel::<()>();
+
+	exec.spawn( async move
+	{
+		let _guard = guard;
+		pending::<()>().await;
+
+	}).expect( "spawn" );
+
+	assert!( block_on( drop_rx ).is_err() );
+}
+
+
+
+// Deadlined applies the budget to spawn_handle_local_deadlined.
+//
+#[ test ]
+//
+fn deadlined_local()
+{
+	let clock    = MockClock::new();
+	let mut pool = LocalPool::new();
+	let exec     = Deadlined::new( clock.track( pool.spawner() ), Duration::from_secs( 10 ) );
+
+	clock.set_auto_advance( true );
+
+	let quick = exec.spawn_handle_local_deadlined( async { Rc::new( 5u8 ) } ).expect( "spawn" );
+	let slow  = exec.spawn_handle_local_deadlined( pending::<()>()           ).expect( "spawn" );
+
+	assert_eq!( pool.run_until( quick ).map( |out| *out ), Ok(5)                  );
+	assert_eq!( pool.run_until( slow  )                  , Err( DeadlineExceeded ) );
+	assert_eq!( clock.elapsed(), Duration::from_secs( 10 ) );
+}
@@ -0,0 +1,202 @@
+#![ cfg(all( feature = "tokio_tp", feature = "tokio_ct", not(target_os = "unknown") )) ]
+
+// Tested:
+//
+// ✔ JoinHandles can be awaited from TokioCt, AsyncStd and futures block_on.
+// ✔ a CPU heavy job doesn't keep the tasks of the IO executor from running.
+// ✔ a panicking job doesn't take down the workers and spawn_catch_unwind returns the panic.
+// ✔ shutdown waits for running jobs and spawning fails afterwards.
+// ✔ shutdown_timeout cancels jobs that are still running after the timeout.
+// ✔ the executor can be dropped from within an async context.
+//
+mod common;
+
+use
+{
+	common  :: { *                                             } ,
+	futures :: { future::{ pending, join }                     } ,
+	std     :: { sync::atomic::{ AtomicBool, Ordering::SeqCst } } ,
+	std     :: { thread, time::{ Duration, Instant }           } ,
+	std     :: { panic::{ catch_unwind, AssertUnwindSafe }     } ,
+};
+
+
+fn dedicated() -> DedicatedExec
+{
+	TokioTpBuilder::new().build_dedicated().expect( "create dedicated executor" )
+}
+
+
+
+// JoinHandles can be awaited from TokioCt, AsyncStd and futures block_on.
+//
+#[ test ]
+//
+fn await_anywhere()
+{
+	let exec     = dedicated();
+	let tokio_ct = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	let handle = exec.spawn_handle( async { 5u8 } ).expect( "spawn" );
+	assert_eq!( tokio_ct.block_on( handle ), 5 );
+
+	#[ cfg( feature = "async_std" ) ]
+	{
+		let handle = exec.spawn_handle( async { 6u8 } ).expect( "spawn" );
+		assert_eq!( AsyncStd::block_on( handle ), 6 );
+	}
+
+	let handle = exec.spawn_handle_generic( async { 7u8 } ).expect( "spawn" );
+	assert_eq!( block_on( handle ), 7 );
+}
+
+
+
+// a CPU heavy job doesn't keep the tasks of the IO executor from running.
+//
+#[ test ]
+//
+fn isolated()
+{
+	let exec  = dedicated();
+	let io    = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let done  = Arc::new( AtomicBool::new( false ) );
+	let done2 = done.clone();
+
+	let job = exec.spawn_handle( async move
+	{
+		// Blocks the worker thread.
+		//
+		thread::sleep( Duration::from_millis( 200 ) );
+		done2.store( true, SeqCst );
+
+	}).expect( "spawn" );
+
+	let ticks = io.block_on( async
+	{
+		let ticker = async
+		{
+			let mut ticks = 0;
+
+			while !done.load( SeqCst )
+			{
+				io.sleep( Duration::from_millis( 10 ) ).await;
+				ticks += 1;
+			}
+
+			ticks
+		};
+
+		join( job, ticker ).await.1
+	});
+
+	assert!( ticks >= 5, "the io executor only ticked {} times", ticks );
+}
+
+
+
+// a panicking job doesn't take down the workers and spawn_catch_unwind returns the panic.
+//
+#[ test ]
+//
+fn panic_isolation()
+{
+	let mut builder = TokioTpBuilder::new();
+	builder.tokio_builder().worker_threads( 1 );
+
+	let exec = builder.build_dedicated().expect( "create dedicated executor" );
+
+	let caught = exec.spawn_catch_unwind( async { panic!( "boom" ) } ).expect( "spawn" );
+
+	assert!( block_on( caught ).is_err() );
+
+	// Without catching, awaiting resumes the panic.
+	//
+	let handle = exec.spawn_handle( async { panic!( "boom" ) } ).expect( "spawn" );
+
+	assert!( catch_unwind( AssertUnwindSafe( || block_on( handle ) ) ).is_err() );
+
+	// The only worker is still there.
+	//
+	let handle = exec.spawn_catch_unwind( async { 5u8 } ).expect( "spawn" );
+
+	assert_eq!( block_on( handle ).expect( "no panic" ), 5 );
+}
+
+
+
+// shutdown waits for running jobs and spawning fails afterwards.
+//
+#[ test ]
+//
+fn shutdown()
+{
+	let exec  = dedicated();
+	let done  = Arc::new( AtomicBool::new( false ) );
+	let done2 = done.clone();
+
+	exec.spawn( async move
+	{
+		tokio::time::sleep( Duration::from_millis( 50 ) ).await;
+		done2.store( true, SeqCst );
+
+	}).expect( "spawn" );
+
+	assert!( !exec.is_shutdown() );
+
+	block_on( exec.shutdown() );
+
+	assert!( done.load( SeqCst ) );
+	assert!( exec.is_shutdown()  );
+
+	assert!( exec.spawn( async {} ).is_err()        );
+	assert!( exec.spawn_handle( async {} ).is_err() );
+
+	// Waiting again returns right away.
+	//
+	block_on( exec.clone().shutdown() );
+}
+
+
+
+// shutdown_timeout cancels jobs that are still running after the timeout.
+//
+#[ test ]
+//
+fn shutdown_timeout()
+{
+	let exec             = dedicated();
+	let (guard, dropped) = oneshot::channel::<()>();
+	let start            = Instant::now();
+
+	exec.spawn( async move
+	{
+		let _guard = guard;
+		pending::<()>().await;
+
+	}).expect( "spawn" );
+
+	block_on( exec.shutdown_timeout( Duration::from_millis( 20 ) ) );
+
+	assert!( start.elapsed() >= Duration::from_millis( 20 ) );
+	assert!( block_on( dropped ).is_err() );
+}
+
+
+
+// the executor can be dropped from within an async context.
+//
+#[ test ]
+//
+fn drop_in_async()
+{
+	let io   = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let exec = dedicated();
+
+	io.block_on( async move
+	{
+		assert_eq!( exec.spawn_handle( async { 5u8 } ).expect( "spawn" ).await, 5 );
+
+		drop( exec );
+	});
+}
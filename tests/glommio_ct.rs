@@ -22,6 +22,7 @@
 // ✔ pass a   &GlommioCt  to a function that takes exec: `&dyn LocalSpawnHandle`
 //
 // ✔ Joinhandle::detach allows task to keep running.
+// ✔ GlommioJoinHandle returns the output, cancels on drop, detaches and resumes panics.
 // - Test cpu pinning.
 // - What happens if we make a nested call to block_on
 // - What happens if we call exec constructor again inside block_on.
@@ -422,3 +423,116 @@ fn join_handle_detach()
 			assert_eq!( out_rx.await, Ok(5) );
 	});
 }
+
+
+
+// GlommioJoinHandle returns the output of the task.
+//
+#[ test ]
+//
+fn native_join_handle()
+{
+	let builder      = LocalExecutorBuilder::new();
+	let exec         = &GlommioCt::new( builder ).expect( "create exec" );
+
+	let result = exec.block_on( async move
+	{
+		exec.spawn_handle_native( async { Rc::new( 5u8 ) } ).expect( "spawn task" ).await
+	});
+
+	assert_eq!( *result, 5 );
+}
+
+
+
+// Dropping a GlommioJoinHandle cancels the task.
+//
+#[ test ]
+//
+fn native_join_handle_drop()
+{
+	let builder      = LocalExecutorBuilder::new();
+	let exec         = &GlommioCt::new( builder ).expect( "create exec" );
+
+	let (in_tx   , in_rx   ) = oneshot::channel::<u8>();
+	let (out_tx  , out_rx  ) = oneshot::channel::<u8>();
+	let (guard_tx, guard_rx) = oneshot::channel::<()>();
+
+
+	exec.block_on( async move
+	{
+		let handle = exec.spawn_handle_native( async move
+		{
+			// Will be dropped together with the future.
+			//
+			let _guard = guard_tx;
+
+			let content = in_rx.await.expect( "receive on in" );
+
+			out_tx.send( content ).expect( "send on out" );
+
+		}).expect( "spawn task" );
+
+		drop( handle );
+
+		// Glommio only drops the future of a cancelled task when it runs it again.
+		//
+		assert!( guard_rx.await.is_err() );
+
+		// The task was dropped, so the in_rx is gone.
+		//
+		assert!( in_tx.send( 5 ).is_err() );
+		assert!( out_rx.await.is_err()    );
+	});
+}
+
+
+
+// GlommioJoinHandle::detach allows task to keep running.
+//
+#[ test ]
+//
+fn native_join_handle_detach()
+{
+	let builder      = LocalExecutorBuilder::new();
+	let exec         = &GlommioCt::new( builder ).expect( "create exec" );
+
+	let (in_tx , in_rx ) = oneshot::channel();
+	let (out_tx, out_rx) = oneshot::channel();
+
+
+	exec.block_on( async move
+	{
+		let handle = exec.spawn_handle_native( async move
+		{
+			let content = in_rx.await.expect( "receive on in" );
+
+			out_tx.send( content ).expect( "send on out" );
+
+		}).expect( "spawn task" );
+
+		handle.detach();
+
+		in_tx.send( 5u8 ).expect( "send on in" );
+
+		assert_eq!( out_rx.await, Ok(5) );
+	});
+}
+
+
+
+// Awaiting a GlommioJoinHandle resumes the panic of the task.
+//
+#[ test ]
+#[ should_panic( expected = "boom" ) ]
+//
+fn native_join_handle_panic()
+{
+	let builder      = LocalExecutorBuilder::new();
+	let exec         = &GlommioCt::new( builder ).expect( "create exec" );
+
+	exec.block_on( async move
+	{
+		exec.spawn_handle_native( async { panic!( "boom" ) } ).expect( "spawn task" ).await
+	});
+}
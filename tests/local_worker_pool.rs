@@ -0,0 +1,188 @@
+#![ cfg( feature = "localpool" ) ]
+
+// Tested:
+//
+// ✔ spawn_pinned runs a !Send future and returns it's output.
+// ✔ spawn_pinned_on runs the task on the chosen worker thread.
+// ✔ tasks go to the least loaded worker.
+// ✔ the load goes down when a task finishes or it's JoinHandle is dropped.
+// ✔ Spawn and SpawnHandle send futures to the workers.
+// ✔ a task that panics doesn't take down it's worker.
+// ✔ workers running TokioCt.
+//
+mod common;
+
+use
+{
+	common  :: { *                                                  } ,
+	futures :: { future::pending                                    } ,
+	std     :: { thread, panic::AssertUnwindSafe, time::{ Duration, Instant } } ,
+};
+
+
+// Wait for a condition that becomes true on another thread.
+//
+fn wait_for( cond: impl Fn() -> bool )
+{
+	let start = Instant::now();
+
+	while !cond()
+	{
+		assert!( start.elapsed() < Duration::from_secs( 10 ), "timed out" );
+		thread::sleep( Duration::from_millis(1) );
+	}
+}
+
+
+fn thread_name() -> String
+{
+	thread::current().name().expect( "worker threads have names" ).to_string()
+}
+
+
+
+// spawn_pinned runs a !Send future and returns it's output.
+//
+#[ test ]
+//
+fn spawn_pinned()
+{
+	let pool   = LocalWorkerPool::local_pool( 2 ).expect( "start workers" );
+	let handle = pool.spawn_pinned( || async { *Rc::new( 5u8 ) } ).expect( "spawn" );
+
+	assert_eq!( pool.len(), 2 );
+	assert_eq!( block_on( handle ), 5 );
+}
+
+
+
+// spawn_pinned_on runs the task on the chosen worker thread.
+//
+#[ test ]
+//
+fn pinned_on()
+{
+	let pool = LocalWorkerPool::local_pool( 3 ).expect( "start workers" );
+
+	for i in 0..3
+	{
+		let name = block_on( pool.spawn_pinned_on( i, || async { thread_name() } ).expect( "spawn" ) );
+
+		assert_eq!( name, format!( "async_executors-worker-{}", i ) );
+	}
+}
+
+
+
+// tasks go to the least loaded worker.
+//
+#[ test ]
+//
+fn least_loaded()
+{
+	let pool  = LocalWorkerPool::local_pool( 3 ).expect( "start workers" );
+	let _busy = pool.spawn_pinned_on( 0, pending::<()> ).expect( "spawn" );
+	let _more = pool.spawn_pinned_on( 0, pending::<()> ).expect( "spawn" );
+	let _one  = pool.spawn_pinned_on( 1, pending::<()> ).expect( "spawn" );
+
+	let name = block_on( pool.spawn_pinned( || async { thread_name() } ).expect( "spawn" ) );
+
+	assert_eq!( name, "async_executors-worker-2" );
+	assert_eq!( pool.load(0), 2 );
+	assert_eq!( pool.load(1), 1 );
+}
+
+
+
+// the load goes down when a task finishes or it's JoinHandle is dropped.
+//
+#[ test ]
+//
+fn load()
+{
+	let pool     = LocalWorkerPool::local_pool( 1 ).expect( "start workers" );
+	let (tx, rx) = oneshot::channel::<()>();
+
+	let waiting = pool.spawn_pinned( || async { rx.await.expect( "receive" ) } ).expect( "spawn" );
+	let forever = pool.spawn_pinned( pending::<()> ).expect( "spawn" );
+
+	assert_eq!( pool.load(0), 2 );
+
+	tx.send(()).expect( "send" );
+	block_on( waiting );
+
+	wait_for( || pool.load(0) == 1 );
+
+	drop( forever );
+
+	wait_for( || pool.load(0) == 0 );
+}
+
+
+
+// Spawn and SpawnHandle send futures to the workers.
+//
+#[ test ]
+//
+fn traits()
+{
+	let pool     = LocalWorkerPool::local_pool( 2 ).expect( "start workers" );
+	let (tx, rx) = oneshot::channel();
+
+	pool.spawn( async move { tx.send( thread_name() ).expect( "send" ); } ).expect( "spawn" );
+
+	assert!( block_on( rx ).expect( "receive" ).starts_with( "async_executors-worker-" ) );
+	assert_eq!( block_on( pool.spawn_handle( async { 5u8 } ).expect( "spawn" ) ), 5 );
+}
+
+
+
+// a task that panics doesn't take down it's worker.
+//
+#[ test ]
+//
+fn panic()
+{
+	let pool   = LocalWorkerPool::local_pool( 1 ).expect( "start workers" );
+	let handle = pool.spawn_pinned( || async { if *Rc::new( true ) { panic!( "pinned panic" ); } } ).expect( "spawn" );
+
+	let panic = block_on( AssertUnwindSafe( handle ).catch_unwind() ).expect_err( "the task panicked" );
+
+	assert_eq!( panic.downcast_ref::<&str>(), Some( &"pinned panic" ) );
+
+	pool.spawn( async { panic!( "detached panic" ) } ).expect( "spawn" );
+
+	wait_for( || pool.load(0) == 0 );
+
+	let handle = pool.spawn_pinned( || async { *Rc::new( 5u8 ) } ).expect( "spawn" );
+
+	assert_eq!( block_on( handle ), 5 );
+}
+
+
+
+// workers running TokioCt.
+//
+#[ cfg( feature = "tokio_ct" ) ]
+//
+#[ test ]
+//
+fn tokio_ct()
+{
+	let pool = LocalWorkerPool::tokio_ct( 2 ).expect( "start workers" );
+
+	let handle = pool.spawn_pinned( ||
+	{
+		let exec = tokio::runtime::Handle::current();
+
+		async move
+		{
+			tokio::time::sleep( Duration::from_millis(1) ).await;
+			drop( exec );
+			*Rc::new( 5u8 )
+		}
+
+	}).expect( "spawn" );
+
+	assert_eq!( block_on( handle ), 5 );
+}
@@ -0,0 +1,90 @@
+#![ cfg(all( feature = "macros", not(target_os = "unknown") )) ]
+
+// Tested:
+//
+// ✔ test(all) runs a test that takes the executor on every enabled executor.
+// ✔ test on a list of executors, spawning !Send futures.
+// ✔ test without the executor parameter.
+// ✔ other attributes are copied to the generated tests.
+// ✔ main runs the function on the executor and returns it's output.
+//
+use
+{
+	async_executors :: { SpawnHandle, SpawnHandleExt, LocalSpawnHandle, LocalSpawnHandleExt, Timer } ,
+	futures         :: { channel::oneshot                                                      } ,
+	std             :: { rc::Rc, time::Duration                                                } ,
+};
+
+
+// test(all) runs a test that takes the executor on every enabled executor.
+//
+#[ async_executors::macros::test( all ) ]
+//
+async fn spawn_handle( exec: impl SpawnHandle<u8> + Timer )
+{
+	let handle = exec.spawn_handle( async { 5 } ).expect( "spawn" );
+
+	exec.sleep( Duration::from_millis(1) ).await;
+
+	assert_eq!( 5, handle.await );
+}
+
+
+// test on a list of executors, spawning !Send futures.
+//
+#[ async_executors::macros::test( tokio_ct, localpool, glommio ) ]
+//
+async fn spawn_handle_local( exec: impl LocalSpawnHandle< Rc<u8> > )
+{
+	let out = exec.spawn_handle_local( async { Rc::new( 5 ) } ).expect( "spawn" ).await;
+
+	assert_eq!( 5, *out );
+}
+
+
+// test without the executor parameter.
+//
+#[ async_executors::macros::test( all ) ]
+//
+async fn no_param()
+{
+	let (tx, rx) = oneshot::channel();
+
+	tx.send( 5u8 ).expect( "send" );
+
+	assert_eq!( Ok(5), rx.await );
+}
+
+
+// other attributes are copied to the generated tests.
+//
+#[ async_executors::macros::test( tokio_ct, async_std ) ]
+#[ should_panic( expected = "copied" ) ]
+//
+async fn should_panic()
+{
+	panic!( "attributes are copied" );
+}
+
+
+
+// main runs the function on the executor and returns it's output.
+//
+#[ cfg( feature = "tokio_ct" ) ]
+//
+#[ async_executors::macros::main( exec = "tokio_ct" ) ]
+//
+async fn run( exec: async_executors::TokioCt ) -> u8
+{
+	exec.spawn_handle( async { 5 } ).expect( "spawn" ).await
+}
+
+
+#[ cfg( feature = "tokio_ct" ) ]
+//
+#[ test ]
+//
+fn main()
+{
+	assert_eq!( 5, run() );
+}
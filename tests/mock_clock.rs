@@ -0,0 +1,224 @@
+// Tested:
+//
+// ✔ sleeps only resolve when the clock is advanced manually.
+// ✔ now() follows virtual time.
+// ✔ auto advance jumps to the next deadline when no tracked task is busy.
+// ✔ auto advance waits while a tracked task is still busy.
+// ✔ a timeout in a spawned task can be tested without sleeping.
+// ✔ dropping a sleep removes it from the clock.
+//
+mod common;
+
+use
+{
+	common  :: { *                                                                        } ,
+	futures :: { executor::LocalPool, future::{ select, Either }, StreamExt, channel::mpsc } ,
+	std     :: { time::Duration                                                           } ,
+};
+
+
+// sleeps only resolve when the clock is advanced manually.
+//
+#[ test ]
+//
+fn manual_advance()
+{
+	let clock    = MockClock::new();
+	let mut pool = LocalPool::new();
+	let exec     = pool.spawner();
+	let (tx, rx) = oneshot::channel();
+	let sleep    = clock.sleep( Duration::from_secs(5) );
+
+	exec.spawn_local( async move
+	{
+		sleep.await;
+		tx.send(()).expect( "send" );
+
+	}).expect( "spawn" );
+
+	pool.run_until_stalled();
+	clock.advance( Duration::from_secs(4) );
+	pool.run_until_stalled();
+
+	let mut rx = rx;
+	assert!( rx.try_recv().expect( "not canceled" ).is_none() );
+
+	clock.advance( Duration::from_secs(1) );
+	pool.run_until_stalled();
+
+	assert!( rx.try_recv().expect( "not canceled" ).is_some() );
+}
+
+
+
+// now() follows virtual time.
+//
+#[ test ]
+//
+fn now()
+{
+	let clock = MockClock::new();
+	let start = clock.now();
+
+	clock.advance( Duration::from_millis(300) );
+
+	assert_eq!( clock.now() - start , Duration::from_millis(300) );
+	assert_eq!( clock.elapsed()     , Duration::from_millis(300) );
+}
+
+
+
+// auto advance jumps to the next deadline when no tracked task is busy.
+//
+#[ test ]
+//
+fn auto_advance()
+{
+	let clock    = MockClock::new();
+	let mut pool = LocalPool::new();
+	let exec     = clock.track( pool.spawner() );
+	let (tx, rx) = oneshot::channel();
+	let timer    = exec.clone();
+
+	clock.set_auto_advance( true );
+
+	exec.spawn_local( async move
+	{
+		timer.sleep( Duration::from_secs(10) ).await;
+		timer.sleep( Duration::from_secs(20) ).await;
+
+		tx.send( timer.clock().elapsed() ).expect( "send" );
+
+	}).expect( "spawn" );
+
+	let elapsed = pool.run_until( rx ).expect( "receive" );
+
+	assert_eq!( elapsed, Duration::from_secs(30) );
+}
+
+
+
+// auto advance waits while a tracked task is still busy. The busy task ping pongs with a second one
+// for a while, which keeps them scheduled, so the sleep should not resolve before they are done.
+//
+#[ test ]
+//
+fn auto_advance_waits_for_busy()
+{
+	let clock          = MockClock::new();
+	let mut pool       = LocalPool::new();
+	let exec           = clock.track( pool.spawner() );
+	let (tx , mut rx ) = mpsc::unbounded();
+	let (ptx, mut prx) = mpsc::unbounded::<u32>();
+	let (qtx, mut qrx) = mpsc::unbounded::<u32>();
+
+	let tx2   = tx.clone();
+	let timer = exec.clone();
+
+	exec.spawn_local( async move
+	{
+		timer.sleep( Duration::from_secs(1) ).await;
+		tx2.unbounded_send( "timer" ).expect( "send" );
+
+	}).expect( "spawn" );
+
+
+	let ptx2 = ptx.clone();
+
+	exec.spawn_local( async move
+	{
+		while let Some(i) = qrx.next().await
+		{
+			if i >= 100 { break; }
+			ptx2.unbounded_send( i + 1 ).expect( "send" );
+		}
+
+		tx.unbounded_send( "pingpong" ).expect( "send" );
+
+	}).expect( "spawn" );
+
+
+	exec.spawn_local( async move
+	{
+		while let Some(i) = prx.next().await
+		{
+			qtx.unbounded_send( i + 1 ).expect( "send" );
+		}
+
+	}).expect( "spawn" );
+
+	clock.set_auto_advance( true );
+	ptx.unbounded_send( 0 ).expect( "send" );
+	drop( ptx );
+
+	let first = pool.run_until( rx.next() );
+
+	assert_eq!( first, Some( "pingpong" ) );
+}
+
+
+
+// a timeout in a spawned task can be tested without sleeping.
+//
+#[ test ]
+//
+fn timeout()
+{
+	let clock    = MockClock::new();
+	let mut pool = LocalPool::new();
+	let exec     = clock.track( pool.spawner() );
+	let timer    = exec.clone();
+
+	clock.set_auto_advance( true );
+
+	let (tx, rx) = oneshot::channel::<()>();
+
+	let res = pool.run_until( async move
+	{
+		let (out_tx, out_rx) = oneshot::channel();
+
+		exec.spawn_local( async move
+		{
+			// Never completes, as we don't send on tx.
+			//
+			let res = match select( rx, timer.sleep( Duration::from_secs(60) ) ).await
+			{
+				Either::Left (_) => "received",
+				Either::Right(_) => "timed out",
+			};
+
+			out_tx.send( res ).expect( "send" );
+
+		}).expect( "spawn" );
+
+		out_rx.await.expect( "receive" )
+	});
+
+	drop( tx );
+
+	assert_eq!( res, "timed out" );
+	assert_eq!( clock.elapsed(), Duration::from_secs(60) );
+}
+
+
+
+// dropping a sleep removes it from the clock, so auto advance doesn't jump to it's deadline.
+//
+#[ test ]
+//
+fn drop_sleep()
+{
+	let clock    = MockClock::new();
+	let mut long = clock.sleep( Duration::from_secs(100) );
+
+	// register it.
+	//
+	assert!( (&mut long).now_or_never().is_none() );
+	drop( long );
+
+	clock.set_auto_advance( true );
+
+	block_on( clock.sleep( Duration::from_secs(1) ) );
+
+	assert_eq!( clock.elapsed(), Duration::from_secs(1) );
+}
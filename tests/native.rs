@@ -0,0 +1,343 @@
+#![ cfg(all( feature = "native", not(target_os = "unknown") )) ]
+
+// Tested:
+//
+// ✔ the builder sets the number of threads, their names and runs the start and stop hooks.
+// ✔ building with 0 threads fails.
+// ✔ pass a Native to functions that take `impl Spawn`, `impl SpawnHandle` and `&dyn SpawnHandle`.
+// ✔ dropping the JoinHandle cancels the task and detach lets it run.
+// ✔ a panicking task doesn't take down the worker and the panic is resumed when awaiting the JoinHandle.
+// ✔ idle workers steal tasks spawned from another worker.
+// ✔ dropping the executor drops unfinished tasks.
+// ✔ block_on panics on a worker thread.
+// ✔ spawning in bulk, from outside the pool and from a worker, keeps the order of the handles.
+// ✔ a task that wakes itself during a long poll doesn't keep another worker waiting.
+//
+mod common;
+
+use
+{
+	common  :: { *                                                      } ,
+	futures :: { channel::mpsc, future::{ pending, join_all }, StreamExt } ,
+	futures :: { future::poll_fn                                        } ,
+	std     :: { collections::HashSet, sync::Mutex, thread              } ,
+	std     :: { sync::atomic::{ AtomicUsize, Ordering::SeqCst }        } ,
+	std     :: { panic::{ catch_unwind, AssertUnwindSafe }              } ,
+	std     :: { time::{ Duration, Instant }, task::Poll                } ,
+};
+
+
+fn wait_for( cond: impl Fn() -> bool )
+{
+	let start = Instant::now();
+
+	while !cond()
+	{
+		assert!( start.elapsed() < Duration::from_secs( 10 ), "timed out" );
+		thread::sleep( Duration::from_millis(1) );
+	}
+}
+
+
+fn native( threads: usize ) -> Native
+{
+	NativeBuilder::new().threads( threads ).build().expect( "create native executor" )
+}
+
+
+
+// the builder sets the number of threads, their names and runs the start and stop hooks.
+//
+#[ test ]
+//
+fn builder()
+{
+	let started = Arc::new( AtomicUsize::new( 0 ) );
+	let stopped = Arc::new( AtomicUsize::new( 0 ) );
+	let (s1, s2) = ( started.clone(), stopped.clone() );
+
+	let exec = NativeBuilder::new()
+
+		.threads        ( 3 )
+		.thread_name_fn ( |i| format!( "native-test-{}", i ) )
+		.stack_size     ( 256 * 1024 )
+		.on_thread_start( move || { s1.fetch_add( 1, SeqCst ); } )
+		.on_thread_stop ( move || { s2.fetch_add( 1, SeqCst ); } )
+		.build()
+		.expect( "create native executor" )
+	;
+
+	assert_eq!( exec.threads(), 3 );
+
+	wait_for( || started.load( SeqCst ) == 3 );
+
+	let name = exec.spawn_handle( async { thread::current().name().map( str::to_string ) } ).expect( "spawn" );
+	let name = exec.block_on( name ).expect( "thread name" );
+
+	assert!( name.starts_with( "native-test-" ), "{}", name );
+	assert_eq!( stopped.load( SeqCst ), 0 );
+
+	drop( exec );
+
+	wait_for( || stopped.load( SeqCst ) == 3 );
+}
+
+
+
+// building with 0 threads fails.
+//
+#[ test ]
+//
+fn zero_threads()
+{
+	assert!( NativeBuilder::new().threads( 0 ).build().is_err() );
+}
+
+
+
+// pass a Native to functions that take `impl Spawn`, `impl SpawnHandle` and `&dyn SpawnHandle`.
+//
+#[ test ]
+//
+fn traits()
+{
+	let exec         = native( 2 );
+	let (tx, mut rx) = mpsc::channel( 1 );
+
+	increment( 4, exec.clone(), tx );
+
+	assert_eq!( exec.block_on( rx.next() ), Some(5) );
+	assert_eq!( exec.block_on( increment_spawn_handle   ( 4, exec.clone() ) ), 5 );
+	assert_eq!( exec.block_on( increment_spawn_handle_os( 4, &exec        ) ), 5 );
+
+	let handle = exec.spawn_handle_generic( async { "five".to_string() } ).expect( "spawn" );
+
+	assert_eq!( exec.block_on( handle ), "five" );
+}
+
+
+
+// dropping the JoinHandle cancels the task and detach lets it run.
+//
+#[ test ]
+//
+fn join_handle()
+{
+	let exec             = native( 2 );
+	let (guard, dropped) = oneshot::channel::<()>();
+
+	let handle = exec.spawn_handle( async move
+	{
+		let _guard = guard;
+		pending::<()>().await;
+
+	}).expect( "spawn" );
+
+	drop( handle );
+
+	assert!( exec.block_on( dropped ).is_err() );
+
+	let (in_tx , in_rx ) = oneshot::channel::<u8>();
+	let (out_tx, out_rx) = oneshot::channel::<u8>();
+
+	exec.spawn_handle( async move
+	{
+		out_tx.send( in_rx.await.expect( "receive" ) ).expect( "send" );
+
+	}).expect( "spawn" ).detach();
+
+	in_tx.send( 5 ).expect( "send" );
+
+	assert_eq!( exec.block_on( out_rx ), Ok(5) );
+}
+
+
+
+// a panicking task doesn't take down the worker and the panic is resumed when awaiting the JoinHandle.
+//
+#[ test ]
+//
+fn panic()
+{
+	let exec = native( 1 );
+
+	exec.spawn( async { panic!( "boom" ) } ).expect( "spawn" );
+
+	let handle = exec.spawn_handle( async { panic!( "boom" ) } ).expect( "spawn" );
+
+	assert!( catch_unwind( AssertUnwindSafe( || exec.block_on( handle ) ) ).is_err() );
+
+	// The only worker is still there.
+	//
+	let handle = exec.spawn_handle( async { 5u8 } ).expect( "spawn" );
+
+	assert_eq!( exec.block_on( handle ), 5 );
+}
+
+
+
+// idle workers steal tasks spawned from another worker.
+//
+#[ test ]
+//
+fn work_stealing()
+{
+	let exec  = native( 4 );
+	let names = Arc::new( Mutex::new( HashSet::new() ) );
+	let exec2 = exec.clone();
+	let names2 = names.clone();
+
+	// Tasks spawned from a worker go to it's own queue.
+	//
+	let parent = exec.spawn_handle( async move
+	{
+		let children: Vec<_> = (0..4).map( |_|
+		{
+			let names = names2.clone();
+
+			exec2.spawn_handle( async move
+			{
+				// Block the worker, so the others have to steal.
+				//
+				thread::sleep( Duration::from_millis( 50 ) );
+				names.lock().expect( "lock" ).insert( thread::current().name().map( str::to_string ) );
+
+			}).expect( "spawn" )
+
+		}).collect();
+
+		for child in children { child.await; }
+
+	}).expect( "spawn" );
+
+	exec.block_on( parent );
+
+	assert!( names.lock().expect( "lock" ).len() > 1 );
+}
+
+
+
+// dropping the executor drops unfinished tasks.
+//
+#[ test ]
+//
+fn shutdown()
+{
+	let exec             = native( 2 );
+	let (guard, dropped) = oneshot::channel::<()>();
+
+	let handle = exec.spawn_handle( async move
+	{
+		let _guard = guard;
+		pending::<()>().await;
+
+	}).expect( "spawn" );
+
+	drop( exec );
+
+	assert!( block_on( dropped ).is_err() );
+
+	// Awaiting the handle of a task that was dropped panics.
+	//
+	assert!( catch_unwind( AssertUnwindSafe( || block_on( handle ) ) ).is_err() );
+}
+
+
+
+// block_on panics on a worker thread.
+//
+#[ test ]
+//
+fn nested_block_on()
+{
+	let exec  = native( 1 );
+	let exec2 = exec.clone();
+
+	let handle = exec.spawn_handle( async move { exec2.block_on( async {} ) } ).expect( "spawn" );
+
+	assert!( catch_unwind( AssertUnwindSafe( || exec.block_on( handle ) ) ).is_err() );
+}
+
+
+
+// spawning in bulk, from outside the pool and from a worker, keeps the order of the handles.
+//
+#[ test ]
+//
+fn spawn_many()
+{
+	let exec  = native( 4 );
+	let count = Arc::new( AtomicUsize::new( 0 ) );
+
+	let handles = exec.spawn_handle_many( (0..10_000u64).map( |i| async move { i } ) ).expect( "spawn" );
+
+	assert_eq!( exec.block_on( join_all( handles ) ), (0..10_000).collect::<Vec<_>>() );
+
+	exec.spawn_many( (0..100).map( |_|
+	{
+		let count = count.clone();
+		async move { count.fetch_add( 1, SeqCst ); }
+
+	})).expect( "spawn" );
+
+	wait_for( || count.load( SeqCst ) == 100 );
+
+	// From a worker, the tasks go on it's local queue, where the other workers steal them.
+	//
+	let exec2  = exec.clone();
+	let nested = exec.spawn_handle( async move
+	{
+		let handles = exec2.spawn_handle_many( (0..1_000u32).map( |i| async move { i * 2 } ) ).expect( "spawn" );
+
+		join_all( handles ).await
+
+	}).expect( "spawn" );
+
+	assert_eq!( exec.block_on( nested ), (0..1_000).map( |i| i * 2 ).collect::<Vec<_>>() );
+}
+
+
+
+// a task that wakes itself during a long poll doesn't keep another worker waiting.
+//
+#[ test ]
+//
+fn wake_during_poll()
+{
+	let exec      = native( 2 );
+	let started   = Arc::new( AtomicUsize::new( 0 ) );
+	let ran       = Arc::new( AtomicUsize::new( 0 ) );
+	let started2  = started.clone();
+	let ran2      = ran.clone();
+	let mut polls = 0;
+
+	// The first poll only returns once the other worker has run another task.
+	//
+	let handle = exec.spawn_handle( poll_fn( move |cx|
+	{
+		polls += 1;
+
+		if polls > 1
+		{
+			return Poll::Ready( polls );
+		}
+
+		cx.waker().wake_by_ref();
+		started2.store( 1, SeqCst );
+
+		wait_for( || ran2.load( SeqCst ) == 1 );
+
+		Poll::Pending
+
+	})).expect( "spawn" );
+
+	wait_for( || started.load( SeqCst ) == 1 );
+
+	// Give the other worker time to pick up the task if the wake up queued it.
+	//
+	thread::sleep( Duration::from_millis( 50 ) );
+
+	exec.spawn( async move { ran.store( 1, SeqCst ); } ).expect( "spawn" );
+
+	assert_eq!( exec.block_on( handle ), 2 );
+}
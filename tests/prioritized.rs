@@ -0,0 +1,283 @@
+#![ cfg( feature = "threadpool" ) ]
+
+// Tested:
+//
+// ✔ spawn_handle on the default class returns the output.
+// ✔ no more tasks than the concurrency are started on the wrapped executor.
+// ✔ classes get started in proportion to their weight.
+// ✔ a class that was idle does not get to monopolize the executor.
+// ✔ a task who's JoinHandle is dropped while queued doesn't run.
+// ✔ the class can be chosen through the SpawnClass trait.
+// ✔ spawning fails when the wrapped executor refuses tasks.
+// ✔ spawning in bulk queues the batch and keeps the order of the handles.
+//
+mod common;
+
+use
+{
+	common       :: { *                                                       } ,
+	futures      :: { future::{ FutureObj, join_all }, task::SpawnError       } ,
+	std          :: { collections::VecDeque, sync::{ Mutex, atomic::{ AtomicBool, Ordering::SeqCst } } } ,
+};
+
+
+// An executor that only runs tasks when we tell it to.
+//
+#[ derive( Clone, Default ) ]
+//
+struct Manual
+{
+	tasks: Arc< Mutex< VecDeque< FutureObj<'static, ()> > > >,
+}
+
+
+impl Spawn for Manual
+{
+	fn spawn_obj( &self, future: FutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		self.tasks.lock().unwrap().push_back( future );
+		Ok(())
+	}
+}
+
+
+impl Manual
+{
+	fn len( &self ) -> usize
+	{
+		self.tasks.lock().unwrap().len()
+	}
+
+	// Run the oldest task to completion, which starts the next one.
+	//
+	fn run_next( &self ) -> bool
+	{
+		let task = self.tasks.lock().unwrap().pop_front();
+
+		match task
+		{
+			Some( task ) => { block_on( task ); true }
+			None         => false,
+		}
+	}
+}
+
+
+// Spawn a task in `class` that records `label` when it runs.
+//
+fn record( exec: &Prioritized<Manual>, class: Class, log: &Arc< Mutex< Vec<&'static str> > >, label: &'static str )
+{
+	let log = log.clone();
+
+	exec.spawn_class( class, async move { log.lock().unwrap().push( label ); } ).expect( "spawn" );
+}
+
+
+
+// spawn_handle on the default class returns the output.
+//
+#[ test ]
+//
+fn spawn_handle()
+{
+	let exec   = Prioritized::new( ThreadPool::new().expect( "create threadpool" ), 2 );
+	let handle = exec.spawn_handle( async { 5u8 } ).expect( "spawn" );
+
+	assert_eq!( block_on( handle ), 5 );
+}
+
+
+
+// no more tasks than the concurrency are started on the wrapped executor.
+//
+#[ test ]
+//
+fn concurrency()
+{
+	let manual = Manual::default();
+	let exec   = Prioritized::new( manual.clone(), 2 );
+
+	for _ in 0..5
+	{
+		exec.spawn( async {} ).expect( "spawn" );
+	}
+
+	assert_eq!( manual.len()  , 2 );
+	assert_eq!( exec.running(), 2 );
+	assert_eq!( exec.queued() , 3 );
+
+	while manual.run_next() {}
+
+	assert_eq!( exec.running(), 0 );
+	assert_eq!( exec.queued() , 0 );
+}
+
+
+
+// classes get started in proportion to their weight.
+//
+#[ test ]
+//
+fn weights()
+{
+	let manual = Manual::default();
+	let exec   = Prioritized::new( manual.clone(), 1 );
+	let light  = exec.add_class( 1 );
+	let heavy  = exec.add_class( 3 );
+	let log    = Arc::new( Mutex::new( Vec::new() ) );
+
+	for _ in 0..8 { record( &exec, light, &log, "light" ); }
+	for _ in 0..8 { record( &exec, heavy, &log, "heavy" ); }
+
+	while manual.run_next() {}
+
+	let log   = log.lock().unwrap();
+	let first = &log[ ..8 ];
+
+	assert_eq!( first.iter().filter( |l| **l == "light" ).count(), 2 );
+	assert_eq!( first.iter().filter( |l| **l == "heavy" ).count(), 6 );
+	assert_eq!( log.len(), 16 );
+}
+
+
+
+// a class that was idle does not get to monopolize the executor.
+//
+#[ test ]
+//
+fn no_banking()
+{
+	let manual = Manual::default();
+	let exec   = Prioritized::new( manual.clone(), 1 );
+	let early  = exec.add_class( 1 );
+	let late   = exec.add_class( 1 );
+	let log    = Arc::new( Mutex::new( Vec::new() ) );
+
+	for _ in 0..10 { record( &exec, early, &log, "early" ); }
+	for _ in 0..5  { manual.run_next(); }
+	for _ in 0..5  { record( &exec, late, &log, "late" ); }
+
+	while manual.run_next() {}
+
+	let log = log.lock().unwrap();
+
+	// After late arrives, they alternate until early runs out.
+	//
+	assert!( log[ 5..13 ].windows(2).all( |w| w[0] != w[1] ), "{:?}", log );
+}
+
+
+
+// a task who's JoinHandle is dropped while queued doesn't run.
+//
+#[ test ]
+//
+fn drop_queued()
+{
+	let manual = Manual::default();
+	let exec   = Prioritized::new( manual.clone(), 1 );
+	let ran    = Arc::new( AtomicBool::new( false ) );
+	let ran2   = ran.clone();
+
+	exec.spawn( async {} ).expect( "spawn" );
+
+	let handle = exec.spawn_handle( async move { ran2.store( true, SeqCst ); } ).expect( "spawn" );
+
+	drop( handle );
+
+	while manual.run_next() {}
+
+	assert!( !ran.load( SeqCst ) );
+}
+
+
+
+// the class can be chosen through the SpawnClass trait.
+//
+#[ test ]
+//
+fn spawn_class_trait()
+{
+	fn spawn_in( exec: &dyn SpawnClass, class: Class, log: &Arc< Mutex< Vec<&'static str> > >, label: &'static str )
+	{
+		let log = log.clone();
+
+		exec.spawn_class( class, async move { log.lock().unwrap().push( label ); } ).expect( "spawn" );
+	}
+
+	let manual = Manual::default();
+	let exec   = Prioritized::new( manual.clone(), 1 );
+	let light  = exec.add_class( 1 );
+	let heavy  = exec.add_class( 3 );
+	let log    = Arc::new( Mutex::new( Vec::new() ) );
+
+	for _ in 0..4 { spawn_in( &exec, light, &log, "light" ); }
+	for _ in 0..4 { spawn_in( &exec, heavy, &log, "heavy" ); }
+
+	let handle = exec.spawn_handle_class( heavy, async { 5u8 } ).expect( "spawn" );
+
+	while manual.run_next() {}
+
+	let log = log.lock().unwrap();
+
+	assert_eq!( log[ ..4 ].iter().filter( |l| **l == "heavy" ).count(), 3 );
+	assert_eq!( block_on( handle ), 5 );
+}
+
+
+
+// spawning fails when the wrapped executor refuses tasks.
+//
+#[ test ]
+//
+fn status()
+{
+	#[ derive( Clone ) ]
+	//
+	struct Closed;
+
+	impl Spawn for Closed
+	{
+		fn spawn_obj( &self, _: FutureObj<'static, ()> ) -> Result<(), SpawnError>
+		{
+			Err( SpawnError::shutdown() )
+		}
+
+		fn status( &self ) -> Result<(), SpawnError>
+		{
+			Err( SpawnError::shutdown() )
+		}
+	}
+
+	let exec = Prioritized::new( Closed, 1 );
+
+	assert!( exec.spawn( async {} ).is_err() );
+	assert!( exec.spawn_handle( async { 5u8 } ).is_err() );
+	assert_eq!( exec.queued() , 0 );
+	assert_eq!( exec.running(), 0 );
+}
+
+
+
+// spawning in bulk queues the batch and keeps the order of the handles.
+//
+#[ test ]
+//
+fn spawn_many()
+{
+	let manual = Manual::default();
+	let exec   = Prioritized::new( manual.clone(), 2 );
+
+	exec.spawn_many( (0..5).map( |_| async {} ) ).expect( "spawn" );
+
+	assert_eq!( manual.len()  , 2 );
+	assert_eq!( exec.queued() , 3 );
+
+	while manual.run_next() {}
+
+	let handles = exec.spawn_handle_many( (0..5u8).map( |i| async move { i } ) ).expect( "spawn" );
+
+	while manual.run_next() {}
+
+	assert_eq!( block_on( join_all( handles ) ), vec![ 0, 1, 2, 3, 4 ] );
+}
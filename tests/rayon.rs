@@ -0,0 +1,203 @@
+#![ cfg( feature = "rayon" ) ]
+
+// Tested:
+//
+// ✔ pass a RayonExec to a function that takes exec: `impl SpawnHandle`.
+// ✔ futures run on the rayon pool and continue there after being woken up.
+// ✔ spawn_compute runs a closure and rayon's parallel iterators use the same pool.
+// ✔ dropping the JoinHandle cancels the task.
+// ✔ a panicking task doesn't abort the process and the panic is resumed when awaiting the JoinHandle.
+// ✔ the global pool.
+// ✔ a future that wakes itself from within rayon::scope on a single thread doesn't deadlock.
+//
+mod common;
+
+use
+{
+	common      :: { *                                                      } ,
+	futures     :: { future::{ pending, poll_fn }                           } ,
+	rayon_crate :: { ThreadPoolBuilder, prelude::*, current_thread_index    } ,
+	std         :: { panic::{ catch_unwind, AssertUnwindSafe }, thread      } ,
+	std         :: { sync::mpsc, task::Poll, time::Duration                 } ,
+};
+
+
+fn pool() -> RayonExec
+{
+	ThreadPoolBuilder::new()
+
+		.num_threads( 2 )
+		.thread_name( |i| format!( "rayon-test-{}", i ) )
+		.build()
+		.expect( "create rayon pool" )
+		.into()
+}
+
+
+
+// pass a RayonExec to a function that takes exec: `impl SpawnHandle`.
+//
+#[ test ]
+//
+fn spawn_handle()
+{
+	let exec   = pool();
+	let result = block_on( increment_spawn_handle( 4, exec ) );
+
+	assert_eq!( 5u8, result );
+}
+
+
+
+// futures run on the rayon pool and continue there after being woken up.
+//
+#[ test ]
+//
+fn woken_up()
+{
+	let exec     = pool();
+	let (tx, rx) = oneshot::channel::<u8>();
+
+	let handle = exec.spawn_handle( async move
+	{
+		let before = thread::current().name().map( str::to_string );
+		let value  = rx.await.expect( "receive" );
+		let after  = thread::current().name().map( str::to_string );
+
+		( value, before, after )
+
+	}).expect( "spawn" );
+
+	tx.send( 5 ).expect( "send" );
+
+	let (value, before, after) = block_on( handle );
+
+	assert_eq!( value, 5 );
+	assert!( before.expect( "thread name" ).starts_with( "rayon-test-" ) );
+	assert!( after .expect( "thread name" ).starts_with( "rayon-test-" ) );
+}
+
+
+
+// spawn_compute runs a closure and rayon's parallel iterators use the same pool.
+//
+#[ test ]
+//
+fn spawn_compute()
+{
+	let exec = pool();
+
+	let sum = exec.spawn_compute( ||
+	{
+		assert!( current_thread_index().is_some() );
+
+		(0..10_000u64).into_par_iter().sum::<u64>()
+
+	}).expect( "spawn" );
+
+	assert_eq!( block_on( sum ), 49_995_000 );
+}
+
+
+
+// dropping the JoinHandle cancels the task.
+//
+#[ test ]
+//
+fn join_handle_drop()
+{
+	let exec             = pool();
+	let (guard, dropped) = oneshot::channel::<()>();
+
+	let handle = exec.spawn_handle( async move
+	{
+		let _guard = guard;
+		pending::<()>().await;
+
+	}).expect( "spawn" );
+
+	drop( handle );
+
+	assert!( block_on( dropped ).is_err() );
+}
+
+
+
+// a panicking task doesn't abort the process and the panic is resumed when awaiting the JoinHandle.
+//
+#[ test ]
+//
+fn panic()
+{
+	let exec = pool();
+
+	exec.spawn( async { panic!( "boom" ) } ).expect( "spawn" );
+
+	let handle = exec.spawn_compute( || -> u8 { panic!( "boom" ) } ).expect( "spawn" );
+
+	assert!( catch_unwind( AssertUnwindSafe( || block_on( handle ) ) ).is_err() );
+
+	// The pool still works.
+	//
+	let handle = exec.spawn_compute( || 5u8 ).expect( "spawn" );
+
+	assert_eq!( block_on( handle ), 5 );
+}
+
+
+
+// the global pool.
+//
+#[ test ]
+//
+fn global()
+{
+	let exec = RayonExec::default();
+
+	assert!( exec.pool().is_none() );
+
+	let handle = exec.spawn_handle_generic( async { current_thread_index().is_some() } ).expect( "spawn" );
+
+	assert!( block_on( handle ) );
+}
+
+
+
+// a future that wakes itself from within rayon::scope on a single thread doesn't deadlock.
+//
+#[ test ]
+//
+fn wake_during_poll()
+{
+	let exec = RayonExec::new( ThreadPoolBuilder::new().num_threads( 1 ).build().expect( "create rayon pool" ) );
+
+	let (tx, rx)  = mpsc::channel();
+	let mut polls = 0;
+
+	// While the scope waits, the worker takes jobs from its queue, last in first out. The wake up queues the next
+	// poll on top of the other job of the scope, so the worker runs it while the first poll hasn't returned.
+	//
+	exec.spawn( poll_fn( move |cx|
+	{
+		polls += 1;
+
+		if polls > 1
+		{
+			tx.send( polls ).expect( "send" );
+			return Poll::Ready(());
+		}
+
+		let waker = cx.waker();
+
+		rayon_crate::scope( |s|
+		{
+			s.spawn( |_| {}                  );
+			s.spawn( |_| waker.wake_by_ref() );
+		});
+
+		Poll::Pending
+
+	})).expect( "spawn" );
+
+	assert_eq!( rx.recv_timeout( Duration::from_secs( 10 ) ), Ok(2) );
+}
@@ -0,0 +1,188 @@
+#![ cfg( feature = "localpool" ) ]
+
+// Tested:
+//
+// ✔ spawn a Send future on a LocalPool from another thread.
+// ✔ spawn a !Send future built by a Send closure from another thread, with and without handle.
+// ✔ dropping the JoinHandle cancels a task spawned with spawn_handle_with.
+// ✔ spawning fails once the LocalPool is dropped.
+
+// ✔ a panic in a remote task is resumed in the JoinHandle and doesn't take down the LocalPool.
+// ✔ spawn on TokioCt from another thread while it runs.
+// ✔ spawning fails once TokioCt is dropped.
+//
+mod common;
+
+use
+{
+	common  :: { *                                  } ,
+	futures :: { executor::LocalPool, future::pending } ,
+	std     :: { thread, panic::AssertUnwindSafe        } ,
+};
+
+
+// spawn a Send future on a LocalPool from another thread.
+//
+#[ test ]
+//
+fn send_future()
+{
+	let mut pool = LocalPool::new();
+	let remote   = RemoteSpawner::new( pool.spawner() ).expect( "spawn" );
+
+	let handle = thread::spawn( move ||
+	{
+		remote.spawn_handle( async { 5u8 } ).expect( "spawn" )
+
+	}).join().expect( "join thread" );
+
+	assert_eq!( pool.run_until( handle ), 5 );
+}
+
+
+
+// spawn a !Send future built by a Send closure from another thread, with and without handle.
+//
+#[ test ]
+//
+fn not_send_future()
+{
+	let mut pool = LocalPool::new();
+	let remote   = RemoteSpawner::new( pool.spawner() ).expect( "spawn" );
+	let (tx, rx) = oneshot::channel();
+
+	let handle = thread::spawn( move ||
+	{
+		remote.spawn_with( move || async move
+		{
+			let rc = Rc::new( 3u8 );
+			tx.send( *rc ).expect( "send" );
+
+		}).expect( "spawn" );
+
+		remote.spawn_handle_with( || async { *Rc::new( 5u8 ) } ).expect( "spawn" )
+
+	}).join().expect( "join thread" );
+
+	assert_eq!( pool.run_until( handle ), 5 );
+	assert_eq!( pool.run_until( rx ), Ok(3) );
+}
+
+
+
+// dropping the JoinHandle cancels a task spawned with spawn_handle_with.
+//
+#[ test ]
+//
+fn drop_cancels()
+{
+	let mut pool         = LocalPool::new();
+	let remote           = RemoteSpawner::new( pool.spawner() ).expect( "spawn" );
+	let (guard, drop_rx) = oneshot::channel::<()>();
+
+	let handle = remote.spawn_handle_with( move || async move
+	{
+		let _guard = guard;
+		let _rc    = Rc::new(());
+		pending::<()>().await;
+
+	}).expect( "spawn" );
+
+	pool.run_until_stalled();
+	drop( handle );
+
+	assert!( pool.run_until( drop_rx ).is_err() );
+}
+
+
+
+// spawning fails once the LocalPool is dropped.
+//
+#[ test ]
+//
+fn pool_dropped()
+{
+	let pool   = LocalPool::new();
+	let remote = RemoteSpawner::new( pool.spawner() ).expect( "spawn" );
+
+	assert!( !remote.is_closed() );
+
+	drop( pool );
+
+	assert!( remote.is_closed() );
+	assert!( remote.spawn( async {} ).is_err() );
+	assert!( remote.spawn_with( || async {} ).is_err() );
+}
+
+
+
+// spawn on TokioCt from another thread while it runs.
+//
+// a panic in a remote task is resumed in the JoinHandle and doesn't take down the LocalPool.
+//
+#[ test ]
+//
+fn panic()
+{
+	let mut pool = LocalPool::new();
+	let remote   = RemoteSpawner::new( pool.spawner() ).expect( "spawn" );
+
+	let (handle, detached, after) = thread::spawn( move ||
+	{
+		let handle   = remote.spawn_handle_with( || async { if *Rc::new( true ) { panic!( "remote panic" ); } } ).expect( "spawn" );
+		let detached = remote.spawn_with( || async { panic!( "detached panic" ) } );
+		let after    = remote.spawn_handle( async { 5u8 } ).expect( "spawn" );
+
+		(handle, detached, after)
+
+	}).join().expect( "join thread" );
+
+	detached.expect( "spawn" );
+
+	let result = pool.run_until( AssertUnwindSafe( handle ).catch_unwind() );
+	let panic  = result.expect_err( "the task panicked" );
+
+	assert_eq!( panic.downcast_ref::<&str>(), Some( &"remote panic" ) );
+	assert_eq!( pool.run_until( after ), 5 );
+}
+
+
+
+#[ cfg( feature = "tokio_ct" ) ]
+//
+#[ test ]
+//
+fn tokio_ct()
+{
+	let exec   = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let remote = exec.remote_spawner();
+
+	let out = exec.block_on( async move
+	{
+		let thread = thread::spawn( move || remote.spawn_handle_with( || async { *Rc::new( 5u8 ) } ).expect( "spawn" ) );
+
+		// The thread doesn't block on the runtime, so it's fine to join it here.
+		//
+		thread.join().expect( "join thread" ).await
+	});
+
+	assert_eq!( out, 5 );
+}
+
+
+
+// spawning fails once TokioCt is dropped.
+//
+#[ cfg( feature = "tokio_ct" ) ]
+//
+#[ test ]
+//
+fn tokio_ct_dropped()
+{
+	let exec   = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let remote = exec.remote_spawner();
+
+	drop( exec );
+
+	assert!( remote.spawn( async {} ).is_err() );
+}
@@ -0,0 +1,282 @@
+#![ cfg(all( feature = "localpool", feature = "threadpool" )) ]
+
+// Tested:
+//
+// ✔ spawn_after starts the future after the delay.
+// ✔ spawn_at starts the future at the given instant.
+// ✔ spawn_after_local works with !Send futures.
+// ✔ dropping the handle cancels a delayed task before it starts.
+// ✔ spawn_periodic with MissedTick::Burst catches up on missed runs.
+// ✔ spawn_periodic with MissedTick::Delay shifts the schedule.
+// ✔ spawn_periodic with MissedTick::Skip skips missed runs.
+// ✔ dropping the handle stops a periodic task.
+// ✔ a periodic task that is always behind schedule still lets other tasks run and can be stopped.
+// ✔ spawn_periodic on a threadpool with the real timer.
+// ✔ spawn_after_local on TokioCt, with the tokio timer.
+//
+mod common;
+
+use
+{
+	common  :: { *                                                               } ,
+	futures :: { executor::LocalPool, channel::mpsc, StreamExt                   } ,
+	std     :: { time::Duration, sync::atomic::{ AtomicBool, Ordering::SeqCst }  } ,
+};
+
+
+// Run a periodic task where the second run takes 25 seconds, with runs every 10 seconds. Returns the start times
+// of the first 5 runs in seconds.
+//
+fn starts( missed: MissedTick ) -> Vec<u64>
+{
+	let clock    = MockClock::new();
+	let mut pool = LocalPool::new();
+	let exec     = clock.track( pool.spawner() );
+	let (tx, rx) = mpsc::unbounded();
+	let mut run  = 0;
+
+	clock.set_auto_advance( true );
+
+	let timer  = exec.clone();
+	let clock2 = clock.clone();
+
+	let _handle = exec.spawn_periodic_local( Duration::from_secs( 10 ), missed, move ||
+	{
+		tx.unbounded_send( clock2.elapsed().as_secs() ).expect( "send" );
+
+		let sleep = timer.sleep( Duration::from_secs( if run == 1 { 25 } else { 0 } ) );
+		run += 1;
+
+		sleep
+
+	}).expect( "spawn" );
+
+	pool.run_until( rx.take(5).collect() )
+}
+
+
+
+// spawn_after starts the future after the delay.
+//
+#[ test ]
+//
+fn after()
+{
+	let clock    = MockClock::new();
+	let mut pool = LocalPool::new();
+	let exec     = clock.track( pool.spawner() );
+	let clock2   = clock.clone();
+
+	clock.set_auto_advance( true );
+
+	let handle = exec.spawn_after( Duration::from_secs( 5 ), async move { clock2.elapsed() } ).expect( "spawn" );
+
+	assert_eq!( pool.run_until( handle ), Duration::from_secs( 5 ) );
+}
+
+
+
+// spawn_at starts the future at the given instant.
+//
+#[ test ]
+//
+fn at()
+{
+	let clock    = MockClock::new();
+	let mut pool = LocalPool::new();
+	let exec     = clock.track( pool.spawner() );
+	let clock2   = clock.clone();
+
+	clock.set_auto_advance( true );
+
+	let handle = exec.spawn_at( exec.now() + Duration::from_secs( 7 ), async move { clock2.elapsed() } ).expect( "spawn" );
+
+	assert_eq!( pool.run_until( handle ), Duration::from_secs( 7 ) );
+}
+
+
+
+// spawn_after_local works with !Send futures.
+//
+#[ test ]
+//
+fn after_local()
+{
+	let clock    = MockClock::new();
+	let mut pool = LocalPool::new();
+	let exec     = clock.track( pool.spawner() );
+	let value    = Rc::new( 5u8 );
+
+	clock.set_auto_advance( true );
+
+	let handle = exec.spawn_after_local( Duration::from_secs( 1 ), async move { *value } ).expect( "spawn" );
+
+	assert_eq!( pool.run_until( handle ), 5 );
+}
+
+
+
+// dropping the handle cancels a delayed task before it starts.
+//
+#[ test ]
+//
+fn drop_after()
+{
+	let clock    = MockClock::new();
+	let mut pool = LocalPool::new();
+	let exec     = clock.track( pool.spawner() );
+	let ran      = Arc::new( AtomicBool::new( false ) );
+	let ran2     = ran.clone();
+
+	let handle = exec.spawn_after( Duration::from_secs( 1 ), async move { ran2.store( true, SeqCst ) } ).expect( "spawn" );
+
+	pool.run_until_stalled();
+	drop( handle );
+
+	clock.advance( Duration::from_secs( 2 ) );
+	pool.run_until_stalled();
+
+	assert!( !ran.load( SeqCst ) );
+}
+
+
+
+// spawn_periodic with MissedTick::Burst catches up on missed runs.
+//
+#[ test ]
+//
+fn burst()
+{
+	assert_eq!( starts( MissedTick::Burst ), vec![ 0, 10, 35, 35, 40 ] );
+}
+
+
+
+// spawn_periodic with MissedTick::Delay shifts the schedule.
+//
+#[ test ]
+//
+fn delay()
+{
+	assert_eq!( starts( MissedTick::Delay ), vec![ 0, 10, 35, 45, 55 ] );
+}
+
+
+
+// spawn_periodic with MissedTick::Skip skips missed runs.
+//
+#[ test ]
+//
+fn skip()
+{
+	assert_eq!( starts( MissedTick::Skip ), vec![ 0, 10, 40, 50, 60 ] );
+}
+
+
+
+// dropping the handle stops a periodic task.
+//
+#[ test ]
+//
+fn drop_periodic()
+{
+	let clock        = MockClock::new();
+	let mut pool     = LocalPool::new();
+	let exec         = clock.track( pool.spawner() );
+	let (tx, mut rx) = mpsc::unbounded();
+
+	let handle = exec.spawn_periodic_local( Duration::from_secs( 1 ), MissedTick::Burst, move ||
+	{
+		tx.unbounded_send(()).expect( "send" );
+		async {}
+
+	}).expect( "spawn" );
+
+	pool.run_until_stalled();
+	assert_eq!( rx.try_recv(), Ok(()) );
+
+	drop( handle );
+
+	clock.advance( Duration::from_secs( 5 ) );
+	pool.run_until_stalled();
+
+	// The sender was dropped with the task.
+	//
+	assert_eq!( pool.run_until( rx.next() ), None );
+}
+
+
+
+// a periodic task that is always behind schedule still lets other tasks run and can be stopped.
+//
+#[ test ]
+//
+fn behind_schedule()
+{
+	for missed in [ MissedTick::Burst, MissedTick::Delay ]
+	{
+		let clock    = MockClock::new();
+		let mut pool = LocalPool::new();
+		let exec     = clock.track( pool.spawner() );
+		let clock2   = clock.clone();
+
+		// Every run is ready right away, but takes longer than the interval.
+		//
+		let handle = exec.spawn_periodic_local( Duration::from_secs( 1 ), missed, move ||
+		{
+			clock2.advance( Duration::from_secs( 2 ) );
+			async {}
+
+		}).expect( "spawn" );
+
+		let other = exec.spawn_handle_local( async { 5u8 } ).expect( "spawn" );
+
+		assert_eq!( pool.run_until( other ), 5 );
+
+		drop( handle );
+
+		// Would never return if the periodic task didn't yield.
+		//
+		pool.run_until_stalled();
+	}
+}
+
+
+
+// spawn_periodic on a threadpool with the real timer.
+//
+#[ test ]
+//
+fn threadpool()
+{
+	let exec     = ThreadPool::new().expect( "create threadpool" );
+	let (tx, rx) = mpsc::unbounded();
+
+	let handle = exec.spawn_periodic( Duration::from_millis( 2 ), MissedTick::Skip, move ||
+	{
+		tx.unbounded_send(()).expect( "send" );
+		async {}
+
+	}).expect( "spawn" );
+
+	assert_eq!( block_on( rx.take(3).count() ), 3 );
+
+	drop( handle );
+}
+
+
+
+// spawn_after_local on TokioCt, with the tokio timer.
+//
+#[ cfg( feature = "tokio_ct" ) ]
+//
+#[ test ]
+//
+fn tokio_ct()
+{
+	let exec   = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let value  = Rc::new( 5u8 );
+	let handle = exec.spawn_after_local( Duration::from_millis( 5 ), async move { *value } ).expect( "spawn" );
+
+	assert_eq!( exec.block_on( handle ), 5 );
+}
@@ -0,0 +1,207 @@
+#![ cfg( feature = "localpool" ) ]
+
+// Tested:
+//
+// ✔ all tasks for the same key run on the same shard.
+// ✔ different keys get spread over the shards.
+// ✔ spawn_on and the local variants use the shard for the key.
+// ✔ tasks without a key are spawned round-robin, and clones share the counter.
+// ✔ thread-per-core over RemoteSpawners.
+// ✔ spawning in bulk gives every shard a contiguous part of the batch and keeps the order of the handles.
+//
+mod common;
+
+use
+{
+	common  :: { *                                                   } ,
+	futures :: { executor::LocalPool, future::join_all                } ,
+	std     :: { thread, sync::atomic::{ AtomicUsize, Ordering::SeqCst } } ,
+};
+
+
+// A LocalPool per shard.
+//
+fn pools( n: usize ) -> ( Vec<LocalPool>, Sharded<LocalSpawner> )
+{
+	let pools: Vec<LocalPool> = (0..n).map( |_| LocalPool::new() ).collect();
+	let exec                  = Sharded::new( pools.iter().map( LocalPool::spawner ) );
+
+	(pools, exec)
+}
+
+
+// all tasks for the same key run on the same shard.
+//
+#[ test ]
+//
+fn same_key()
+{
+	let (mut pools, exec) = pools( 4 );
+	let shard             = exec.shard_for( &42u64 );
+
+	let handles: Vec<_> = (0..10u8).map( |i| exec.spawn_handle_on( &42u64, async move { i } ).expect( "spawn" ) ).collect();
+
+	for handle in handles
+	{
+		let out = pools[ shard ].run_until( handle );
+		assert!( out < 10 );
+	}
+
+	assert_eq!( exec.shard_for( &42u64 ), shard );
+	assert_eq!( exec.len(), 4 );
+}
+
+
+
+// different keys get spread over the shards.
+//
+#[ test ]
+//
+fn spread()
+{
+	let (_pools, exec) = pools( 4 );
+	let mut used       = [false; 4];
+
+	for key in 0..100u32
+	{
+		used[ exec.shard_for( &key ) ] = true;
+	}
+
+	assert!( used.iter().all( |u| *u ) );
+}
+
+
+
+// spawn_on and the local variants use the shard for the key.
+//
+#[ test ]
+//
+fn spawn_on()
+{
+	let (mut pools, exec) = pools( 3 );
+	let shard             = exec.shard_for( "key" );
+	let (tx, rx)          = oneshot::channel();
+	let (ltx, lrx)        = oneshot::channel();
+
+	exec.spawn_on      ( "key", async move { tx .send( 1u8              ).expect( "send" ); } ).expect( "spawn" );
+	exec.spawn_local_on( "key", async move { ltx.send( *Rc::new( 2u8 ) ).expect( "send" ); } ).expect( "spawn" );
+
+	let handle = exec.spawn_handle_local_on( "key", async { *Rc::new( 3u8 ) } ).expect( "spawn" );
+
+	for (i, pool) in pools.iter_mut().enumerate()
+	{
+		if i != shard { pool.run_until_stalled(); }
+	}
+
+	let pool = &mut pools[ shard ];
+
+	assert_eq!( pool.run_until( rx     ), Ok(1) );
+	assert_eq!( pool.run_until( lrx    ), Ok(2) );
+	assert_eq!( pool.run_until( handle ), 3     );
+}
+
+
+
+// tasks without a key are spawned round-robin, and clones share the counter.
+//
+#[ test ]
+//
+fn round_robin()
+{
+	let (mut pools, exec) = pools( 3 );
+	let clone             = exec.clone();
+	let count             = Arc::new( AtomicUsize::new(0) );
+
+	for i in 0..6
+	{
+		let exec  = if i % 2 == 0 { &exec } else { &clone };
+		let count = count.clone();
+
+		if i < 3 { exec.spawn( async move { count.fetch_add( 1, SeqCst ); } ).expect( "spawn" );                       }
+		else     { exec.spawn_handle( async move { count.fetch_add( 1, SeqCst ); } ).expect( "spawn" ).detach(); }
+	}
+
+	for pool in &mut pools
+	{
+		let before = count.load( SeqCst );
+
+		pool.run_until_stalled();
+
+		assert_eq!( count.load( SeqCst ) - before, 2 );
+	}
+}
+
+
+
+// thread-per-core over RemoteSpawners.
+//
+#[ test ]
+//
+fn thread_per_core()
+{
+	let mut stops   = Vec::new();
+	let mut remotes = Vec::new();
+	let mut threads = Vec::new();
+
+	for _ in 0..2
+	{
+		let (stop_tx , stop_rx ) = oneshot::channel::<()>();
+		let (ready_tx, ready_rx) = oneshot::channel();
+
+		threads.push( thread::spawn( move ||
+		{
+			let mut pool = LocalPool::new();
+
+			ready_tx.send( RemoteSpawner::new( pool.spawner() ).expect( "spawn" ) ).expect( "send" );
+
+			let _ = pool.run_until( stop_rx );
+		}));
+
+		stops  .push( stop_tx );
+		remotes.push( block_on( ready_rx ).expect( "receive" ) );
+	}
+
+	let exec = Sharded::new( remotes );
+
+	let a1 = block_on( exec.spawn_handle_on( "a", async { thread::current().id() } ).expect( "spawn" ) );
+	let a2 = block_on( exec.spawn_handle_on( "a", async { thread::current().id() } ).expect( "spawn" ) );
+
+	assert_eq!( a1, a2 );
+	assert_ne!( a1, thread::current().id() );
+
+	drop( stops );
+
+	for thread in threads { thread.join().expect( "join thread" ); }
+}
+
+
+
+// spawning in bulk gives every shard a contiguous part of the batch and keeps the order of the handles.
+//
+#[ test ]
+//
+fn spawn_many()
+{
+	let (mut pools, exec) = pools( 3 );
+	let count             = Arc::new( AtomicUsize::new(0) );
+
+	let handles = exec.spawn_handle_many( (0..7u8).map( |i|
+	{
+		let count = count.clone();
+		async move { count.fetch_add( 1, SeqCst ); i }
+
+	})).expect( "spawn" );
+
+	// 7 tasks over 3 shards gives chunks of 3, 3 and 1.
+	//
+	for (pool, chunk) in pools.iter_mut().zip( [3, 3, 1] )
+	{
+		let before = count.load( SeqCst );
+
+		pool.run_until_stalled();
+
+		assert_eq!( count.load( SeqCst ) - before, chunk );
+	}
+
+	assert_eq!( block_on( join_all( handles ) ), (0..7).collect::<Vec<_>>() );
+}
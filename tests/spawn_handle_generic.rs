@@ -0,0 +1,300 @@
+#![ cfg(all( not(target_os = "unknown"), any( feature = "threadpool", feature = "localpool", feature = "tokio_ct", feature = "tokio_tp", feature = "async_std", feature = "async_global" ) )) ]
+
+// Tested:
+//
+// ✔ a bound per output spawns futures with different outputs.
+// ✔ through references and smart pointers.
+// ✔ through trait objects of SpawnHandle and LocalSpawnHandle.
+// ✔ the wrappers forward to the executor they wrap.
+// ✔ Deadlined resolves to DeadlineExceeded when the future runs out of time.
+// ✔ RemoteSpawner and LocalWorkerPool.
+// ✔ LocalSpawnHandleGeneric spawns !Send futures on LocalPool.
+// ✔ TokioTp: output and dropping the JoinHandle cancels the task.
+// ✔ TokioCt: Send and !Send futures.
+// ✔ AsyncStd: output, !Send futures and dropping the JoinHandle cancels the task.
+// ✔ AsyncGlobal: output.
+// ✔ the spawn traits of tracing wrappers.
+//
+mod common;
+
+use
+{
+	common  :: { *               } ,
+	futures :: { future::pending } ,
+	std     :: { time::Duration  } ,
+};
+
+
+// Spawn futures with different outputs.
+//
+async fn outputs( exec: impl SpawnHandleGeneric<u8> + SpawnHandleGeneric<String> ) -> ( u8, String )
+{
+	let number = exec.spawn_handle_generic( async { 5u8 }              ).expect( "spawn" );
+	let text   = exec.spawn_handle_generic( async { "five".to_owned() } ).expect( "spawn" );
+
+	( number.await, text.await )
+}
+
+
+async fn number( exec: impl SpawnHandleGeneric<u8> ) -> u8
+{
+	exec.spawn_handle_generic( async { 5u8 } ).expect( "spawn" ).await
+}
+
+
+async fn local_output( exec: impl LocalSpawnHandleGeneric<u8> ) -> u8
+{
+	let value = Rc::new( 5u8 );
+
+	exec.spawn_handle_local_generic( async move { *value } ).expect( "spawn" ).await
+}
+
+
+// Check that dropping the handle cancels the task. The guard is dropped with the task.
+//
+async fn drop_cancels( exec: impl SpawnHandleGeneric<()> )
+{
+	let (guard, dropped) = oneshot::channel::<()>();
+
+	let handle = exec.spawn_handle_generic( async move
+	{
+		let _guard = guard;
+		pending::<()>().await;
+
+	}).expect( "spawn" );
+
+	drop( handle );
+
+	assert!( dropped.await.is_err() );
+}
+
+
+
+// a bound per output spawns futures with different outputs.
+//
+#[ cfg( feature = "threadpool" ) ]
+//
+#[ test ]
+//
+fn threadpool()
+{
+	let exec = ThreadPool::new().expect( "create threadpool" );
+
+	assert_eq!( block_on( outputs( exec ) ), ( 5, "five".to_owned() ) );
+}
+
+
+
+// through references and smart pointers.
+//
+#[ cfg( feature = "threadpool" ) ]
+//
+#[ test ]
+//
+fn pointers()
+{
+	let exec = ThreadPool::new().expect( "create threadpool" );
+
+	block_on( async
+	{
+		assert_eq!( outputs( &exec                 ).await.0, 5 );
+		assert_eq!( outputs( Arc::new( exec.clone() ) ).await.0, 5 );
+		assert_eq!( outputs( Box::new( exec.clone() ) ).await.0, 5 );
+		assert_eq!( outputs( Rc::new ( exec        ) ).await.0, 5 );
+	});
+}
+
+
+
+// through trait objects of SpawnHandle and LocalSpawnHandle.
+//
+#[ cfg(all( feature = "threadpool", feature = "localpool" )) ]
+//
+#[ test ]
+//
+fn trait_objects()
+{
+	let exec = ThreadPool::new().expect( "create threadpool" );
+
+	let boxed : Box< dyn SpawnHandle<u8>               > = Box::new( exec.clone() );
+	let shared: Arc< dyn SpawnHandle<u8> + Send + Sync > = Arc::new( exec.clone() );
+	let send  : &  ( dyn SpawnHandle<u8> + Send        ) = &exec;
+
+	block_on( async
+	{
+		assert_eq!( number( boxed  ).await, 5 );
+		assert_eq!( number( shared ).await, 5 );
+		assert_eq!( number( send   ).await, 5 );
+	});
+
+	let mut pool                               = futures::executor::LocalPool::new();
+	let local: Box< dyn LocalSpawnHandle<u8> > = Box::new( pool.spawner() );
+
+	assert_eq!( pool.run_until( local_output( local ) ), 5 );
+}
+
+
+
+// the wrappers forward to the executor they wrap.
+//
+#[ cfg( feature = "threadpool" ) ]
+//
+#[ test ]
+//
+fn wrappers()
+{
+	let exec = ThreadPool::new().expect( "create threadpool" );
+
+	block_on( async
+	{
+		assert_eq!( outputs( AnyExec::from         ( exec.clone()                       ) ).await.0, 5 );
+		assert_eq!( outputs( Bounded::new          ( exec.clone(), 4                    ) ).await.0, 5 );
+		assert_eq!( outputs( Prioritized::new      ( exec.clone(), 4                    ) ).await.0, 5 );
+		assert_eq!( outputs( Inherit::new          ( exec.clone()                       ) ).await.0, 5 );
+		assert_eq!( outputs( WithHandle::new       ( exec.clone()                       ) ).await.0, 5 );
+		assert_eq!( outputs( Sharded::new          ( vec![ exec.clone(), exec.clone() ] ) ).await.0, 5 );
+		assert_eq!( outputs( MockClock::new().track( exec                               ) ).await.0, 5 );
+	});
+}
+
+
+
+// Deadlined resolves to DeadlineExceeded when the future runs out of time.
+//
+#[ cfg( feature = "threadpool" ) ]
+//
+#[ test ]
+//
+fn deadlined()
+{
+	let exec = Deadlined::new( ThreadPool::new().expect( "create threadpool" ), Duration::from_millis( 10 ) );
+
+	let quick = exec.spawn_handle_generic( async { Ok( 5u8 ) }                         ).expect( "spawn" );
+	let slow  = exec.spawn_handle_generic( pending::< Result<u8, DeadlineExceeded> >() ).expect( "spawn" );
+
+	assert_eq!( block_on( quick ), Ok( 5 )                 );
+	assert_eq!( block_on( slow  ), Err( DeadlineExceeded ) );
+}
+
+
+
+// RemoteSpawner and LocalWorkerPool.
+//
+#[ cfg( feature = "localpool" ) ]
+//
+#[ test ]
+//
+fn remote()
+{
+	let mut pool = futures::executor::LocalPool::new();
+	let remote   = RemoteSpawner::new( pool.spawner() ).expect( "create remote spawner" );
+
+	assert_eq!( pool.run_until( outputs( remote ) ), ( 5, "five".to_owned() ) );
+
+	let workers = LocalWorkerPool::local_pool( 2 ).expect( "create worker pool" );
+
+	assert_eq!( block_on( outputs( workers ) ), ( 5, "five".to_owned() ) );
+}
+
+
+
+// LocalSpawnHandleGeneric spawns !Send futures on LocalPool.
+//
+#[ cfg( feature = "localpool" ) ]
+//
+#[ test ]
+//
+fn localpool()
+{
+	let mut pool = futures::executor::LocalPool::new();
+	let exec     = pool.spawner();
+
+	assert_eq!( pool.run_until( local_output( &exec ) ), 5                   );
+	assert_eq!( pool.run_until( outputs     (  exec ) ), ( 5, "five".to_owned() ) );
+}
+
+
+
+// TokioTp: output and dropping the JoinHandle cancels the task.
+//
+#[ cfg( feature = "tokio_tp" ) ]
+//
+#[ test ]
+//
+fn tokio_tp()
+{
+	let exec = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
+
+	assert_eq!( exec.block_on( outputs( &exec ) ), ( 5, "five".to_owned() ) );
+
+	exec.block_on( drop_cancels( &exec ) );
+}
+
+
+
+// TokioCt: Send and !Send futures.
+//
+#[ cfg( feature = "tokio_ct" ) ]
+//
+#[ test ]
+//
+fn tokio_ct()
+{
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	assert_eq!( exec.block_on( outputs     ( &exec ) ), ( 5, "five".to_owned() ) );
+	assert_eq!( exec.block_on( local_output( &exec ) ), 5                   );
+}
+
+
+
+// AsyncStd: output, !Send futures and dropping the JoinHandle cancels the task.
+//
+#[ cfg( feature = "async_std" ) ]
+//
+#[ test ]
+//
+fn async_std()
+{
+	AsyncStd::block_on( async
+	{
+		assert_eq!( outputs     ( AsyncStd ).await, ( 5, "five".to_owned() ) );
+		assert_eq!( local_output( AsyncStd ).await, 5                   );
+
+		drop_cancels( AsyncStd ).await;
+	});
+}
+
+
+
+// AsyncGlobal: output.
+//
+#[ cfg( feature = "async_global" ) ]
+//
+#[ test ]
+//
+fn async_global()
+{
+	AsyncGlobal::block_on( async
+	{
+		assert_eq!( outputs     ( AsyncGlobal ).await, ( 5, "five".to_owned() ) );
+		assert_eq!( local_output( AsyncGlobal ).await, 5                   );
+	});
+}
+
+
+
+// the spawn traits of tracing wrappers.
+//
+#[ cfg(all( feature = "tracing", feature = "threadpool" )) ]
+//
+#[ test ]
+//
+fn tracing()
+{
+	use tracing_futures::Instrument;
+
+	let exec = ThreadPool::new().expect( "create threadpool" ).instrument( tracing_crate::info_span!( "generic" ) );
+
+	assert_eq!( block_on( outputs( exec ) ), ( 5, "five".to_owned() ) );
+}
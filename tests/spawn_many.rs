@@ -0,0 +1,193 @@
+#![ cfg(all( feature = "threadpool", feature = "localpool" )) ]
+
+// Tested:
+//
+// ✔ spawn_handle_many returns the handles in the same order as the futures.
+// ✔ a fan-out of 10k tasks on ThreadPool.
+// ✔ spawn_handle_local_many spawns !Send futures.
+// ✔ spawn_many and spawn_local_many run all futures.
+// ✔ spawning fails when the executor is gone.
+// ✔ the methods are forwarded through trait objects and AnyExec.
+// ✔ an executor can override spawn_obj_many and wrappers forward to it.
+//
+mod common;
+
+use
+{
+	common  :: { *                                                   } ,
+	futures :: { executor::LocalPool, future::{ join_all, FutureObj } } ,
+	futures :: { task::SpawnError                                    } ,
+	std     :: { sync::atomic::{ AtomicUsize, Ordering::SeqCst }      } ,
+};
+
+
+
+// spawn_handle_many returns the handles in the same order as the futures.
+//
+#[ test ]
+//
+fn order()
+{
+	let exec    = ThreadPool::new().expect( "create threadpool" );
+	let handles = exec.spawn_handle_many( (0..100u32).map( |i| async move { i } ) ).expect( "spawn" );
+
+	assert_eq!( block_on( join_all( handles ) ), (0..100).collect::<Vec<_>>() );
+}
+
+
+
+// a fan-out of 10k tasks on ThreadPool.
+//
+#[ test ]
+//
+fn fan_out()
+{
+	let exec    = ThreadPool::new().expect( "create threadpool" );
+	let handles = exec.spawn_handle_many( (0..10_000u64).map( |i| async move { i } ) ).expect( "spawn" );
+
+	assert_eq!( handles.len(), 10_000 );
+	assert_eq!( block_on( join_all( handles ) ).into_iter().sum::<u64>(), 49_995_000 );
+}
+
+
+
+// spawn_handle_local_many spawns !Send futures.
+//
+#[ test ]
+//
+fn local()
+{
+	let mut pool = LocalPool::new();
+	let exec     = pool.spawner();
+
+	let handles = exec.spawn_handle_local_many( (0..10u8).map( |i|
+	{
+		let value = Rc::new(i);
+		async move { *value }
+
+	})).expect( "spawn" );
+
+	assert_eq!( pool.run_until( join_all( handles ) ), (0..10).collect::<Vec<_>>() );
+}
+
+
+
+// spawn_many and spawn_local_many run all futures.
+//
+#[ test ]
+//
+fn without_handles()
+{
+	let mut pool = LocalPool::new();
+	let exec     = pool.spawner();
+	let count    = Arc::new( AtomicUsize::new(0) );
+
+	exec.spawn_many( (0..10).map( |_|
+	{
+		let count = count.clone();
+		async move { count.fetch_add( 1, SeqCst ); }
+
+	})).expect( "spawn" );
+
+	exec.spawn_local_many( (0..10).map( |_|
+	{
+		let count = Rc::new( count.clone() );
+		async move { count.fetch_add( 1, SeqCst ); }
+
+	})).expect( "spawn" );
+
+	pool.run();
+
+	assert_eq!( count.load( SeqCst ), 20 );
+}
+
+
+
+// spawning fails when the executor is gone.
+//
+#[ test ]
+//
+fn error()
+{
+	let pool = LocalPool::new();
+	let exec = pool.spawner();
+
+	drop( pool );
+
+	assert!( exec.spawn_handle_many( vec![ async {} ] ).is_err() );
+	assert!( exec.spawn_many       ( vec![ async {} ] ).is_err() );
+}
+
+
+
+// the methods are forwarded through trait objects and AnyExec.
+//
+#[ test ]
+//
+fn forwarded()
+{
+	let boxed: Box< dyn SpawnHandle<u8> > = Box::new( ThreadPool::new().expect( "create threadpool" ) );
+	let handles = boxed.spawn_handle_many( (0..3u8).map( |i| async move { i } ) ).expect( "spawn" );
+
+	assert_eq!( block_on( join_all( handles ) ), vec![ 0, 1, 2 ] );
+
+	let any     = ExecConfig{ exec: ExecKind::ThreadPool, threads: Some(2) }.build().expect( "create AnyExec" );
+	let handles = any.spawn_handle_many( (0..3u8).map( |i| async move { i } ) ).expect( "spawn" );
+
+	assert_eq!( block_on( join_all( handles ) ), vec![ 0, 1, 2 ] );
+}
+
+
+
+// An executor that counts how often it is asked to spawn a batch.
+//
+struct Batches
+{
+	pool   : ThreadPool  ,
+	batches: AtomicUsize ,
+}
+
+
+impl Spawn for Batches
+{
+	fn spawn_obj( &self, future: FutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		self.pool.spawn_obj( future )
+	}
+}
+
+
+impl SpawnMany for Batches
+{
+	fn spawn_obj_many( &self, futures: &mut dyn Iterator< Item = FutureObj<'static, ()> > ) -> Result<(), SpawnError>
+	{
+		self.batches.fetch_add( 1, SeqCst );
+
+		for future in futures
+		{
+			self.pool.spawn_obj( future )?;
+		}
+
+		Ok(())
+	}
+}
+
+
+
+// an executor can override spawn_obj_many and wrappers forward to it.
+//
+#[ test ]
+//
+fn override_hook()
+{
+	let exec = Arc::new( Batches{ pool: ThreadPool::new().expect( "create threadpool" ), batches: AtomicUsize::new(0) } );
+
+	exec.spawn_many( (0..3).map( |_| async {} ) ).expect( "spawn" );
+
+	Bounded::new( exec.clone(), 10 ).spawn_many( (0..3).map( |_| async {} ) ).expect( "spawn" );
+
+	let dynamic: &dyn SpawnMany = &exec;
+	dynamic.spawn_many( (0..3).map( |_| async {} ) ).expect( "spawn" );
+
+	assert_eq!( exec.batches.load( SeqCst ), 3 );
+}
@@ -0,0 +1,273 @@
+#![ cfg( feature = "threadpool" ) ]
+
+// Tested:
+//
+// ✔ a failing task is restarted until it succeeds.
+// ✔ with Restart::Never the task runs once, the events report it.
+// ✔ with Restart::Always, the supervisor gives up after max restarts, with exponential backoff.
+// ✔ panics are caught and the task is restarted.
+// ✔ panics in the factory are caught and the task is restarted.
+// ✔ restarts that are older than the window don't count.
+// ✔ dropping the JoinHandle stops the supervision.
+//
+mod common;
+
+use
+{
+	common  :: { *                                                        } ,
+	futures :: { StreamExt                                                } ,
+	std     :: { sync::atomic::{ AtomicU32, Ordering::SeqCst }, time::Duration } ,
+};
+
+
+// A factory whose task fails the first `fails` times and counts it's runs.
+//
+fn flaky( fails: u32, runs: &Arc<AtomicU32> ) -> impl Fn() -> futures::future::Ready< Result<(), String> > + Send + 'static
+{
+	let runs = runs.clone();
+
+	move ||
+	{
+		let run = runs.fetch_add( 1, SeqCst );
+
+		futures::future::ready( if run < fails { Err( format!( "fail {}", run ) ) } else { Ok(()) } )
+	}
+}
+
+
+fn kinds( events: &mut futures::channel::mpsc::UnboundedReceiver<SupervisorEvent> ) -> Vec<EventKind>
+{
+	let mut out = Vec::new();
+
+	while let Ok( e ) = events.try_recv()
+	{
+		assert_eq!( &*e.name, "worker" );
+		out.push( e.kind );
+	}
+
+	out
+}
+
+
+
+// a failing task is restarted until it succeeds.
+//
+#[ test ]
+//
+fn until_success()
+{
+	let sup    = Supervisor::new( ThreadPool::new().expect( "create threadpool" ) );
+	let runs   = Arc::new( AtomicU32::new(0) );
+	let policy = RestartPolicy::on_failure().backoff( Duration::from_millis(1), Duration::from_millis(1) );
+
+	let handle = sup.supervise( "worker", policy, flaky( 3, &runs ) ).expect( "spawn" );
+
+	assert_eq!( block_on( handle ), TaskExit::Completed );
+	assert_eq!( runs.load( SeqCst ), 4 );
+}
+
+
+
+// with Restart::Never the task runs once, the events report it.
+//
+#[ test ]
+//
+fn never()
+{
+	let sup        = Supervisor::new( ThreadPool::new().expect( "create threadpool" ) );
+	let mut events = sup.events();
+	let runs       = Arc::new( AtomicU32::new(0) );
+
+	let handle = sup.supervise( "worker", RestartPolicy::never(), flaky( 1, &runs ) ).expect( "spawn" );
+
+	assert_eq!( block_on( handle ), TaskExit::Failed( "fail 0".to_string() ) );
+	assert_eq!( runs.load( SeqCst ), 1 );
+
+	assert_eq!( kinds( &mut events ), vec!
+	[
+		EventKind::Started{ attempt: 0 }                               ,
+		EventKind::Exited( TaskExit::Failed( "fail 0".to_string() ) ) ,
+	]);
+}
+
+
+
+// with Restart::Always, the supervisor gives up after max restarts, with exponential backoff.
+//
+#[ test ]
+//
+fn give_up()
+{
+	let clock      = MockClock::new();
+	let sup        = Supervisor::new( clock.track( ThreadPool::new().expect( "create threadpool" ) ) );
+	let mut events = sup.events();
+	let runs       = Arc::new( AtomicU32::new(0) );
+
+	clock.set_auto_advance( true );
+
+	let policy = RestartPolicy::always()
+
+		.max_restarts( 3, Duration::from_secs( 3600 )                  )
+		.backoff     ( Duration::from_secs(1), Duration::from_secs( 3 ) )
+	;
+
+	let handle = sup.supervise( "worker", policy, flaky( 0, &runs ) ).expect( "spawn" );
+
+	assert_eq!( block_on( handle ), TaskExit::Completed );
+	assert_eq!( runs.load( SeqCst ), 4 );
+
+	// 1 + 2 + 3 (capped)
+	//
+	assert_eq!( clock.elapsed(), Duration::from_secs( 6 ) );
+
+	let kinds = kinds( &mut events );
+
+	assert_eq!( kinds.last(), Some( &EventKind::GaveUp ) );
+
+	let delays: Vec<_> = kinds.iter().filter_map( |k| match k
+	{
+		EventKind::Restarting{ delay } => Some( delay.as_secs() ),
+		_                              => None,
+
+	}).collect();
+
+	assert_eq!( delays, vec![ 1, 2, 3 ] );
+}
+
+
+
+// panics are caught and the task is restarted.
+//
+#[ test ]
+//
+fn panics()
+{
+	let sup        = Supervisor::new( ThreadPool::new().expect( "create threadpool" ) );
+	let mut events = sup.events();
+	let runs       = Arc::new( AtomicU32::new(0) );
+	let runs2      = runs.clone();
+	let policy     = RestartPolicy::on_failure().backoff( Duration::ZERO, Duration::ZERO );
+
+	let handle = sup.supervise( "worker", policy, move ||
+	{
+		let run = runs2.fetch_add( 1, SeqCst );
+
+		async move
+		{
+			if run == 0 { panic!( "boom" ); }
+
+			Ok::<(), String>(())
+		}
+
+	}).expect( "spawn" );
+
+	assert_eq!( block_on( handle ), TaskExit::Completed );
+	assert_eq!( runs.load( SeqCst ), 2 );
+	assert!( kinds( &mut events ).contains( &EventKind::Exited( TaskExit::Panicked( "boom".to_string() ) ) ) );
+}
+
+
+
+// panics in the factory are caught and the task is restarted.
+//
+#[ test ]
+//
+fn factory_panics()
+{
+	let sup        = Supervisor::new( ThreadPool::new().expect( "create threadpool" ) );
+	let mut events = sup.events();
+	let runs       = Arc::new( AtomicU32::new(0) );
+	let runs2      = runs.clone();
+	let policy     = RestartPolicy::on_failure().backoff( Duration::ZERO, Duration::ZERO );
+
+	let handle = sup.supervise( "worker", policy, move ||
+	{
+		if runs2.fetch_add( 1, SeqCst ) == 0 { panic!( "no future" ); }
+
+		futures::future::ready( Ok::<(), String>(()) )
+
+	}).expect( "spawn" );
+
+	assert_eq!( block_on( handle ), TaskExit::Completed );
+	assert_eq!( runs.load( SeqCst ), 2 );
+	assert!( kinds( &mut events ).contains( &EventKind::Exited( TaskExit::Panicked( "no future".to_string() ) ) ) );
+}
+
+
+
+// restarts that are older than the window don't count.
+//
+#[ test ]
+//
+fn window()
+{
+	let clock    = MockClock::new();
+	let exec     = clock.track( ThreadPool::new().expect( "create threadpool" ) );
+	let sup      = Supervisor::new( exec.clone() );
+	let runs     = Arc::new( AtomicU32::new(0) );
+	let runs2    = runs.clone();
+
+	clock.set_auto_advance( true );
+
+	let policy = RestartPolicy::on_failure()
+
+		.max_restarts( 1, Duration::from_secs( 10 )                     )
+		.backoff     ( Duration::from_secs( 1 ), Duration::from_secs( 1 ) )
+	;
+
+	// Each run fails after 20 seconds, so there is never more than one restart in the window.
+	//
+	let handle = sup.supervise( "worker", policy, move ||
+	{
+		let run   = runs2.fetch_add( 1, SeqCst );
+		let sleep = exec.sleep( Duration::from_secs( 20 ) );
+
+		async move
+		{
+			sleep.await;
+
+			if run < 5 { Err( "fail" ) } else { Ok(()) }
+		}
+
+	}).expect( "spawn" );
+
+	assert_eq!( block_on( handle ), TaskExit::Completed );
+	assert_eq!( runs.load( SeqCst ), 6 );
+}
+
+
+
+// dropping the JoinHandle stops the supervision.
+//
+#[ test ]
+//
+fn drop_handle()
+{
+	let sup          = Supervisor::new( ThreadPool::new().expect( "create threadpool" ) );
+	let mut events   = sup.events();
+	let runs         = Arc::new( AtomicU32::new(0) );
+	let runs2        = runs.clone();
+	let (mut tx, rx) = oneshot::channel::<()>();
+	let rx           = Arc::new( std::sync::Mutex::new( Some(rx) ) );
+
+	let handle = sup.supervise( "worker", RestartPolicy::always(), move ||
+	{
+		runs2.fetch_add( 1, SeqCst );
+		let rx = rx.lock().unwrap().take();
+
+		async move
+		{
+			if let Some( rx ) = rx { let _ = rx.await; }
+
+			futures::future::pending::<Result<(), String>>().await
+		}
+
+	}).expect( "spawn" );
+
+	assert_eq!( block_on( events.next() ).map( |e| e.kind ), Some( EventKind::Started{ attempt: 0 } ) );
+
+	drop( handle );
+	block_on( tx.cancellation() );
+
+	assert_eq!( runs.load( SeqCst ), 1 );
+}
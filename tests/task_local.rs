@@ -0,0 +1,189 @@
+#![ cfg(all( feature = "localpool", feature = "threadpool" )) ]
+
+// Tested:
+//
+// ✔ the value is visible within the scope, across await points, and not outside.
+// ✔ nested scopes override the value and restore it afterwards, other keys stay visible.
+// ✔ tasks spawned without Inherit don't see the values.
+// ✔ Inherit passes the values on to tasks on a threadpool.
+// ✔ Inherit passes the values on to !Send tasks on a LocalPool.
+// ✔ a child overriding a value doesn't affect the parent.
+// ✔ Inherit works on TokioTp.
+// ✔ Inherit works on AsyncStd.
+//
+mod common;
+
+use
+{
+	common  :: { *                                            } ,
+	futures :: { executor::LocalPool, future::ready           } ,
+};
+
+
+task_local!
+{
+	static REQUEST_ID: u64;
+	static USER      : String;
+}
+
+
+// Returns the values seen by a task.
+//
+fn snapshot() -> ( Option<u64>, Option<String> )
+{
+	( REQUEST_ID.try_with( |id| *id ).ok(), USER.try_with( String::clone ).ok() )
+}
+
+
+
+// the value is visible within the scope, across await points, and not outside.
+//
+#[ test ]
+//
+fn scope()
+{
+	let id = block_on( REQUEST_ID.scope( 3, async
+	{
+		ready(()).await;
+		REQUEST_ID.get()
+	}));
+
+	assert_eq!( id, 3 );
+	assert_eq!( REQUEST_ID.try_with( |id| *id ), Err( AccessError ) );
+}
+
+
+
+// nested scopes override the value and restore it afterwards, other keys stay visible.
+//
+#[ test ]
+//
+fn nested()
+{
+	block_on( REQUEST_ID.scope( 1, USER.scope( "ann".to_string(), async
+	{
+		let inner = REQUEST_ID.scope( 2, async { snapshot() } ).await;
+
+		assert_eq!( inner     , ( Some(2), Some( "ann".to_string() ) ) );
+		assert_eq!( snapshot(), ( Some(1), Some( "ann".to_string() ) ) );
+	})));
+}
+
+
+
+// tasks spawned without Inherit don't see the values.
+//
+#[ test ]
+//
+fn not_inherited()
+{
+	let exec = ThreadPool::new().expect( "create threadpool" );
+
+	let seen = block_on( REQUEST_ID.scope( 1, async
+	{
+		exec.spawn_handle( async { snapshot() } ).expect( "spawn" ).await
+	}));
+
+	assert_eq!( seen, ( None, None ) );
+}
+
+
+
+// Inherit passes the values on to tasks on a threadpool.
+//
+#[ test ]
+//
+fn inherit_threadpool()
+{
+	let exec = Inherit::new( ThreadPool::new().expect( "create threadpool" ) );
+
+	let seen = block_on( REQUEST_ID.scope( 1, USER.scope( "ann".to_string(), async
+	{
+		exec.spawn_handle( async { snapshot() } ).expect( "spawn" ).await
+	})));
+
+	assert_eq!( seen, ( Some(1), Some( "ann".to_string() ) ) );
+}
+
+
+
+// Inherit passes the values on to !Send tasks on a LocalPool.
+//
+#[ test ]
+//
+fn inherit_local()
+{
+	let mut pool = LocalPool::new();
+	let exec     = Inherit::new( pool.spawner() );
+
+	let seen = pool.run_until( REQUEST_ID.scope( 1, async
+	{
+		exec.spawn_handle_local( async
+		{
+			let _not_send = Rc::new(());
+			snapshot()
+
+		}).expect( "spawn" ).await
+	}));
+
+	assert_eq!( seen, ( Some(1), None ) );
+}
+
+
+
+// a child overriding a value doesn't affect the parent.
+//
+#[ test ]
+//
+fn child_override()
+{
+	let exec = Inherit::new( ThreadPool::new().expect( "create threadpool" ) );
+
+	block_on( REQUEST_ID.scope( 1, async
+	{
+		let child = exec.spawn_handle( REQUEST_ID.scope( 2, async { REQUEST_ID.get() } ) ).expect( "spawn" );
+
+		assert_eq!( child.await, 2 );
+		assert_eq!( REQUEST_ID.get(), 1 );
+	}));
+}
+
+
+
+// Inherit works on TokioTp.
+//
+#[ cfg( feature = "tokio_tp" ) ]
+//
+#[ test ]
+//
+fn inherit_tokio_tp()
+{
+	let exec = Inherit::new( TokioTpBuilder::new().build().expect( "create tokio threadpool" ) );
+
+	let seen = exec.inner().block_on( REQUEST_ID.scope( 4, async
+	{
+		exec.spawn_handle( async { snapshot() } ).expect( "spawn" ).await
+	}));
+
+	assert_eq!( seen, ( Some(4), None ) );
+}
+
+
+
+// Inherit works on AsyncStd.
+//
+#[ cfg( feature = "async_std" ) ]
+//
+#[ test ]
+//
+fn inherit_async_std()
+{
+	let exec = Inherit::new( AsyncStd );
+
+	let seen = AsyncStd::block_on( REQUEST_ID.scope( 5, async
+	{
+		exec.spawn_handle( async { snapshot() } ).expect( "spawn" ).await
+	}));
+
+	assert_eq!( seen, ( Some(5), None ) );
+}
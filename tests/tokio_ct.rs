@@ -22,14 +22,18 @@
 //
 // ✔ we can spawn without being in a future running on block_on.
 // ✔ Joinhandle::detach allows task to keep running.
+// ✔ Timer works from inside and outside of the runtime.
+//
+// ✔ tasks from Spawn, LocalSpawn, SpawnHandle and LocalSpawnHandle interleave fairly, for both placements.
+// ✔ Send futures go into the LocalSet by default, and on the runtime with SendPlacement::Runtime.
 //
 mod common;
 
 use
 {
-	common          :: * ,
-	futures         :: { channel::{ mpsc }, StreamExt } ,
-	std             :: { rc::Rc                       } ,
+	common          :: *                                                                 ,
+	futures         :: { channel::{ mpsc }, StreamExt, future::join4                    } ,
+	std             :: { rc::Rc, sync::Mutex, panic::catch_unwind                        } ,
 };
 
 
@@ -427,3 +431,134 @@ fn join_handle_detach()
 		assert_eq!( out_rx.await, Ok(5) );
 	});
 }
+
+
+
+// Timer works from inside and outside of the runtime.
+//
+#[ test ]
+//
+fn timer()
+{
+	let exec  = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+	let start = std::time::Instant::now();
+	let sleep = exec.sleep( std::time::Duration::from_millis(10) );
+
+	exec.block_on( async
+	{
+		sleep.await;
+		exec.sleep( std::time::Duration::from_millis(10) ).await;
+	});
+
+	assert!( start.elapsed() >= std::time::Duration::from_millis(20) );
+}
+
+
+
+// How many times each task records itself in the fairness tests.
+//
+const ROUNDS: usize = 20;
+
+
+// Record the id in the log and yield, ROUNDS times.
+//
+async fn record( id: usize, log: Arc<Mutex<Vec<usize>>> )
+{
+	for _ in 0..ROUNDS
+	{
+		log.lock().expect( "lock log" ).push( id );
+
+		tokio::task::yield_now().await;
+	}
+}
+
+
+// Spawn one task with each of the four traits and run them all to completion.
+//
+fn interleave( exec: &TokioCt ) -> Vec<usize>
+{
+	let log          = Arc::new( Mutex::new( Vec::new() ) );
+	let (tx0, rx0)   = oneshot::channel();
+	let (tx1, rx1)   = oneshot::channel();
+	let (log0, log1) = ( log.clone(), log.clone() );
+
+	exec.spawn      ( async move { record( 0, log0 ).await; tx0.send(()).expect( "send done" ); } ).expect( "spawn" );
+	exec.spawn_local( async move { record( 1, log1 ).await; tx1.send(()).expect( "send done" ); } ).expect( "spawn" );
+
+	let h2 = exec.spawn_handle      ( record( 2, log.clone() ) ).expect( "spawn" );
+	let h3 = exec.spawn_handle_local( record( 3, log.clone() ) ).expect( "spawn" );
+
+	let (done0, done1, (), ()) = exec.block_on( join4( rx0, rx1, h2, h3 ) );
+
+	done0.expect( "task 0 done" );
+	done1.expect( "task 1 done" );
+
+	let log = log.lock().expect( "lock log" ).clone();
+	log
+}
+
+
+// No task gets ahead of another by more than one round at any point in the log.
+//
+fn assert_fair( log: &[usize] )
+{
+	let mut counts = [0; 4];
+
+	for &id in log
+	{
+		counts[id] += 1;
+
+		let max = counts.iter().max().expect( "max" );
+		let min = counts.iter().min().expect( "min" );
+
+		assert!( max - min <= 1, "unfair scheduling: {:?}", log );
+	}
+
+	assert_eq!( counts, [ROUNDS; 4] );
+}
+
+
+
+// tasks from Spawn, LocalSpawn, SpawnHandle and LocalSpawnHandle interleave fairly, for both placements.
+//
+#[ test ]
+//
+fn fair_interleaving()
+{
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	assert_eq!( exec.send_placement(), SendPlacement::LocalSet );
+	assert_fair( &interleave( &exec ) );
+
+	let exec = TokioCtBuilder::new().send_placement( SendPlacement::Runtime ).build().expect( "create tokio current thread" );
+
+	assert_eq!( exec.send_placement(), SendPlacement::Runtime );
+	assert_fair( &interleave( &exec ) );
+}
+
+
+
+// Send futures go into the LocalSet by default, and on the runtime with SendPlacement::Runtime.
+//
+#[ test ]
+//
+fn send_placement()
+{
+	// spawn_local panics outside of a LocalSet.
+	//
+	let in_local_set = || catch_unwind( || drop( tokio::task::spawn_local( async {} ) ) ).is_ok();
+
+	let exec = TokioCtBuilder::new().build().expect( "create tokio current thread" );
+
+	let spawned = exec.spawn_handle( async move { in_local_set() } ).expect( "spawn" );
+
+	assert!( exec.block_on( spawned ) );
+
+	let exec = TokioCtBuilder::new().send_placement( SendPlacement::Runtime ).build().expect( "create tokio current thread" );
+
+	let spawned = exec.spawn_handle( async move { in_local_set() } ).expect( "spawn" );
+	let local   = exec.spawn_handle_local( async move { in_local_set() } ).expect( "spawn" );
+
+	assert!( !exec.block_on( spawned ) );
+	assert!(  exec.block_on( local   ) );
+}
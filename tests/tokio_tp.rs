@@ -13,6 +13,7 @@
 // ✔ pass a builder with some config set.
 //
 // ✔ Joinhandle::detach allows task to keep running.
+// ✔ Timer works from inside and outside of the runtime.
 //
 mod common;
 
@@ -210,3 +211,24 @@ fn join_handle_detach()
 		assert_eq!( out_rx.await, Ok(5) );
 	});
 }
+
+
+
+// Timer works from inside and outside of the runtime.
+//
+#[ test ]
+//
+fn timer()
+{
+	let exec  = TokioTpBuilder::new().build().expect( "create tokio threadpool" );
+	let start = std::time::Instant::now();
+	let sleep = exec.sleep( std::time::Duration::from_millis(10) );
+
+	exec.block_on( async
+	{
+		sleep.await;
+		exec.sleep( std::time::Duration::from_millis(10) ).await;
+	});
+
+	assert!( start.elapsed() >= std::time::Duration::from_millis(20) );
+}
@@ -0,0 +1,171 @@
+#![ cfg( feature = "localpool" ) ]
+
+// Tested:
+//
+// ✔ WithHandle returns the output of a future spawned on a third-party executor.
+// ✔ dropping the JoinHandle cancels the task.
+// ✔ a detached task runs to completion.
+// ✔ WithLocalHandle spawns !Send futures.
+// ✔ errors from the wrapped executor are passed on.
+//
+mod common;
+
+use
+{
+	common  :: { *                                                    } ,
+	futures :: { executor::LocalPool, future::{ FutureObj, LocalFutureObj, pending }, task::{ SpawnError, LocalSpawn } } ,
+	std     :: { cell::RefCell                                        } ,
+};
+
+
+// An executor that only implements the futures traits, by queueing tasks and running them on a LocalPool
+// when asked to.
+//
+#[ derive( Clone, Default ) ]
+//
+struct ThirdParty
+{
+	sends : Rc<RefCell< Vec< FutureObj<'static, ()>      > >>,
+	locals: Rc<RefCell< Vec< LocalFutureObj<'static, ()> > >>,
+}
+
+
+impl Spawn for ThirdParty
+{
+	fn spawn_obj( &self, future: FutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		self.sends.borrow_mut().push( future );
+		Ok(())
+	}
+}
+
+
+impl LocalSpawn for ThirdParty
+{
+	fn spawn_local_obj( &self, future: LocalFutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		self.locals.borrow_mut().push( future );
+		Ok(())
+	}
+}
+
+
+impl ThirdParty
+{
+	// Move the queued tasks to the pool.
+	//
+	fn flush( &self, pool: &LocalPool )
+	{
+		let spawner = pool.spawner();
+
+		for task in self.sends .borrow_mut().drain(..) { spawner.spawn_obj      ( task ).expect( "spawn" ); }
+		for task in self.locals.borrow_mut().drain(..) { spawner.spawn_local_obj( task ).expect( "spawn" ); }
+	}
+}
+
+
+// An executor that is always shut down.
+//
+struct Closed;
+
+impl Spawn for Closed
+{
+	fn spawn_obj( &self, _: FutureObj<'static, ()> ) -> Result<(), SpawnError>
+	{
+		Err( SpawnError::shutdown() )
+	}
+}
+
+
+
+// WithHandle returns the output of a future spawned on a third-party executor.
+//
+#[ test ]
+//
+fn output()
+{
+	let mut pool = LocalPool::new();
+	let exec     = WithHandle::new( ThirdParty::default() );
+	let handle   = exec.spawn_handle( async { 5u8 } ).expect( "spawn" );
+
+	exec.inner().flush( &pool );
+
+	assert_eq!( pool.run_until( handle ), 5 );
+}
+
+
+
+// dropping the JoinHandle cancels the task.
+//
+#[ test ]
+//
+fn drop_cancels()
+{
+	let mut pool         = LocalPool::new();
+	let exec             = WithHandle::new( ThirdParty::default() );
+	let (guard, drop_rx) = oneshot::channel::<()>();
+
+	let handle = exec.spawn_handle( async move
+	{
+		let _guard = guard;
+		pending::<()>().await;
+
+	}).expect( "spawn" );
+
+	exec.inner().flush( &pool );
+	pool.run_until_stalled();
+
+	drop( handle );
+
+	assert!( pool.run_until( drop_rx ).is_err() );
+}
+
+
+
+// a detached task runs to completion.
+//
+#[ test ]
+//
+fn detach()
+{
+	let mut pool = LocalPool::new();
+	let exec     = WithHandle::new( ThirdParty::default() );
+	let (tx, rx) = oneshot::channel();
+
+	exec.spawn_handle( async move { tx.send( 5u8 ).expect( "send" ); } ).expect( "spawn" ).detach();
+
+	exec.inner().flush( &pool );
+
+	assert_eq!( pool.run_until( rx ), Ok(5) );
+}
+
+
+
+// WithLocalHandle spawns !Send futures.
+//
+#[ test ]
+//
+fn local()
+{
+	let mut pool = LocalPool::new();
+	let exec     = WithLocalHandle::new( ThirdParty::default() );
+	let value    = Rc::new( 5u8 );
+	let handle   = exec.spawn_handle_local( async move { *value } ).expect( "spawn" );
+
+	exec.inner().flush( &pool );
+
+	assert_eq!( pool.run_until( handle ), 5 );
+}
+
+
+
+// errors from the wrapped executor are passed on.
+//
+#[ test ]
+//
+fn error()
+{
+	let exec = WithHandle::new( Closed );
+
+	assert!( exec.spawn_handle( async {} ).is_err() );
+}